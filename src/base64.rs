@@ -0,0 +1,125 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Base64 encoding (RFC 4648), for the few places OpenRTB values are encoded.
+
+const STANDARD: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// Encodes bytes with the standard alphabet and padding.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    encode_with(bytes, STANDARD, true)
+}
+
+// Encodes bytes with the URL and filename safe alphabet, without padding.
+pub(crate) fn encode_url_safe(bytes: &[u8]) -> String {
+    encode_with(bytes, URL_SAFE, false)
+}
+
+// Encodes six bit values with the URL and filename safe alphabet.
+#[cfg(test)]
+pub(crate) fn encode_sextets(sextets: &[u8]) -> String {
+    sextets.iter().map(|&sextet| URL_SAFE[sextet as usize] as char).collect()
+}
+
+// Decodes either alphabet, with or without padding.
+pub(crate) fn decode(s: &str) -> Result<Vec<u8>, char> {
+    let mut bytes = Vec::with_capacity(s.len() * 3 / 4);
+    let mut group = 0u32;
+    let mut bits = 0;
+
+    for sextet in decode_sextets(s)? {
+        group = group << 6 | sextet as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((group >> bits) as u8);
+        }
+    }
+
+    Ok(bytes)
+}
+
+// Decodes either alphabet, with or without padding, into the six bit value
+// of each character, for bit fields that do not end on a byte boundary.
+pub(crate) fn decode_sextets(s: &str) -> Result<Vec<u8>, char> {
+    s.trim_end_matches('=')
+        .chars()
+        .map(|c| match c {
+            'A'..='Z' => Ok(c as u8 - b'A'),
+            'a'..='z' => Ok(c as u8 - b'a' + 26),
+            '0'..='9' => Ok(c as u8 - b'0' + 52),
+            '+' | '-' => Ok(62),
+            '/' | '_' => Ok(63),
+            _ => Err(c),
+        })
+        .collect()
+}
+
+fn encode_with(bytes: &[u8], alphabet: &[u8; 64], padding: bool) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let group = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(alphabet[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else if padding {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VECTORS: [(&[u8], &str); 7] = [
+        (b"", ""),
+        (b"f", "Zg=="),
+        (b"fo", "Zm8="),
+        (b"foo", "Zm9v"),
+        (b"foob", "Zm9vYg=="),
+        (b"fooba", "Zm9vYmE="),
+        (b"foobar", "Zm9vYmFy"),
+    ];
+
+    #[test]
+    fn encode_rfc_4648_vectors() {
+        for (bytes, encoded) in VECTORS.iter() {
+            assert_eq!(encode(bytes), *encoded);
+            assert_eq!(encode_url_safe(bytes), encoded.trim_end_matches('='));
+        }
+        assert_eq!(encode(&[0xfb, 0xff]), "+/8=");
+        assert_eq!(encode_url_safe(&[0xfb, 0xff]), "-_8");
+    }
+
+    #[test]
+    fn decode_rfc_4648_vectors() {
+        for (bytes, encoded) in VECTORS.iter() {
+            assert_eq!(decode(encoded).unwrap(), *bytes);
+            assert_eq!(decode(encoded.trim_end_matches('=')).unwrap(), *bytes);
+        }
+        assert_eq!(decode("+/8=").unwrap(), vec![0xfb, 0xff]);
+        assert_eq!(decode("-_8").unwrap(), vec![0xfb, 0xff]);
+        assert_eq!(decode("Zm9v!"), Err('!'));
+    }
+
+    #[test]
+    fn sextets() {
+        assert_eq!(decode_sextets("Az-_+/=").unwrap(), vec![0, 51, 62, 63, 62, 63]);
+        assert_eq!(encode_sextets(&[0, 51, 62, 63]), "Az-_");
+        assert_eq!(decode_sextets("A!"), Err('!'));
+    }
+}
@@ -16,12 +16,17 @@ extern crate serde_json;
 
 #[macro_use]
 mod macros;
+mod base64;
 mod serde_utils;
 
 pub mod native;
+pub mod openrtb3;
+pub mod price_encryption;
+pub mod privacy;
 pub mod v2_5;
+pub mod v2_6;
 
-pub use v2_5 as current;
+pub use v2_6 as current;
 
 #[cfg(test)]
 mod tests {
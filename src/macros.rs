@@ -76,3 +76,61 @@ macro_rules! enum_list_module {
         }
     }
 }
+
+// Same as `enum_list_module!`, but for lists that leave room for values the
+// specification does not define (e.g. exchange-specific values above 500).
+// Such values are kept in an `Unknown` variant instead of being rejected, so
+// they survive a round trip.
+macro_rules! open_enum_list_module {
+    ( $enum_name:ident $variant_type:ty :
+      $(
+          $variant_name:ident $variant_value:expr
+       ),*
+    ) => {
+        use serde;
+
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub enum $enum_name {
+            $($variant_name,)*
+            Unknown($variant_type),
+        }
+
+        impl From<$variant_type> for $enum_name {
+            fn from(value: $variant_type) -> $enum_name {
+                match value {
+                    $( $variant_value => $enum_name::$variant_name, )*
+                    _ => $enum_name::Unknown(value),
+                }
+            }
+        }
+
+        impl From<$enum_name> for $variant_type {
+            fn from(value: $enum_name) -> $variant_type {
+                match value {
+                    $( $enum_name::$variant_name => $variant_value, )*
+                    $enum_name::Unknown(x) => x,
+                }
+            }
+        }
+
+        impl serde::Serialize for $enum_name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                let x: $variant_type = self.clone().into();
+                serde::Serialize::serialize(&x, serializer)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $enum_name {
+            fn deserialize<D>(deserializer: D) -> Result<$enum_name, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let x: $variant_type = serde::Deserialize::deserialize(deserializer)?;
+                Ok(x.into())
+            }
+        }
+    }
+}
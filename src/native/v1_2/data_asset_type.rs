@@ -0,0 +1,59 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// 7.4 Data Asset Types
+//
+// Values of 500 and above are reserved for exchange-specific usage.
+open_enum_list_module! {
+    DataAssetType u16:
+        Sponsored    1,
+        Description  2,
+        Rating       3,
+        Likes        4,
+        Downloads    5,
+        Price        6,
+        SalePrice    7,
+        Phone        8,
+        Address      9,
+        Description2 10,
+        DisplayUrl   11,
+        CtaText      12
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialize_good() {
+        let x = DataAssetType::Sponsored;
+        let expected = "1";
+        let serialized = serde_json::to_string(&x).unwrap();
+
+        assert_eq!(expected, serialized)
+    }
+
+    #[test]
+    fn deserialize_good() {
+        let serialized = "12";
+        let expected = DataAssetType::CtaText;
+        let x = serde_json::from_str(serialized).unwrap();
+
+        assert_eq!(expected, x)
+    }
+
+    #[test]
+    fn deserialize_exchange_specific() {
+        let serialized = "500";
+        let x: DataAssetType = serde_json::from_str(serialized).unwrap();
+
+        assert_eq!(DataAssetType::Unknown(500), x);
+        assert_eq!("500", serde_json::to_string(&x).unwrap());
+    }
+}
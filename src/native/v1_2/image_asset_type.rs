@@ -0,0 +1,50 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// 7.5 Image Asset Types
+//
+// Values of 500 and above are reserved for exchange-specific usage.
+open_enum_list_module! {
+    ImageAssetType u16:
+        Icon 1,
+        Logo 2,
+        Main 3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialize_good() {
+        let x = ImageAssetType::Main;
+        let expected = "3";
+        let serialized = serde_json::to_string(&x).unwrap();
+
+        assert_eq!(expected, serialized)
+    }
+
+    #[test]
+    fn deserialize_good() {
+        let serialized = "1";
+        let expected = ImageAssetType::Icon;
+        let x = serde_json::from_str(serialized).unwrap();
+
+        assert_eq!(expected, x)
+    }
+
+    #[test]
+    fn deserialize_exchange_specific() {
+        let serialized = "501";
+        let x: ImageAssetType = serde_json::from_str(serialized).unwrap();
+
+        assert_eq!(ImageAssetType::Unknown(501), x);
+        assert_eq!("501", serde_json::to_string(&x).unwrap());
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::error;
+use std::fmt::{self, Display};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+// Native 1.0 and 1.1 wrapped both the request and the response in a
+// `{"native": ...}` object. Version 1.2 dropped it, but some exchanges still
+// send it, so both forms are accepted when parsing.
+const WRAPPER_KEY: &str = "native";
+
+#[derive(Debug)]
+pub enum MarkupError {
+    // The markup is not valid JSON or does not match the expected object.
+    Json(serde_json::Error),
+    // There is no markup to parse, e.g. the bid has no adm.
+    MissingMarkup,
+    // The impression the markup refers to does not offer native.
+    MissingNativeRequest,
+    // The response contains an asset whose ID was not in the request.
+    UnknownAsset(u64),
+    // The response contains an asset without an ID, which embedded assets
+    // must have to be matched with those of the request.
+    MissingAssetId,
+    // The request flagged an asset as required but the response omits it.
+    MissingRequiredAsset(u64),
+}
+
+impl Display for MarkupError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MarkupError::Json(e) => write!(formatter, "invalid native markup: {}", e),
+            MarkupError::MissingMarkup => formatter.write_str("no native markup"),
+            MarkupError::MissingNativeRequest => formatter.write_str("impression has no native request"),
+            MarkupError::UnknownAsset(id) => write!(formatter, "asset {} is not in the native request", id),
+            MarkupError::MissingAssetId => formatter.write_str("native response asset has no ID"),
+            MarkupError::MissingRequiredAsset(id) => write!(formatter, "required asset {} is missing from the native response", id),
+        }
+    }
+}
+
+impl error::Error for MarkupError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            MarkupError::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for MarkupError {
+    fn from(e: serde_json::Error) -> Self {
+        MarkupError::Json(e)
+    }
+}
+
+#[derive(Serialize)]
+struct Wrapper<'a, T> {
+    native: &'a T,
+}
+
+pub(crate) fn from_markup<T: DeserializeOwned>(markup: &str) -> Result<T, MarkupError> {
+    let value = match serde_json::from_str(markup)? {
+        Value::Object(mut object) if object.len() == 1 && object.contains_key(WRAPPER_KEY) => {
+            object.remove(WRAPPER_KEY).unwrap_or(Value::Null)
+        }
+        value => value,
+    };
+
+    Ok(serde_json::from_value(value)?)
+}
+
+pub(crate) fn to_markup<T: Serialize>(value: &T) -> Result<String, MarkupError> {
+    Ok(serde_json::to_string(value)?)
+}
+
+pub(crate) fn to_wrapped_markup<T: Serialize>(value: &T) -> Result<String, MarkupError> {
+    Ok(serde_json::to_string(&Wrapper { native: value })?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Request;
+    use super::*;
+
+    #[test]
+    fn parse_unwrapped() {
+        let request: Request = from_markup(r#"{"ver":"1.2","assets":[{"id":1,"title":{"len":90}}]}"#).unwrap();
+
+        assert_eq!(request.ver, Some("1.2".to_string()));
+        assert_eq!(request.assets[0].title.as_ref().unwrap().len, 90);
+    }
+
+    #[test]
+    fn parse_wrapped() {
+        let request: Request =
+            from_markup(r#"{"native":{"ver":"1.1","assets":[{"id":1,"title":{"len":90}}]}}"#).unwrap();
+
+        assert_eq!(request.ver, Some("1.1".to_string()));
+        assert_eq!(request.assets.len(), 1);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let err = from_markup::<Request>(r#"{"assets":[{"title":{}}]}"#).unwrap_err();
+        assert!(err.to_string().starts_with("invalid native markup"));
+
+        assert!(from_markup::<Request>("not json").is_err());
+        assert!(from_markup::<Request>(r#"{"native":"1.2"}"#).is_err());
+    }
+
+    #[test]
+    fn write_wrapped() {
+        let request: Request = from_markup(r#"{"ver":"1.1"}"#).unwrap();
+
+        assert_eq!(to_markup(&request).unwrap(), r#"{"ver":"1.1"}"#);
+        assert_eq!(to_wrapped_markup(&request).unwrap(), r#"{"native":{"ver":"1.1"}}"#);
+    }
+}
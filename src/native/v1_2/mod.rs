@@ -8,6 +8,14 @@
 
 use crate::serde_utils;
 
+mod data_asset_type;
+mod image_asset_type;
+mod markup;
+
+pub use self::data_asset_type::DataAssetType;
+pub use self::image_asset_type::ImageAssetType;
+pub use self::markup::MarkupError;
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Request {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -40,6 +48,37 @@ pub struct Request {
     pub ext: Option<serde_utils::Ext>,
 }
 
+impl Request {
+    /// Parses a native request from its JSON markup, accepting both the 1.2
+    /// form and the legacy form wrapped in a `{"native": ...}` object.
+    pub fn from_markup(markup: &str) -> Result<Request, MarkupError> {
+        markup::from_markup(markup)
+    }
+
+    /// Serializes the request in the 1.2 form.
+    pub fn to_markup(&self) -> Result<String, MarkupError> {
+        markup::to_markup(self)
+    }
+
+    /// Serializes the request wrapped in a `{"native": ...}` object, as
+    /// expected by exchanges that still follow 1.0 and 1.1.
+    pub fn to_wrapped_markup(&self) -> Result<String, MarkupError> {
+        markup::to_wrapped_markup(self)
+    }
+
+    /// Returns the data asset of the given type requested by the publisher, if any.
+    pub fn data_asset(&self, type_: &DataAssetType) -> Option<&Asset> {
+        self.assets
+            .iter()
+            .find(|asset| asset.data.as_ref().map(|data| &data.type_) == Some(type_))
+    }
+
+    /// Returns the assets the publisher flagged as required.
+    pub fn required_assets(&self) -> impl Iterator<Item = &Asset> {
+        self.assets.iter().filter(|asset| asset.is_required())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Asset {
     pub id: u64,
@@ -57,6 +96,12 @@ pub struct Asset {
     pub ext: Option<serde_utils::Ext>,
 }
 
+impl Asset {
+    pub fn is_required(&self) -> bool {
+        self.required == Some(1)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Title {
     pub len: u64,
@@ -67,7 +112,7 @@ pub struct Title {
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Image {
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
-    pub type_: Option<u64>,
+    pub type_: Option<ImageAssetType>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub w: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -82,11 +127,46 @@ pub struct Image {
     pub ext: Option<serde_utils::Ext>,
 }
 
+// 4.5 Object: Video
+//
+// The video object to be used for all video elements supported in the Native
+// Ad. This corresponds to the Video object of OpenRTB. Exchange implementers
+// can impose their own specific restrictions.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
-pub struct Video {}
+pub struct Video {
+    // Required. Content MIME types supported. Popular MIME types include,
+    // but are not limited to “video/x-ms-wmv” for Windows Media, and
+    // “video/x-flv” for Flash Video, or “video/mp4”.
+    pub mimes: Vec<String>,
+    // Minimum video ad duration in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minduration: Option<u32>,
+    // Maximum video ad duration in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxduration: Option<u32>,
+    // Required. An array of video protocols the publisher can accept in
+    // the bid response. See OpenRTB Table ‘Video Bid Response Protocols’.
+    pub protocols: Vec<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
 
+// 4.6 Object: Data
+//
+// The Data Object is to be used for all non-core elements of the native unit
+// such as Brand Name, Ratings, Review Count, Stars, Download count,
+// descriptions etc.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
-pub struct Data {}
+pub struct Data {
+    // Required. Type ID of the element supported by the publisher.
+    #[serde(rename = "type")]
+    pub type_: DataAssetType,
+    // Maximum length of the text in the element’s response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub len: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct EventTracker {
@@ -96,72 +176,272 @@ pub struct EventTracker {
     pub ext: Option<serde_utils::Ext>,
 }
 
+// 5.1 Native Markup Response Object
+//
+// The native object is the top level JSON object which identifies a native
+// response.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Response {
+    // Version of the Native Markup version in use.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ver: Option<String>,
+    // List of native ad’s assets. Required if no assetsurl.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub assets: Vec<AssetResponse>,
+    // URL of an alternate source for the assets object. The expected
+    // response is a JSON object mirroring the assets object in the bid
+    // response, subject to certain requirements as specified in the
+    // individual objects.
     #[serde(rename = "assetsurl", skip_serializing_if = "Option::is_none")]
     pub assets_url: Option<String>,
+    // URL where a dynamic creative specification may be found for
+    // populating this ad, per the Dynamic Content Ads Specification.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dcourl: Option<String>,
+    // Required. Destination Link. This is default link object for the ad.
+    // Individual assets can also have a link object which applies if the
+    // asset is activated (clicked).
     pub link: DestinationLink,
+    // Array of impression tracking URLs, expected to return a 1x1 image
+    // or 204 response - typically only passed when using 3rd party
+    // trackers. To be deprecated - replaced with eventtrackers.
     #[serde(default, rename = "imptrackers", skip_serializing_if = "Vec::is_empty")]
     pub imp_trackers: Vec<String>,
+    // Optional JavaScript impression tracker. This is a valid HTML,
+    // Javascript is already wrapped in <script> tags. It should be
+    // executed at impression time where it can be supported. To be
+    // deprecated - replaced with eventtrackers.
     #[serde(rename = "jstracker", skip_serializing_if = "Option::is_none")]
     pub js_tracker: Option<String>,
+    // Array of tracking objects to run with the ad, in response to the
+    // declared supported methods in the request.
     #[serde(
         default,
         rename = "eventtrackers",
         skip_serializing_if = "Vec::is_empty"
     )]
     pub event_trackers: Vec<EventTrackerResponse>,
+    // If support was indicated in the request, URL of a page informing
+    // the user about the buyer’s targeting activity.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub privacy: Option<String>,
+    // This object is a placeholder that may contain custom JSON agreed to
+    // by the parties to support flexibility beyond the standard defined
+    // in this specification.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ext: Option<serde_utils::Ext>,
 }
 
+impl Response {
+    /// Parses a native response from its JSON markup (e.g. a bid's adm),
+    /// accepting both the 1.2 form and the legacy form wrapped in a
+    /// `{"native": ...}` object.
+    pub fn from_markup(markup: &str) -> Result<Response, MarkupError> {
+        markup::from_markup(markup)
+    }
+
+    /// Serializes the response in the 1.2 form.
+    pub fn to_markup(&self) -> Result<String, MarkupError> {
+        markup::to_markup(self)
+    }
+
+    /// Serializes the response wrapped in a `{"native": ...}` object, as
+    /// expected by exchanges that still follow 1.0 and 1.1.
+    pub fn to_wrapped_markup(&self) -> Result<String, MarkupError> {
+        markup::to_wrapped_markup(self)
+    }
+
+    /// Checks that every asset of the response answers an asset of the
+    /// request by its ID, and that every required asset of the request is
+    /// answered. Required assets are not checked when the assets are
+    /// delivered out of band through assetsurl or dcourl.
+    pub fn check_assets(&self, request: &Request) -> Result<(), MarkupError> {
+        for asset in &self.assets {
+            let id = asset.id.ok_or(MarkupError::MissingAssetId)?;
+            if !request.assets.iter().any(|asset| asset.id == id) {
+                return Err(MarkupError::UnknownAsset(id));
+            }
+        }
+
+        if self.assets_url.is_some() || self.dcourl.is_some() {
+            return Ok(());
+        }
+
+        for required in request.required_assets() {
+            if !self.assets.iter().any(|asset| asset.id == Some(required.id)) {
+                return Err(MarkupError::MissingRequiredAsset(required.id));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn new(link: DestinationLink) -> Response {
+        Response {
+            ver: Some("1.2".to_string()),
+            assets: vec![],
+            assets_url: None,
+            dcourl: None,
+            link,
+            imp_trackers: vec![],
+            js_tracker: None,
+            event_trackers: vec![],
+            privacy: None,
+            ext: None,
+        }
+    }
+}
+
+// 5.7 Object: Link
+//
+// Used for ‘call to action’ assets, or other links from the Native ad. This
+// Object should be associated to its peer object in the parent Asset Object
+// or as the master link in the top level NativeAd response object. When that
+// peer object is activated (clicked) the action should take the user to the
+// location of the link.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
-pub struct DestinationLink {}
+pub struct DestinationLink {
+    // Required. Landing URL of the clickable link.
+    pub url: String,
+    // List of third-party tracker URLs to be fired on click of the URL.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clicktrackers: Vec<String>,
+    // Fallback URL for deeplink. To be used if the URL given in url is
+    // not supported by the device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+impl DestinationLink {
+    pub fn new(url: String) -> DestinationLink {
+        DestinationLink {
+            url,
+            clicktrackers: vec![],
+            fallback: None,
+            ext: None,
+        }
+    }
+}
 
+// The link of an individual asset is the same object as the master link of
+// the response.
+pub type LinkResponse = DestinationLink;
+
+// 5.2 Object: Asset
+//
+// Corresponds to the Asset Object in the request. The main container object
+// for each asset requested or supported by Exchange on behalf of the rendering
+// client. Any object that is required is to be flagged as such. Only one of
+// the {title,img,video,data} objects should be present in each object. All
+// others should be null/absent. The id is to be unique within the AssetObject
+// array so that the response can be aligned.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct AssetResponse {
+    // Unique asset ID, assigned by exchange, must match one of the asset
+    // IDs in request. Optional if assetsurl/dcourl is being used;
+    // required if embedded asset is being used.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<u64>,
+    // Set to 1 if asset is required (bidder requires it to be displayed).
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<TitleResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub img: Option<ImageResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub video: Option<VideoResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<DataResponse>,
+    // Link object for call to actions. The link object applies if the
+    // asset item is activated (clicked). If there is no link object on
+    // the asset, the parent link object on the bid response applies.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub link: Option<LinkResponse>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ext: Option<serde_utils::Ext>,
 }
 
+// 5.3 Object: Title
+//
+// Corresponds to the Title Object in the request, with the value filled in.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
-pub struct TitleResponse {}
+pub struct TitleResponse {
+    // Required. The text associated with the text element.
+    pub text: String,
+    // The length of the title being provided. Required if using assetsurl/
+    // dcourl representation, optional if using embedded asset
+    // representation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub len: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
 
+// 5.4 Object: Image
+//
+// Corresponds to the Image Object in the request.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct ImageResponse {
+    // Required for assetsurl or dcourl responses, not required for
+    // embedded asset responses. The type of image element being submitted.
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
-    pub type_: Option<u64>,
+    pub type_: Option<ImageAssetType>,
+    // Required. URL of the image asset.
     pub url: String,
+    // Width of the image in pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub w: Option<u64>,
+    // Height of the image in pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub h: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ext: Option<serde_utils::Ext>,
 }
 
+// 5.6 Object: Video
+//
+// Corresponds to the Video Object in the request, yet containing a value of a
+// conforming VAST tag as a value.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
-pub struct VideoResponse {}
-
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
-pub struct DataResponse {}
+pub struct VideoResponse {
+    // Required. VAST XML.
+    pub vasttag: String,
+}
 
+// 5.5 Object: Data
+//
+// Corresponds to the Data Object in the request, with the value filled in.
+// The Data Object is to be used for all miscellaneous elements of the native
+// unit such as Brand Name, Ratings, Review Count, Stars, Downloads, etc.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
-pub struct LinkResponse {}
+pub struct DataResponse {
+    // Required for assetsurl/dcourl responses, not required for embedded
+    // asset responses. The type of data element being submitted.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<DataAssetType>,
+    // Required for assetsurl/dcourl responses. The length of the data
+    // element being submitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub len: Option<u64>,
+    // Required. The formatted string of data to be displayed. Can contain
+    // a formatted value such as “5 stars” or “$10” or “3.4 stars out of 5”.
+    pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
 
+// 5.8 Object: Event Tracker Response
+//
+// The event trackers response is an array of objects and specifies the types
+// of events the bidder wishes to track and the URLs/information to track them.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct EventTrackerResponse {
     pub event: u64,
     pub method: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
     #[serde(rename = "customdata", skip_serializing_if = "Option::is_none")]
     pub custom_data: Option<serde_utils::Ext>,
@@ -0,0 +1,675 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::serde_utils;
+
+use super::{Data, Gender, OperatingSystem};
+
+// The Layer-4 context of a request. For AdCOM these are the distribution
+// channel (site or app), the user, the device, and the regulations and
+// restrictions that apply to all items of the request.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Context {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub site: Option<Site>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app: Option<App>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<User>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<Device>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regs: Option<Regs>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<Restrictions>,
+}
+
+// Object: Site
+//
+// This object is used to define an ad supported website, in contrast to a
+// non-browser application, for example.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Site {
+    // Vendor-specific unique identifier of the distribution channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    // Displayable name of the distribution channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    // Details about the publisher of the distribution channel.
+    #[serde(rename = "pub", skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<Publisher>,
+
+    // Details about the content within the distribution channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<Content>,
+
+    // Domain of the distribution channel (e.g., “mysite.foo.com”).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+
+    // Array of content categories describing the site using IDs from the
+    // taxonomy indicated in cattax.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cat: Vec<String>,
+
+    // The taxonomy in use for the cat, sectcat and pagecat attributes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cattax: Option<u32>,
+
+    // Array of content categories describing the current section of the site.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sectcat: Vec<String>,
+
+    // Array of content categories describing the current page or view of
+    // the site.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pagecat: Vec<String>,
+
+    // Indicates if the site has a privacy policy, where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub privpolicy: Option<bool>,
+
+    // Comma separated list of keywords about the site.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+
+    // URL of the page within the site.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<String>,
+
+    // Referrer URL that caused navigation to the current page.
+    #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub referrer: Option<String>,
+
+    // Search string that caused navigation to the current page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+
+    // Indicates if the site has been programmed to optimize layout when
+    // viewed on mobile devices, where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub mobile: Option<bool>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: App
+//
+// This object is used to define an ad supported non-browser application, in
+// contrast to a typical website.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct App {
+    // Vendor-specific unique identifier of the distribution channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    // Displayable name of the distribution channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    // Details about the publisher of the distribution channel.
+    #[serde(rename = "pub", skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<Publisher>,
+
+    // Details about the content within the distribution channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<Content>,
+
+    // Domain of the distribution channel (e.g., “mygame.foo.com”).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+
+    // Array of content categories describing the app using IDs from the
+    // taxonomy indicated in cattax.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cat: Vec<String>,
+
+    // The taxonomy in use for the cat, sectcat and pagecat attributes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cattax: Option<u32>,
+
+    // Array of content categories describing the current section of the app.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sectcat: Vec<String>,
+
+    // Array of content categories describing the current page or view of
+    // the app.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pagecat: Vec<String>,
+
+    // Indicates if the app has a privacy policy, where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub privpolicy: Option<bool>,
+
+    // Comma separated list of keywords about the app.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+
+    // A platform-specific application identifier intended to be unique to
+    // the app and independent of the exchange (e.g., “com.foo.mygame”).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle: Option<String>,
+
+    // App store URL for an installed app.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storeurl: Option<String>,
+
+    // Application version.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ver: Option<String>,
+
+    // Indicates if the app is a paid version, where 0 = free, 1 = paid.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub paid: Option<bool>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Publisher
+//
+// This object describes the publisher of the media in which ads will be
+// displayed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Publisher {
+    // Vendor-specific unique publisher identifier.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    // Displayable name of the publisher.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    // Highest level domain of the publisher (e.g., “publisher.com”).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+
+    // Array of content categories that describe the publisher using IDs
+    // from the taxonomy indicated in cattax.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cat: Vec<String>,
+
+    // The taxonomy in use for the cat attribute.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cattax: Option<u32>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Content
+//
+// This object describes the content in which an ad may appear, which may be
+// syndicated from a source other than the publisher’s own.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Content {
+    // ID uniquely identifying the content.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    // Episode number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub episode: Option<i64>,
+
+    // Content title.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    // Content series.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series: Option<String>,
+
+    // Content season.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub season: Option<String>,
+
+    // Artist credited with the content.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artist: Option<String>,
+
+    // Genre that best describes the content.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub genre: Option<String>,
+
+    // Album to which the content belongs; typically for audio.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub album: Option<String>,
+
+    // International Standard Recording Code conforming to ISO-3901.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub isrc: Option<String>,
+
+    // URL of the content, for buy-side contextualization or review.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    // Array of content categories describing the content using IDs from
+    // the taxonomy indicated in cattax.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cat: Vec<String>,
+
+    // The taxonomy in use for the cat attribute.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cattax: Option<u32>,
+
+    // Production quality.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prodq: Option<i64>,
+
+    // Type of content (game, video, text, etc.).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<i64>,
+
+    // Content rating (e.g., MPAA).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<String>,
+
+    // User rating of the content (e.g., number of stars, likes, etc.).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub urating: Option<String>,
+
+    // Media rating per IQG guidelines.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mrating: Option<i64>,
+
+    // Comma separated list of keywords describing the content.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+
+    // Indicator of whether or not the content is live, where 0 = not live,
+    // 1 = live.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub live: Option<i64>,
+
+    // Source relationship, where 0 = indirect, 1 = direct.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub srcrel: Option<i64>,
+
+    // Length of content in seconds; typically used for video or audio.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub len: Option<i64>,
+
+    // Content language using ISO-639-1-alpha-2.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+
+    // Indicator of whether or not the content is embeddable, where 0 = no,
+    // 1 = yes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embed: Option<i64>,
+
+    // Additional content data.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub data: Vec<Data>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Device
+//
+// This object provides information pertaining to the device through which
+// the user is interacting.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Device {
+    // The general type of device.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<u32>,
+
+    // Browser user agent string.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ua: Option<String>,
+
+    // ID sanctioned for advertiser use in the clear (i.e., not hashed).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ifa: Option<String>,
+
+    // Standard “Do Not Track” option as set in the header by the browser,
+    // where 0 = tracking is unrestricted, 1 = do not track.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub dnt: Option<bool>,
+
+    // “Limit Ad Tracking” signal commercially endorsed (e.g., iOS, Android),
+    // where 0 = tracking is unrestricted, 1 = tracking must be limited.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub lmt: Option<bool>,
+
+    // Device make (e.g., “Apple”).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub make: Option<String>,
+
+    // Device model (e.g., “iPhone10,1” when the specific device model is
+    // known, “iPhone” otherwise).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    // Device operating system.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub os: Option<OperatingSystem>,
+
+    // Device operating system version (e.g., “3.1.2”).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub osv: Option<String>,
+
+    // Hardware version of the device (e.g., “5S” for iPhone 5S).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hwv: Option<String>,
+
+    // Physical height of the screen in pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub h: Option<u64>,
+
+    // Physical width of the screen in pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub w: Option<u64>,
+
+    // Screen size as pixels per linear inch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ppi: Option<u64>,
+
+    // The ratio of physical pixels to device independent pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pxratio: Option<f64>,
+
+    // Support for JavaScript, where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub js: Option<bool>,
+
+    // Browser language using ISO-639-1-alpha-2.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+
+    // IPv4 address closest to device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+
+    // IP address closest to device as IPv6.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipv6: Option<String>,
+
+    // Carrier or ISP (e.g., “VERIZON”) using exchange curated string names
+    // which should be published to bidders a priori.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub carrier: Option<String>,
+
+    // Mobile carrier as the concatenated MCC-MNC code (e.g., “310-005”
+    // identifies Verizon Wireless CDMA in the USA).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mccmnc: Option<String>,
+
+    // Network connection type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contype: Option<u32>,
+
+    // Indicates if the geolocation API will be available to JavaScript code
+    // running in display ad, where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub geofetch: Option<bool>,
+
+    // Location of the device (i.e., typically the user’s current location).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geo: Option<Geo>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Geo
+//
+// This object encapsulates various methods for specifying a geographic
+// location.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Geo {
+    // Source of location data.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<u32>,
+
+    // Latitude from -90.0 to +90.0, where negative is south.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lat: Option<f64>,
+
+    // Longitude from -180.0 to +180.0, where negative is west.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lon: Option<f64>,
+
+    // Estimated location accuracy in meters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accur: Option<u64>,
+
+    // Number of seconds since this geolocation fix was established.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lastfix: Option<u64>,
+
+    // Service or provider used to determine geolocation from IP address if
+    // applicable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipserv: Option<u32>,
+
+    // Country code using ISO-3166-1-alpha-2.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+
+    // Region code using ISO-3166-2; 2-letter state code if USA.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+
+    // Regional marketing areas such as Nielsen’s DMA codes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metro: Option<String>,
+
+    // City using United Nations Code for Trade & Transport Locations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+
+    // ZIP or postal code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zip: Option<String>,
+
+    // Local time as the number +/- of minutes from UTC.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub utcoffset: Option<i64>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: User
+//
+// This object contains information known or derived about the human user of
+// the device (i.e., the audience for advertising).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct User {
+    // Vendor-specific ID for the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    // Buyer-specific ID for the user as mapped by an exchange for the buyer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buyeruid: Option<String>,
+
+    // Year of birth as a 4-digit integer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yob: Option<u32>,
+
+    // Gender, where “M” = male, “F” = female, “O” = known to be other.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gender: Option<Gender>,
+
+    // Comma separated list of keywords, interests, or intent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+
+    // GDPR consent string if applicable, complying with the IAB standard
+    // Consent String Format in the Transparency and Consent Framework
+    // technical specifications.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consent: Option<String>,
+
+    // Location of the user’s home base (i.e., not necessarily their current
+    // location).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geo: Option<Geo>,
+
+    // Additional user data.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub data: Vec<Data>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Regs
+//
+// This object contains any legal, governmental, or industry regulations that
+// the sender deems applicable to the request.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Regs {
+    // Flag indicating if this request is subject to the COPPA regulations
+    // established by the USA FTC, where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub coppa: Option<bool>,
+
+    // Flag that indicates whether or not the request is subject to GDPR
+    // regulations, where 0 = no, 1 = yes, and omission indicates unknown.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub gdpr: Option<bool>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Restrictions
+//
+// This object allows lists of restrictions on the ads that may be served,
+// which apply to all items of the request.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Restrictions {
+    // Block list of content categories using IDs from the taxonomy
+    // indicated in cattax.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bcat: Vec<String>,
+
+    // The taxonomy in use for the bcat attribute.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cattax: Option<u32>,
+
+    // Block list of advertisers by their domains (e.g., “ford.com”).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub badv: Vec<String>,
+
+    // Block list of apps by their platform-specific exchange-independent
+    // application identifiers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bapp: Vec<String>,
+
+    // Block list of creative attributes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub battr: Vec<u64>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialization_skip_fields() {
+        let c = Context {
+            site: Some(Site::default()),
+            ..Default::default()
+        };
+
+        let expected = r#"{"site":{}}"#;
+        let serialized = serde_json::to_string(&c).unwrap();
+
+        assert_eq!(expected, serialized)
+    }
+
+    #[test]
+    fn deserialize_device() {
+        let serialized = r#"{"type":4,"ua":"Mozilla/5.0","lmt":1,"os":13,"osv":"17.1","geo":{"type":2,"accur":20,"utcoffset":-300}}"#;
+        let d: Device = serde_json::from_str(serialized).unwrap();
+
+        assert_eq!(d.type_, Some(4));
+        assert_eq!(d.lmt, Some(true));
+        assert_eq!(d.os, Some(OperatingSystem::Ios));
+        assert_eq!(d.geo.as_ref().unwrap().utcoffset, Some(-300));
+        assert_eq!(serialized, serde_json::to_string(&d).unwrap());
+    }
+}
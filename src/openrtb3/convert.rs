@@ -0,0 +1,1119 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Conversions between the OpenRTB 2.5 and 3.0 models.
+//
+// An OpenRTB 2.5 request becomes a 3.0 request whose impressions are items
+// offering an AdCOM placement, and whose site, app, device, user and
+// regulations make up the context. The request-level block lists, together
+// with the blocked creative attributes of all impressions, become the
+// restrictions of the context. Native requests are parsed into the asset
+// formats of a display placement; their event trackers, and native requests
+// that do not parse, are left out, as are banner ad types and video and audio
+// companions. Values out of the range of their 3.0 field are dropped, and
+// reported by their JSON path, e.g. `$.imp[0].native.request.context`.
+//
+// An OpenRTB 3.0 response becomes a 2.5 response by flattening the ad of each
+// bid into it, after expanding the bid's macros in its markup and notice
+// URLs and renaming the standard macros to their 2.5 names. A structured
+// native ad becomes native markup. Values 2.5 has no place for are kept in
+// the ext of the bid, with what is left of the ad under ext.ad.
+
+use std::convert::TryFrom;
+use std::error;
+use std::fmt::{self, Display};
+
+use crate::native::v1_2;
+use crate::serde_utils::{
+    put_ext, remove_ext, take_ext, EXT_CONSENT, EXT_GDPR, EXT_GPP, EXT_GPP_SID, EXT_SCHAIN, EXT_US_PRIVACY,
+};
+use crate::v2_5::{self, Category};
+
+use super::{
+    Ad, App, AssetFormat, AudioPlacement, Bid, Content, Context, DataAssetFormat, Deal, Device, DisplayFormat,
+    DisplayPlacement, Geo, ImageAssetFormat, Item, LinkAsset, Native, NativeFormat, OperatingSystem, Placement,
+    Publisher, Regs, Request, Response, Restrictions, Seatbid, Site, Source, Spec, TitleAssetFormat, User, Video,
+    VideoPlacement,
+};
+
+// Category taxonomy of OpenRTB 2.5, IAB Content Category Taxonomy 1.0.
+const CATTAX_IAB_1_0: u32 = 1;
+
+// Keys of the values either side has no field for and keeps in ext.
+const EXT_FD: &str = "fd";
+const EXT_OS: &str = "os";
+const EXT_CUSTOMDATA: &str = "customdata";
+const EXT_PURL: &str = "purl";
+const EXT_CURL: &str = "curl";
+const EXT_CAT: &str = "cat";
+const EXT_CATTAX: &str = "cattax";
+const EXT_AD: &str = "ad";
+
+// Standard macros of OpenRTB 3.0 and the OpenRTB 2.5 macros they correspond
+// to.
+const OPENRTB_MACRO_START: &str = "${OPENRTB_";
+const STANDARD_MACROS: [(&str, &str); 8] = [
+    ("OPENRTB_ID", v2_5::AUCTION_ID),
+    ("OPENRTB_BID_ID", v2_5::AUCTION_BID_ID),
+    ("OPENRTB_ITEM_ID", v2_5::AUCTION_IMP_ID),
+    ("OPENRTB_SEAT_ID", v2_5::AUCTION_SEAT_ID),
+    ("OPENRTB_PRICE", v2_5::AUCTION_PRICE),
+    ("OPENRTB_CURRENCY", v2_5::AUCTION_CURRENCY),
+    ("OPENRTB_MBR", v2_5::AUCTION_MBR),
+    ("OPENRTB_LOSS", v2_5::AUCTION_LOSS),
+];
+
+/// The values of an OpenRTB 2.5 request that are out of the range of their
+/// OpenRTB 3.0 field, by JSON path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversionError {
+    pub paths: Vec<String>,
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "OpenRTB 3.0 cannot represent {}", self.paths.join(", "))
+    }
+}
+
+impl error::Error for ConversionError {}
+
+impl TryFrom<v2_5::BidRequest> for Request {
+    type Error = ConversionError;
+
+    fn try_from(request: v2_5::BidRequest) -> Result<Request, ConversionError> {
+        match Request::from_v2_5_lossy(request) {
+            (request, paths) if paths.is_empty() => Ok(request),
+            (_, paths) => Err(ConversionError { paths }),
+        }
+    }
+}
+
+impl Request {
+    /// Converts an OpenRTB 2.5 request, dropping the values out of the range
+    /// of their OpenRTB 3.0 field and returning their JSON paths.
+    pub fn from_v2_5_lossy(request: v2_5::BidRequest) -> (Request, Vec<String>) {
+        let mut dropped = Dropped(vec![]);
+        let restrictions = restrictions(&request);
+
+        let (seat, wseat) = if !request.seat_whitelist.is_empty() {
+            (request.seat_whitelist, None)
+        } else if !request.seat_blocklist.is_empty() {
+            (request.seat_blocklist, Some(false))
+        } else {
+            (vec![], None)
+        };
+
+        let wlang = request.language_whitelist;
+        let context = Context {
+            site: request.site.map(Site::from),
+            app: request.app.map(App::from),
+            user: request.user.map(User::from),
+            device: request.device.map(Device::from),
+            regs: request.regulations.map(Regs::from),
+            restrictions,
+        };
+
+        let request = Request {
+            id: request.id,
+            test: request.test,
+            tmax: request.tmax,
+            at: request.auction_type,
+            cur: request.currency,
+            seat,
+            wseat,
+            cdata: None,
+            source: request.source.map(Source::from),
+            item: request
+                .imp
+                .into_iter()
+                .enumerate()
+                .map(|(i, imp)| item(imp, &wlang, &format!("$.imp[{}]", i), &mut dropped))
+                .collect(),
+            package: request.all_imps,
+            context: Some(context).filter(|context| *context != Context::default()),
+            ext: request.ext,
+        };
+
+        (request, dropped.0)
+    }
+}
+
+impl From<v2_5::Source> for Source {
+    fn from(source: v2_5::Source) -> Source {
+        let mut ext = source.ext;
+        let schain = source.schain.or_else(|| take_ext(&mut ext, EXT_SCHAIN));
+        if let Some(fd) = source.fd {
+            put_ext(&mut ext, EXT_FD, fd);
+        }
+
+        Source {
+            tid: source.tid,
+            pchain: source.pchain,
+            schain,
+            ext,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<v2_5::Site> for Site {
+    fn from(site: v2_5::Site) -> Site {
+        let cat = categories(site.cat.unwrap_or_default());
+        let sectcat = categories(site.sectioncat.unwrap_or_default());
+        let pagecat = categories(site.pagecat.unwrap_or_default());
+
+        Site {
+            id: site.id,
+            name: site.name,
+            publisher: site.publisher.map(Publisher::from),
+            content: site.content.map(Content::from),
+            domain: site.domain,
+            cattax: cattax(&[&cat, &sectcat, &pagecat]),
+            cat,
+            sectcat,
+            pagecat,
+            privpolicy: site.privacypolicy.map(|flag| flag != 0),
+            keywords: site.keywords,
+            page: site.page,
+            referrer: site.referrer,
+            search: site.search,
+            mobile: site.mobile.map(|flag| flag != 0),
+            ext: site.ext,
+        }
+    }
+}
+
+impl From<v2_5::App> for App {
+    fn from(app: v2_5::App) -> App {
+        let cat = categories(app.cat);
+        let sectcat = categories(app.section_cat);
+        let pagecat = categories(app.page_cat);
+
+        App {
+            id: Some(app.id).filter(|id| !id.is_empty()),
+            name: app.name,
+            publisher: app.publisher.map(Publisher::from),
+            content: app.content.map(Content::from),
+            domain: app.domain,
+            cattax: cattax(&[&cat, &sectcat, &pagecat]),
+            cat,
+            sectcat,
+            pagecat,
+            privpolicy: None,
+            keywords: app.keywords,
+            bundle: app.bundle,
+            storeurl: app.store_url,
+            ver: app.ver,
+            paid: None,
+            ext: app.ext,
+        }
+    }
+}
+
+impl From<v2_5::Publisher> for Publisher {
+    fn from(publisher: v2_5::Publisher) -> Publisher {
+        let cat = categories(publisher.cat.unwrap_or_default());
+
+        Publisher {
+            id: Some(publisher.id).filter(|id| !id.is_empty()),
+            name: publisher.name,
+            domain: publisher.domain,
+            cattax: cattax(&[&cat]),
+            cat,
+            ext: publisher.ext,
+        }
+    }
+}
+
+impl From<v2_5::Content> for Content {
+    fn from(content: v2_5::Content) -> Content {
+        let cat = content.cat.unwrap_or_default();
+
+        Content {
+            id: content.id,
+            episode: content.episode,
+            title: content.title,
+            series: content.series,
+            season: content.season,
+            artist: content.artist,
+            genre: content.genre,
+            album: content.album,
+            isrc: content.isrc,
+            url: content.url,
+            cattax: cattax(&[&cat]),
+            cat,
+            prodq: content.prodq,
+            context: content.context,
+            rating: content.content_rating,
+            urating: content.user_rating,
+            mrating: content.qag_media_rating,
+            keywords: content.keywords,
+            live: content.live_stream,
+            srcrel: content.source_relationship,
+            len: content.len,
+            lang: content.language,
+            embed: content.embeddable,
+            data: vec![],
+            ext: content.ext,
+        }
+    }
+}
+
+impl From<v2_5::Device> for Device {
+    fn from(device: v2_5::Device) -> Device {
+        // AdCOM lists operating systems by number; names it has no number
+        // for are kept in ext.
+        let mut ext = device.ext;
+        let os = device.os.and_then(|name| {
+            let os = OperatingSystem::from_name(&name);
+            if os.is_none() {
+                put_ext(&mut ext, EXT_OS, name);
+            }
+            os
+        });
+
+        Device {
+            type_: device.device_type,
+            ua: device.ua,
+            ifa: device.ifa,
+            dnt: device.dnt,
+            lmt: device.lmt,
+            make: device.make,
+            model: device.model,
+            os,
+            osv: device.osv,
+            hwv: device.hwv,
+            h: device.h,
+            w: device.w,
+            ppi: device.ppi,
+            pxratio: device.pxratio,
+            js: device.js,
+            lang: device.language,
+            ip: device.ip,
+            ipv6: device.ipv6,
+            carrier: device.carrier,
+            mccmnc: device.mccmnc,
+            contype: device.connection_type,
+            geofetch: device.geofetch,
+            geo: device.geo.map(Geo::from),
+            ext,
+        }
+    }
+}
+
+impl From<v2_5::Geo> for Geo {
+    fn from(geo: v2_5::Geo) -> Geo {
+        Geo {
+            type_: geo.type_,
+            lat: geo.lat,
+            lon: geo.lon,
+            accur: geo.accuracy,
+            lastfix: geo.lastfix,
+            ipserv: geo.ip_service,
+            country: geo.country,
+            region: geo.region,
+            metro: geo.metro,
+            city: geo.city,
+            zip: geo.zip,
+            utcoffset: geo.utc_offset.map(|offset| offset as i64),
+            ext: geo.ext,
+        }
+    }
+}
+
+impl From<v2_5::User> for User {
+    fn from(user: v2_5::User) -> User {
+        // AdCOM has no field for the bidder data set in the exchange’s
+        // cookie, so it moves to ext.
+        let mut ext = user.ext;
+        if let Some(customdata) = user.customdata {
+            put_ext(&mut ext, EXT_CUSTOMDATA, customdata);
+        }
+
+        User {
+            id: user.id,
+            buyeruid: user.buyer_uid,
+            yob: user.yob,
+            gender: user.gender,
+            keywords: user.keywords,
+            consent: take_ext(&mut ext, EXT_CONSENT),
+            geo: user.geo.map(Geo::from),
+            data: user.data.unwrap_or_default(),
+            ext,
+        }
+    }
+}
+
+impl From<v2_5::Regulations> for Regs {
+    fn from(regs: v2_5::Regulations) -> Regs {
+        // AdCOM has no fields for the US Privacy and GPP signals, so these
+        // stay in ext, where OpenRTB 2.5 requests usually carry them anyway.
+        let gdpr = regs.gdpr_applies();
+        let mut ext = regs.ext;
+        remove_ext(&mut ext, EXT_GDPR);
+        if let Some(us_privacy) = regs.us_privacy {
+            put_ext(&mut ext, EXT_US_PRIVACY, us_privacy);
+        }
+        if let Some(gpp) = regs.gpp {
+            put_ext(&mut ext, EXT_GPP, gpp);
+        }
+        if !regs.gpp_sid.is_empty() {
+            put_ext(&mut ext, EXT_GPP_SID, regs.gpp_sid);
+        }
+
+        Regs {
+            coppa: Some(true).filter(|_| regs.coppa),
+            gdpr,
+            ext,
+        }
+    }
+}
+
+impl From<v2_5::Deal> for Deal {
+    fn from(deal: v2_5::Deal) -> Deal {
+        Deal {
+            id: deal.id,
+            flr: deal.bid_floor.map(price),
+            flrcur: deal.bid_floor_cur,
+            at: deal.auction_type,
+            wseat: deal.seat_whitelist,
+            wadomain: deal.advertiser_domain_whitelist,
+            ext: deal.ext,
+        }
+    }
+}
+
+impl From<v2_5::Video> for VideoPlacement {
+    fn from(video: v2_5::Video) -> VideoPlacement {
+        VideoPlacement {
+            ptype: video.placement,
+            playmethod: video.playbackmethod,
+            mime: video.mimes,
+            ctype: video.protocols,
+            w: video.w,
+            h: video.h,
+            mindur: video.minduration,
+            maxdur: video.maxduration,
+            minbitr: video.minbitrate,
+            maxbitr: video.maxbitrate,
+            linear: video.linearity,
+            boxing: video.boxingallowed,
+            ext: video.ext,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<v2_5::Audio> for AudioPlacement {
+    fn from(audio: v2_5::Audio) -> AudioPlacement {
+        AudioPlacement {
+            delay: audio.startdelay,
+            feed: audio.feed,
+            nvol: audio.nvol,
+            mime: audio.mimes,
+            api: audio.api,
+            ctype: audio.protocols,
+            mindur: audio.minduration,
+            maxdur: audio.maxduration,
+            maxext: audio.maxextended,
+            minbitr: audio.minbitrate,
+            maxbitr: audio.maxbitrate,
+            delivery: audio.delivery,
+            maxseq: audio.maxseq,
+            comptype: audio.companiontype,
+            ext: audio.ext,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<v1_2::Asset> for AssetFormat {
+    fn from(asset: v1_2::Asset) -> AssetFormat {
+        AssetFormat {
+            id: asset.id,
+            req: asset.required.map(|flag| flag != 0),
+            title: asset.title.map(|title| TitleAssetFormat {
+                len: title.len,
+                ext: title.ext,
+            }),
+            img: asset.img.map(|img| ImageAssetFormat {
+                type_: img.type_,
+                mime: img.mimes,
+                w: img.w,
+                h: img.h,
+                wmin: img.wmin,
+                hmin: img.hmin,
+                ext: img.ext,
+            }),
+            video: asset.video.map(|video| VideoPlacement {
+                mime: video.mimes,
+                mindur: video.minduration,
+                maxdur: video.maxduration,
+                ctype: video.protocols,
+                ext: video.ext,
+                ..Default::default()
+            }),
+            data: asset.data.map(|data| DataAssetFormat {
+                type_: data.type_,
+                len: data.len,
+                ext: data.ext,
+            }),
+            ext: asset.ext,
+        }
+    }
+}
+
+impl From<Response> for v2_5::BidResponse {
+    fn from(response: Response) -> v2_5::BidResponse {
+        v2_5::BidResponse {
+            id: response.id,
+            seat_bid: response.seatbid.into_iter().map(v2_5::SeatBid::from).collect(),
+            bid_id: response.bidid,
+            currency: response.cur,
+            custom_data: response.cdata,
+            no_bidding_reason: response.nbr,
+            ext: response.ext,
+        }
+    }
+}
+
+impl From<Seatbid> for v2_5::SeatBid {
+    fn from(seatbid: Seatbid) -> v2_5::SeatBid {
+        v2_5::SeatBid {
+            bid: seatbid.bid.into_iter().map(v2_5::Bid::from).collect(),
+            seat: seatbid.seat,
+            group: Some(1).filter(|_| seatbid.package),
+            ext: seatbid.ext,
+        }
+    }
+}
+
+impl From<Bid> for v2_5::Bid {
+    fn from(bid: Bid) -> v2_5::Bid {
+        let mut ad = bid.media.and_then(|media| media.ad).unwrap_or_default();
+        let mut ext = bid.ext;
+
+        let (markup, curl, api, protocol) = if let Some(display) = ad.display.as_mut() {
+            let markup = display.adm.take().or_else(|| native_markup(&mut display.native));
+            (markup, display.curl.take(), single_api(&mut display.api), None)
+        } else if let Some(video) = ad.video.as_mut() {
+            (video.adm.take(), video.curl.take(), single_api(&mut video.api), video.ctype.take())
+        } else if let Some(audio) = ad.audio.as_mut() {
+            (audio.adm.take(), audio.curl.take(), single_api(&mut audio.api), audio.ctype.take())
+        } else {
+            (None, None, None, None)
+        };
+
+        let (w, h, wratio, hratio) = match ad.display.as_mut() {
+            Some(display) => (display.w.take(), display.h.take(), display.wratio.take(), display.hratio.take()),
+            None => (None, None, None, None),
+        };
+
+        // OpenRTB 2.5 exchanges fetch the markup from the win notice URL
+        // when it is not in the bid, so markup by reference takes its place.
+        let nurl = match (markup.is_none(), curl) {
+            (true, Some(curl)) => {
+                if let Some(purl) = bid.purl {
+                    put_ext(&mut ext, EXT_PURL, purl);
+                }
+                Some(curl)
+            }
+            (_, curl) => {
+                if let Some(curl) = curl {
+                    put_ext(&mut ext, EXT_CURL, curl);
+                }
+                bid.purl
+            }
+        };
+
+        let (cat, cattax) = (std::mem::take(&mut ad.cat), ad.cattax.take());
+        let cat = if cattax.is_none_or(|cattax| cattax == CATTAX_IAB_1_0) {
+            cat.iter().map(|cat| Category::from_value(cat)).collect()
+        } else {
+            put_ext(&mut ext, EXT_CAT, cat);
+            put_ext(&mut ext, EXT_CATTAX, cattax);
+            vec![]
+        };
+
+        let expand = |value: Option<String>| {
+            value.map(|value| rewrite_standard_macros(bid.macros.iter().fold(value, |value, m| m.expand(&value))))
+        };
+
+        let mut v2_5_bid = v2_5::Bid {
+            id: bid.id,
+            imp_id: bid.item,
+            price: bid.price,
+            nurl: expand(nurl),
+            burl: expand(bid.burl),
+            lurl: expand(bid.lurl),
+            adm: expand(markup),
+            adid: bid.mid,
+            adomain: std::mem::take(&mut ad.adomain),
+            bundle: ad.bundle.take(),
+            iurl: ad.iurl.take(),
+            cid: bid.cid,
+            cr_id: ad.id.take(),
+            tactic: bid.tactic,
+            cat,
+            attr: std::mem::take(&mut ad.attr),
+            api,
+            protocol,
+            qagmediarating: ad.mrating.take(),
+            language: ad.lang.take(),
+            deal_id: bid.deal,
+            w,
+            h,
+            wratio,
+            hratio,
+            exp: bid.exp,
+            ext,
+        };
+
+        // Whatever is left of the ad has no place in OpenRTB 2.5.
+        ad.display = ad.display.filter(|display| *display != Default::default());
+        ad.video = ad.video.filter(|video| *video != Default::default());
+        ad.audio = ad.audio.filter(|audio| *audio != Default::default());
+        if ad != Ad::default() {
+            put_ext(&mut v2_5_bid.ext, EXT_AD, ad);
+        }
+
+        v2_5_bid
+    }
+}
+
+// OpenRTB 2.5 bids carry a single API framework.
+fn single_api(api: &mut Vec<u32>) -> Option<u32> {
+    if api.len() == 1 {
+        api.pop()
+    } else {
+        None
+    }
+}
+
+// Takes a structured native ad as native markup, unless it has video assets
+// native markup has no place for.
+fn native_markup(native: &mut Option<Native>) -> Option<String> {
+    let response = native_response(native.as_ref()?)?;
+    let markup = response.to_markup().ok()?;
+    *native = None;
+    Some(markup)
+}
+
+fn native_response(native: &Native) -> Option<v1_2::Response> {
+    let mut response = v1_2::Response::new(link_response(&native.link));
+    response.ext = native.ext.clone();
+
+    for asset in &native.asset {
+        let video = match asset.video {
+            Some(ref video) => {
+                let adm = video.adm.clone()?;
+                if *video != (Video { adm: Some(adm.clone()), ..Default::default() }) {
+                    return None;
+                }
+                Some(v1_2::VideoResponse { vasttag: adm })
+            }
+            None => None,
+        };
+
+        response.assets.push(v1_2::AssetResponse {
+            id: Some(asset.id),
+            required: asset.req.map(u64::from),
+            title: asset.title.as_ref().map(|title| v1_2::TitleResponse {
+                text: title.text.clone(),
+                len: title.len,
+                ext: title.ext.clone(),
+            }),
+            img: asset.img.as_ref().map(|img| v1_2::ImageResponse {
+                type_: img.type_.clone(),
+                url: img.url.clone(),
+                w: img.w,
+                h: img.h,
+                ext: img.ext.clone(),
+            }),
+            video,
+            data: asset.data.as_ref().map(|data| v1_2::DataResponse {
+                type_: data.type_.clone(),
+                len: data.len,
+                value: data.value.clone(),
+                ext: data.ext.clone(),
+            }),
+            link: asset.link.as_ref().map(link_response),
+            ext: asset.ext.clone(),
+        });
+    }
+
+    Some(response)
+}
+
+fn link_response(link: &LinkAsset) -> v1_2::LinkResponse {
+    v1_2::LinkResponse {
+        url: link.url.clone(),
+        clicktrackers: link.trkr.clone(),
+        fallback: link.urlfb.clone(),
+        ext: link.ext.clone(),
+    }
+}
+
+// Rewrites the standard OpenRTB 3.0 macros to their OpenRTB 2.5 names, for
+// the exchange to substitute. Each name is matched up to its closing brace
+// or suffix, so that ${OPENRTB_ID} does not match ${OPENRTB_ITEM_ID}.
+fn rewrite_standard_macros(value: String) -> String {
+    if !value.contains(OPENRTB_MACRO_START) {
+        return value;
+    }
+
+    STANDARD_MACROS.iter().fold(value, |value, (from, to)| {
+        let value = value.replace(&format!("${{{}}}", from), &format!("${{{}}}", to));
+        value.replace(&format!("${{{}:", from), &format!("${{{}:", to))
+    })
+}
+
+// The JSON paths of the values dropped by a conversion.
+struct Dropped(Vec<String>);
+
+impl Dropped {
+    // Narrows a value to its 3.0 field, dropping it when out of range.
+    fn narrow(&mut self, value: Option<u64>, path: &str, name: &str) -> Option<u32> {
+        let narrowed = u32::try_from(value?).ok();
+        if narrowed.is_none() {
+            self.0.push(format!("{}.{}", path, name));
+        }
+        narrowed
+    }
+}
+
+fn item(imp: v2_5::Imp, wlang: &[String], path: &str, dropped: &mut Dropped) -> Item {
+    let (deal, private) = match imp.pmp {
+        Some(pmp) => (pmp.deals.into_iter().map(Deal::from).collect(), pmp.private_auction),
+        None => (vec![], false),
+    };
+
+    let placement = Placement {
+        tagid: imp.tag_id,
+        sdk: imp.display_manager,
+        sdkver: imp.display_manager_ver,
+        wlang: wlang.to_vec(),
+        secure: imp.secure,
+        display: display_placement(imp.banner, imp.native.as_ref(), imp.interstitial, path, dropped),
+        video: imp.video.map(VideoPlacement::from),
+        audio: imp.audio.map(AudioPlacement::from),
+        ..Default::default()
+    };
+
+    Item {
+        id: imp.id,
+        flr: imp.bid_floor.map(price),
+        flrcur: imp.bid_floor_cur,
+        exp: imp.exp,
+        metric: imp.metric,
+        deal,
+        private,
+        spec: Some(Spec {
+            placement: Some(placement),
+        }),
+        ext: imp.ext,
+        ..Default::default()
+    }
+}
+
+// Banners and native requests are both display placements in AdCOM, so an
+// impression offering both becomes a single display placement.
+fn display_placement(
+    banner: Option<v2_5::Banner>,
+    native: Option<&v2_5::Native>,
+    interstitial: Option<bool>,
+    path: &str,
+    dropped: &mut Dropped,
+) -> Option<DisplayPlacement> {
+    if banner.is_none() && native.is_none() {
+        return None;
+    }
+
+    let mut display = DisplayPlacement {
+        instl: interstitial,
+        ..Default::default()
+    };
+
+    if let Some(banner) = banner {
+        display.pos = banner.pos;
+        display.topframe = banner.topframe;
+        display.w = banner.w;
+        display.h = banner.h;
+        display.displayfmt = banner
+            .format
+            .into_iter()
+            .map(|format| DisplayFormat {
+                w: format.w,
+                h: format.h,
+                wratio: format.w_ratio,
+                hratio: format.h_ratio,
+                expdir: vec![],
+                ext: format.ext,
+            })
+            .collect();
+        display.ext = banner.ext;
+    }
+
+    if let Some(request) = native.and_then(|native| native.parse_request().ok()) {
+        let path = format!("{}.native.request", path);
+        display.context = dropped.narrow(request.context, &path, "context");
+        display.ptype = dropped.narrow(request.placement_type, &path, "plcmttype");
+        display.nativefmt = Some(NativeFormat {
+            asset: request.assets.into_iter().map(AssetFormat::from).collect(),
+            ext: request.ext,
+        });
+    }
+
+    Some(display)
+}
+
+fn restrictions(request: &v2_5::BidRequest) -> Option<Restrictions> {
+    let mut battr: Vec<u64> = request
+        .imp
+        .iter()
+        .flat_map(|imp| {
+            let banner = imp.banner.iter().flat_map(|banner| &banner.battr);
+            let video = imp.video.iter().flat_map(|video| &video.battr);
+            let audio = imp.audio.iter().flat_map(|audio| &audio.battr);
+            let native = imp.native.iter().flat_map(|native| &native.battr);
+            banner.chain(video).chain(audio).chain(native).cloned()
+        })
+        .collect();
+    battr.sort_unstable();
+    battr.dedup();
+
+    let bcat = categories(request.category_blocklist.clone());
+    let restrictions = Restrictions {
+        cattax: cattax(&[&bcat]),
+        bcat,
+        badv: request.advertiser_blocklist.clone(),
+        bapp: request.app_blocklist.clone(),
+        battr,
+        ext: None,
+    };
+
+    Some(restrictions).filter(|restrictions| *restrictions != Restrictions::default())
+}
+
+fn categories(categories: Vec<Category>) -> Vec<String> {
+    categories.iter().map(Category::value).collect()
+}
+
+// The taxonomy of the given category lists, if any of them is in use.
+fn cattax(categories: &[&Vec<String>]) -> Option<u32> {
+    Some(CATTAX_IAB_1_0).filter(|_| categories.iter().any(|cat| !cat.is_empty()))
+}
+
+// Floors are single precision in OpenRTB 2.5. Going through their shortest
+// decimal representation keeps e.g. 0.1 from becoming 0.10000000149011612.
+fn price(floor: f32) -> f64 {
+    floor.to_string().parse().unwrap_or(floor as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+    use serde_json::json;
+
+    use crate::openrtb3::{AdPosition, AuctionType, DataAssetType, ImageAssetType, NoBidReason};
+
+    #[test]
+    fn convert_request() {
+        // Categories only deserialize from borrowed strings, so this goes
+        // through the JSON text.
+        let request = json!({
+            "id": "80ce30c53c16e6ede735f123ef6e32361bfc7b22",
+            "at": 1,
+            "cur": ["USD"],
+            "bseat": ["s1"],
+            "allimps": 1,
+            "wlang": ["en"],
+            "bcat": ["IAB25", "IAB26"],
+            "badv": ["company1.com"],
+            "imp": [{
+                "id": "1",
+                "bidfloor": 0.1,
+                "tagid": "t1",
+                "instl": 1,
+                "banner": {"w": 300, "h": 250, "pos": 1, "battr": [13], "format": [{"w": 300, "h": 250}]},
+                "pmp": {"private_auction": 1, "deals": [{"id": "d1", "bidfloor": 2.5, "at": 3}]}
+            }, {
+                "id": "2",
+                "video": {"mimes": ["video/mp4"], "protocols": [2, 3], "placement": 1, "battr": [13, 14]}
+            }],
+            "site": {"id": "102855", "cat": ["IAB3-1"], "page": "http://www.foobar.com/1234.html",
+                     "publisher": {"id": "8953", "name": "foobar.com"}},
+            "device": {"ua": "Mozilla/5.0", "ip": "123.145.167.10", "os": "iOS", "geo": {"country": "USA"}},
+            "user": {"id": "55816b39711f9b5acf3b90e313ed29e51665623f", "ext": {"consent": "CO-X2XiO_eyUoAsAxBFRBECsAP_AAH_AAAqIGMgB7CpERSNAYWApAOMAKYhfQAACAGAABAYIASgBQQBAMJQEkGAIMAAAAAAAAAQEIAAAAAAAAAAAAAAAA"}},
+            "regs": {"coppa": 1, "ext": {"gdpr": 1, "us_privacy": "1YNN"}},
+            "source": {"tid": "t-1", "fd": 1, "ext": {"schain": {"complete": 1, "ver": "1.0", "nodes": [{"asi": "exchange1.com", "sid": "1234", "hp": 1}]}}}
+        });
+        let request: v2_5::BidRequest = serde_json::from_str(&request.to_string()).unwrap();
+
+        let request = Request::try_from(request).unwrap();
+
+        assert_eq!(request.at, Some(AuctionType::FirstPrice));
+        assert_eq!(request.seat, vec!["s1"]);
+        assert_eq!(request.wseat, Some(false));
+        assert!(request.package);
+
+        let item = &request.item[0];
+        assert_eq!(item.flr, Some(0.1));
+        assert!(item.private);
+        assert_eq!(item.deal[0].flr, Some(2.5));
+        assert_eq!(item.deal[0].at, Some(AuctionType::ExchangeSpecific(3)));
+        let placement = item.placement().unwrap();
+        assert_eq!(placement.tagid, Some("t1".to_string()));
+        assert_eq!(placement.wlang, vec!["en"]);
+        let display = placement.display.as_ref().unwrap();
+        assert_eq!(display.pos, Some(AdPosition::AboveTheFold));
+        assert_eq!(display.instl, Some(true));
+        assert_eq!(display.displayfmt[0].w, Some(300));
+        let video = request.item[1].placement().unwrap().video.as_ref().unwrap();
+        assert_eq!(video.ptype, Some(1));
+        assert_eq!(video.ctype, vec![2, 3]);
+
+        let context = request.context.unwrap();
+        let restrictions = context.restrictions.unwrap();
+        assert_eq!(restrictions.bcat, vec!["IAB25", "IAB26"]);
+        assert_eq!(restrictions.cattax, Some(1));
+        assert_eq!(restrictions.badv, vec!["company1.com"]);
+        assert_eq!(restrictions.battr, vec![13, 14]);
+
+        let site = context.site.unwrap();
+        assert_eq!(site.cat, vec!["IAB3-1"]);
+        assert_eq!(site.cattax, Some(1));
+        assert_eq!(site.publisher.unwrap().id, Some("8953".to_string()));
+
+        let device = context.device.unwrap();
+        assert_eq!(device.os, Some(OperatingSystem::Ios));
+        assert_eq!(device.geo.unwrap().country, Some("USA".to_string()));
+
+        let user = context.user.unwrap();
+        assert!(user.consent.unwrap().starts_with("CO-X2XiO"));
+        assert_eq!(user.ext, None);
+
+        let regs = context.regs.unwrap();
+        assert_eq!(regs.coppa, Some(true));
+        assert_eq!(regs.gdpr, Some(true));
+        assert_eq!(regs.ext, Some(json!({"us_privacy": "1YNN"}).as_object().unwrap().clone()));
+
+        let source = request.source.unwrap();
+        assert_eq!(source.schain.unwrap().nodes[0].asi, "exchange1.com");
+        assert_eq!(source.ext, Some(json!({"fd": 1}).as_object().unwrap().clone()));
+    }
+
+    #[test]
+    fn convert_native_request() {
+        let native = v1_2::Request::from_markup(
+            r#"{"ver":"1.2","context":1,"plcmttype":1,"assets":[{"id":1,"required":1,"title":{"len":90}},{"id":2,"img":{"type":3,"wmin":300,"hmin":250}},{"id":3,"data":{"type":2}}]}"#,
+        )
+        .unwrap();
+        let mut request = v2_5::BidRequest::new("1".to_string());
+        request.imp.push(v2_5::Imp {
+            id: "1".to_string(),
+            native: Some(v2_5::Native::from_request(&native).unwrap()),
+            ..Default::default()
+        });
+        request.device = Some(v2_5::Device {
+            os: Some("Plan 9".to_string()),
+            ..Default::default()
+        });
+
+        let request = Request::try_from(request).unwrap();
+
+        let display = request.item[0].placement().unwrap().display.clone().unwrap();
+        assert_eq!(display.context, Some(1));
+        assert_eq!(display.ptype, Some(1));
+        let asset = display.nativefmt.unwrap().asset;
+        assert_eq!(asset[0].req, Some(true));
+        assert_eq!(asset[0].title.as_ref().unwrap().len, 90);
+        assert_eq!(asset[1].img.as_ref().unwrap().type_, Some(ImageAssetType::Main));
+        assert_eq!(asset[2].data.as_ref().unwrap().type_, DataAssetType::Description);
+
+        let device = request.context.unwrap().device.unwrap();
+        assert_eq!(device.os, None);
+        assert_eq!(device.ext, Some(json!({"os": "Plan 9"}).as_object().unwrap().clone()));
+    }
+
+    #[test]
+    fn convert_native_request_out_of_range() {
+        let native = v1_2::Request::from_markup(r#"{"ver":"1.2","context":4294967296,"plcmttype":1}"#).unwrap();
+        let mut request = v2_5::BidRequest::new("1".to_string());
+        request.imp.push(v2_5::Imp {
+            id: "1".to_string(),
+            native: Some(v2_5::Native::from_request(&native).unwrap()),
+            ..Default::default()
+        });
+
+        let err = Request::try_from(request.clone()).unwrap_err();
+        assert_eq!(err.paths, vec!["$.imp[0].native.request.context"]);
+
+        let (request, _) = Request::from_v2_5_lossy(request);
+        let display = request.item[0].placement().unwrap().display.clone().unwrap();
+        assert_eq!(display.context, None);
+        assert_eq!(display.ptype, Some(1));
+    }
+
+    #[test]
+    fn convert_response() {
+        let response: Response = serde_json::from_value(json!({
+            "id": "0123456789ABCDEF",
+            "bidid": "0011223344AABBCC",
+            "cur": "USD",
+            "nbr": 8,
+            "seatbid": [{
+                "seat": "XYZ",
+                "package": 1,
+                "bid": [{
+                    "id": "yaddayadda",
+                    "item": "30b31c1e1ca2",
+                    "price": 2.0,
+                    "deal": "1234",
+                    "cid": "c1",
+                    "purl": "https://buyer.com/p?t=${TIMESTAMP}",
+                    "burl": "https://buyer.com/b?price=${OPENRTB_PRICE}",
+                    "macro": [{"key": "TIMESTAMP", "value": "1127987134"}, {"key": "CLICKTOKEN", "value": "A7D800F2716DB"}],
+                    "media": {"ad": {
+                        "id": "555555",
+                        "adomain": ["ford.com"],
+                        "cat": ["IAB2"],
+                        "lang": "en",
+                        "attr": [12],
+                        "mrating": 1,
+                        "display": {"w": 300, "h": 250, "api": [3], "adm": "<a href=\"https://x.com/c?t=${CLICKTOKEN}\">ad</a>"}
+                    }}
+                }, {
+                    "id": "2",
+                    "item": "2",
+                    "price": 1.0,
+                    "purl": "https://buyer.com/p",
+                    "media": {"ad": {"cat": ["483"], "cattax": 2, "video": {"ctype": 3, "curl": "https://buyer.com/vast"}}}
+                }]
+            }]
+        }))
+        .unwrap();
+
+        let response = v2_5::BidResponse::from(response);
+
+        assert_eq!(response.bid_id, Some("0011223344AABBCC".to_string()));
+        assert_eq!(response.no_bidding_reason, Some(NoBidReason::UnmatchedUser));
+        let seat_bid = &response.seat_bid[0];
+        assert_eq!(seat_bid.seat, Some("XYZ".to_string()));
+        assert_eq!(seat_bid.group, Some(1));
+
+        let bid = &seat_bid.bid[0];
+        assert_eq!(bid.imp_id, "30b31c1e1ca2");
+        assert_eq!(bid.deal_id, Some("1234".to_string()));
+        assert_eq!(bid.cr_id, Some("555555".to_string()));
+        assert_eq!(bid.nurl, Some("https://buyer.com/p?t=1127987134".to_string()));
+        assert_eq!(bid.burl, Some("https://buyer.com/b?price=${AUCTION_PRICE}".to_string()));
+        assert_eq!(bid.adm, Some("<a href=\"https://x.com/c?t=A7D800F2716DB\">ad</a>".to_string()));
+        assert_eq!(bid.cat, vec![Category::from_value("IAB2")]);
+        assert_eq!(bid.api, Some(3));
+        assert_eq!(bid.protocol, None);
+        assert_eq!(bid.qagmediarating, Some(1));
+        assert_eq!((bid.w, bid.h), (Some(300), Some(250)));
+
+        let bid = &seat_bid.bid[1];
+        assert_eq!(bid.adm, None);
+        assert_eq!(bid.nurl, Some("https://buyer.com/vast".to_string()));
+        assert_eq!(bid.protocol, Some(3));
+        assert!(bid.cat.is_empty());
+        assert_eq!(
+            bid.ext,
+            Some(json!({"purl": "https://buyer.com/p", "cat": ["483"], "cattax": 2}).as_object().unwrap().clone())
+        );
+    }
+
+    #[test]
+    fn rewrite_standard_macros_ok() {
+        let url = "a=${OPENRTB_ID}&i=${OPENRTB_ITEM_ID}&p=${OPENRTB_PRICE:B64}&x=${OPENRTB_X}";
+        assert_eq!(
+            rewrite_standard_macros(url.to_string()),
+            "a=${AUCTION_ID}&i=${AUCTION_IMP_ID}&p=${AUCTION_PRICE:B64}&x=${OPENRTB_X}"
+        );
+    }
+
+    #[test]
+    fn convert_response_keeps_rest_of_ad() {
+        let bid: Bid = serde_json::from_value(json!({
+            "id": "1",
+            "item": "1",
+            "price": 1.0,
+            "media": {"ad": {
+                "secure": 1,
+                "init": 1600000000,
+                "ext": {"x": 1},
+                "display": {
+                    "mime": "text/html",
+                    "api": [3, 5],
+                    "adm": "<div>ad</div>",
+                    "event": [{"type": 1, "method": 1, "url": "https://buyer.com/i"}]
+                },
+                "video": {"dur": 30, "adm": "<VAST/>"}
+            }}
+        }))
+        .unwrap();
+
+        let bid = v2_5::Bid::from(bid);
+
+        assert_eq!(bid.adm, Some("<div>ad</div>".to_string()));
+        assert_eq!(bid.api, None);
+        assert_eq!(
+            bid.ext.unwrap()["ad"],
+            json!({
+                "secure": 1,
+                "init": 1600000000,
+                "ext": {"x": 1},
+                "display": {
+                    "mime": "text/html",
+                    "api": [3, 5],
+                    "event": [{"type": 1, "method": 1, "url": "https://buyer.com/i"}]
+                },
+                "video": {"dur": 30, "adm": "<VAST/>"}
+            })
+        );
+    }
+
+    #[test]
+    fn convert_response_native() {
+        let native = json!({
+            "link": {"url": "https://ford.com", "trkr": ["https://buyer.com/c"]},
+            "asset": [
+                {"id": 1, "req": 1, "title": {"text": "Ford"}},
+                {"id": 2, "img": {"url": "https://ford.com/i.jpg", "type": 3}},
+                {"id": 3, "video": {"adm": "<VAST/>"}}
+            ]
+        });
+        let bid = json!({"id": "1", "item": "1", "price": 1.0, "media": {"ad": {"display": {"native": native}}}});
+        let bid: Bid = serde_json::from_value(bid).unwrap();
+
+        let bid = v2_5::Bid::from(bid);
+
+        let response = v1_2::Response::from_markup(bid.adm.as_ref().unwrap()).unwrap();
+        assert_eq!(response.link.clicktrackers, vec!["https://buyer.com/c".to_string()]);
+        assert_eq!(response.assets[0].required, Some(1));
+        assert_eq!(response.assets[1].img.as_ref().unwrap().type_, Some(ImageAssetType::Main));
+        assert_eq!(response.assets[2].video.as_ref().unwrap().vasttag, "<VAST/>");
+        assert_eq!(bid.ext, None);
+
+        // Native markup has no place for a video asset by reference.
+        let mut native = native;
+        native["asset"][2]["video"] = json!({"curl": "https://buyer.com/vast"});
+        let bid = json!({"id": "1", "item": "1", "price": 1.0, "media": {"ad": {"display": {"native": native}}}});
+        let bid: Bid = serde_json::from_value(bid).unwrap();
+
+        let bid = v2_5::Bid::from(bid);
+
+        assert_eq!(bid.adm, None);
+        assert_eq!(bid.ext.unwrap()["ad"]["display"]["native"], native);
+    }
+}
@@ -0,0 +1,181 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::serde_utils;
+
+use super::{AuctionType, Metric, Placement};
+
+// Object: Item
+//
+// This object represents a unit of goods being offered for sale either on the
+// open market or in relation to a private marketplace deal. The id attribute
+// is required since there may be multiple items being offered in the same bid
+// request and bids must reference the specific item of interest. This object
+// interfaces to Layer-4 domain objects for deeper specification of the item
+// being offered (e.g., an impression).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Item {
+    // Required. A unique identifier for this item within the context of the
+    // offer (typically starts with “1” and increments).
+    pub id: String,
+
+    // The number of instances (i.e., “quantity”) of this item being
+    // offered (e.g., multiple identical impressions in a digital
+    // out-of-home scenario). Default 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qty: Option<u32>,
+
+    // If multiple items are offered in the same bid request, the sequence
+    // number allows for the coordinated delivery.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u32>,
+
+    // Minimum bid price for this item expressed in CPM.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flr: Option<f64>,
+
+    // Currency of the flr attribute specified using ISO-4217 alpha codes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flrcur: Option<String>,
+
+    // Advisory as to the number of seconds that may elapse between auction
+    // and fulfilment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<u64>,
+
+    // Timestamp when the item is expected to be fulfilled (e.g. when a DOOH
+    // impression will be displayed) in Unix format (i.e., milliseconds since
+    // the epoch).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dt: Option<u64>,
+
+    // Item (e.g., an Ad object) delivery method required, where 0 = either
+    // method, 1 = the item must be sent as part of the transaction (e.g., by
+    // value in the bid itself, fetched by URL included in the bid), and 2 =
+    // an item previously uploaded to the exchange must be referenced by its
+    // ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dlvy: Option<u32>,
+
+    // An array of Metric objects.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub metric: Vec<Metric>,
+
+    // Array of Deal objects that convey special terms applicable to this
+    // item.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deal: Vec<Deal>,
+
+    // Indicator of auction eligibility to seats named in Deal objects, where
+    // 0 = all bids are accepted, 1 = bids are restricted to the deals
+    // specified and the terms thereof.
+    #[serde(
+        default = "serde_utils::default_false",
+        skip_serializing_if = "serde_utils::is_false",
+        serialize_with = "serde_utils::bool_to_u8",
+        deserialize_with = "serde_utils::u8_to_bool"
+    )]
+    pub private: bool,
+
+    // Required. Layer-4 domain object structure that provides specifies the
+    // item being offered conforming to the specification and version
+    // referenced in openrtb.domainspec and openrtb.domainver.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec: Option<Spec>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Deal
+//
+// This object constitutes a specific deal that was struck a priori between a
+// seller and a buyer. Its presence indicates that this item is available
+// under the terms of that deal.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Deal {
+    // Required. A unique identifier for the deal.
+    pub id: String,
+
+    // Minimum deal price for this item expressed in CPM.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flr: Option<f64>,
+
+    // Currency of the flr attribute specified using ISO-4217 alpha codes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flrcur: Option<String>,
+
+    // Optional override of the overall auction type of the request, where
+    // 1 = First Price, 2 = Second Price Plus, 3 = the value passed in flr is
+    // the agreed upon deal price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub at: Option<AuctionType>,
+
+    // Allow list of buyer seats allowed to bid on this deal.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wseat: Vec<String>,
+
+    // Array of advertiser domains (e.g., advertiser.com) allowed to bid on
+    // this deal.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wadomain: Vec<String>,
+
+    // Optional exchange-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// The Layer-4 specification of an item. For AdCOM this is the placement the
+// item offers.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Spec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placement: Option<Placement>,
+}
+
+impl Item {
+    pub fn new(id: String) -> Item {
+        Item {
+            id,
+            ..Default::default()
+        }
+    }
+
+    /// The placement the item offers, if any.
+    pub fn placement(&self) -> Option<&Placement> {
+        self.spec.as_ref().and_then(|spec| spec.placement.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialization_skip_fields() {
+        let i = Item::new("1".to_string());
+
+        let expected = r#"{"id":"1"}"#;
+        let serialized = serde_json::to_string(&i).unwrap();
+
+        assert_eq!(expected, serialized)
+    }
+
+    #[test]
+    fn deserialize_deal() {
+        let serialized = r#"{"id":"1","flr":1.5,"deal":[{"id":"d1","flr":2.0,"at":3,"wseat":["s1"]}],"private":1,"spec":{"placement":{"tagid":"t1"}}}"#;
+        let i: Item = serde_json::from_str(serialized).unwrap();
+
+        assert!(i.private);
+        assert_eq!(i.deal[0].at, Some(AuctionType::ExchangeSpecific(3)));
+        assert_eq!(i.placement().unwrap().tagid, Some("t1".to_string()));
+        assert_eq!(serialized, serde_json::to_string(&i).unwrap());
+    }
+}
@@ -0,0 +1,491 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::serde_utils;
+
+use super::{DataAssetType, ImageAssetType};
+
+// The Layer-4 media of a bid. For AdCOM this is the ad to be presented if the
+// bid is won.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Media {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ad: Option<Ad>,
+}
+
+// Object: Ad
+//
+// This object is the root of a structure that defines an instance of
+// advertising media. It includes metadata about the ad overall and
+// sub-objects that provide additional detail specific to the type of media
+// comprising the creative, of which exactly one must be present.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Ad {
+    // ID of the creative; unique at least throughout the scope of a vendor
+    // (e.g., an exchange or buying platform).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    // Advertiser domain; top two levels only (e.g., “ford.com”).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub adomain: Vec<String>,
+
+    // When the product of the ad is an app, the unique ID of that app as a
+    // bundle or package name (e.g., “com.foo.mygame”).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle: Option<String>,
+
+    // URL without cache-busting to an image that is representative of the
+    // ad content for cases where exchanges or ad servers cannot review the
+    // full creative.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iurl: Option<String>,
+
+    // Array of content categories describing the ad using IDs from the
+    // taxonomy indicated in cattax.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cat: Vec<String>,
+
+    // The taxonomy in use for the cat attribute.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cattax: Option<u32>,
+
+    // Language of the creative using ISO-639-1-alpha-2.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+
+    // Set of attributes describing the creative.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attr: Vec<u32>,
+
+    // Flag to indicate if the creative is secure, where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub secure: Option<bool>,
+
+    // Media rating per IQG guidelines.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mrating: Option<u32>,
+
+    // Timestamp of the original instantiation of this ad in Unix format.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub init: Option<u64>,
+
+    // Timestamp of the most recent modification to this ad in Unix format.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lastmod: Option<u64>,
+
+    // Media subtype object if this is a display ad.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<Display>,
+
+    // Media subtype object if this is a video ad.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video: Option<Video>,
+
+    // Media subtype object if this is an audio ad.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<Audio>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Display
+//
+// This object provides additional detail about an ad specifically for
+// display ads.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Display {
+    // Mime type of the ad (e.g., “image/jpeg”).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime: Option<String>,
+
+    // API required by the ad if applicable.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api: Vec<u32>,
+
+    // Subtype of display creative.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ctype: Option<u32>,
+
+    // Absolute width of the creative in device independent pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub w: Option<u64>,
+
+    // Absolute height of the creative in device independent pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub h: Option<u64>,
+
+    // Relative width of the creative when expressing size as a ratio.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wratio: Option<u64>,
+
+    // Relative height of the creative when expressing size as a ratio.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hratio: Option<u64>,
+
+    // General display markup (e.g., HTML, AMPHTML) if not using a
+    // structured alternative.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adm: Option<String>,
+
+    // Optional means of retrieving display markup by reference; a URL that
+    // can return HTML or AMPHTML.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub curl: Option<String>,
+
+    // Structured banner ad, as an alternative to general markup.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub banner: Option<Banner>,
+
+    // Structured native ad, as an alternative to general markup.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub native: Option<Native>,
+
+    // Array of events that the buyer would like to track.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub event: Vec<Event>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Banner
+//
+// This object describes a banner ad: an image that is a link to a
+// destination.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Banner {
+    // Required. URL of the image to be displayed.
+    pub img: String,
+
+    // Required. Destination link if the image is activated (e.g., clicked).
+    pub link: LinkAsset,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Native
+//
+// This object describes a native ad as its assets and the destination link
+// of the ad overall.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Native {
+    // Required. Default destination link for the native ad overall, which
+    // applies to assets that have no link of their own.
+    pub link: LinkAsset,
+
+    // Array of assets that make up the native ad.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub asset: Vec<Asset>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Asset
+//
+// This object is a single asset of a native ad, of which exactly one of
+// title, img, video or data must be present. The id matches the asset to
+// the AssetFormat of the placement it answers.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Asset {
+    // Required. Asset ID, matching the ID of an AssetFormat of the
+    // placement.
+    pub id: u64,
+
+    // Indicator of whether or not this asset is required, where 0 = no,
+    // 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub req: Option<bool>,
+
+    // Title asset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<TitleAsset>,
+
+    // Image asset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub img: Option<ImageAsset>,
+
+    // Video asset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video: Option<Video>,
+
+    // Data asset (e.g., a rating or a price).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<DataAsset>,
+
+    // Destination link if the asset is activated, in place of the link of
+    // the native ad.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<LinkAsset>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: TitleAsset
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct TitleAsset {
+    // Required. The text of the title.
+    pub text: String,
+
+    // The length of the title.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub len: Option<u64>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: ImageAsset
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct ImageAsset {
+    // Required. URL of the image asset.
+    pub url: String,
+
+    // Absolute width of the image in device independent pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub w: Option<u64>,
+
+    // Absolute height of the image in device independent pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub h: Option<u64>,
+
+    // The type of image asset.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<ImageAssetType>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: DataAsset
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct DataAsset {
+    // Required. The formatted string of data to be displayed (e.g.,
+    // “5 stars”, “$10”).
+    pub value: String,
+
+    // The length of the data value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub len: Option<u64>,
+
+    // The type of data asset.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<DataAssetType>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: LinkAsset
+//
+// This object is the destination of a banner, a native ad or one of its
+// assets when activated (e.g., clicked).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct LinkAsset {
+    // Required. Landing URL of the clickable link.
+    pub url: String,
+
+    // Fallback URL for deep-link to be used if the URL given in url is not
+    // supported by the device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub urlfb: Option<String>,
+
+    // Array of third-party tracker URLs to be fired on click of the URL.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trkr: Vec<String>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Event
+//
+// This object specifies a type of ad tracking event and how the buyer
+// wishes to track it, by image pixel or by JavaScript.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Event {
+    // Required. Type of supported ad tracking event (e.g., 1 =
+    // impression, 2 = viewable).
+    #[serde(rename = "type")]
+    pub type_: u32,
+
+    // Required. Method of tracking requested, where 1 = image pixel and
+    // 2 = JavaScript.
+    pub method: u32,
+
+    // The APIs being used by the tracker.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api: Vec<u32>,
+
+    // JavaScript trackers, if the method is JavaScript.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jstrk: Vec<String>,
+
+    // Whether the JavaScript trackers are requested to run in a
+    // wrapper, where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub wjs: Option<bool>,
+
+    // URL of the image pixel, if the method is image pixel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    // Additional data for the tracker as key-value pairs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cdata: Option<serde_utils::Ext>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Video
+//
+// This object provides additional detail about an ad specifically for video
+// ads.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Video {
+    // Mime type(s) of the ad creative(s) (e.g., “video/mp4”).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mime: Vec<String>,
+
+    // API required by the ad if applicable.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api: Vec<u32>,
+
+    // Subtype of video creative (e.g., a VAST version).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ctype: Option<u32>,
+
+    // Duration of the video creative in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dur: Option<u32>,
+
+    // Video markup (e.g., VAST) if not using curl.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adm: Option<String>,
+
+    // Optional means of retrieving markup by reference; a URL that returns
+    // video markup.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub curl: Option<String>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Audio
+//
+// This object provides additional detail about an ad specifically for audio
+// ads.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Audio {
+    // Mime type(s) of the ad creative(s) (e.g., “audio/mp4”).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mime: Vec<String>,
+
+    // API required by the ad if applicable.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api: Vec<u32>,
+
+    // Subtype of audio creative (e.g., a DAAST version).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ctype: Option<u32>,
+
+    // Duration of the audio creative in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dur: Option<u32>,
+
+    // Audio markup (e.g., DAAST) if not using curl.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adm: Option<String>,
+
+    // Optional means of retrieving markup by reference; a URL that returns
+    // audio markup.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub curl: Option<String>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialization_skip_fields() {
+        let m = Media {
+            ad: Some(Ad {
+                id: Some("555555".to_string()),
+                ..Default::default()
+            }),
+        };
+
+        let expected = r#"{"ad":{"id":"555555"}}"#;
+        let serialized = serde_json::to_string(&m).unwrap();
+
+        assert_eq!(expected, serialized)
+    }
+
+    #[test]
+    fn deserialize_display_ad() {
+        let serialized = r#"{"ad":{"id":"555555","adomain":["ford.com"],"secure":1,"display":{"mime":"text/html","w":300,"h":250,"adm":"<div>ad</div>"}}}"#;
+        let m: Media = serde_json::from_str(serialized).unwrap();
+
+        let ad = m.ad.as_ref().unwrap();
+        assert_eq!(ad.secure, Some(true));
+        assert_eq!(ad.display.as_ref().unwrap().w, Some(300));
+        assert_eq!(serialized, serde_json::to_string(&m).unwrap());
+    }
+
+    #[test]
+    fn deserialize_native_ad() {
+        let serialized = r#"{"ad":{"display":{"native":{"link":{"url":"https://ford.com"},"asset":[{"id":1,"req":1,"title":{"text":"Ford"}},{"id":2,"img":{"url":"https://ford.com/i.jpg","w":300,"h":250,"type":3}}]},"event":[{"type":1,"method":1,"url":"https://buyer.com/i"}]}}}"#;
+        let m: Media = serde_json::from_str(serialized).unwrap();
+
+        let display = m.ad.as_ref().unwrap().display.as_ref().unwrap();
+        let native = display.native.as_ref().unwrap();
+        assert_eq!(native.link.url, "https://ford.com");
+        assert_eq!(native.asset[0].req, Some(true));
+        assert_eq!(native.asset[1].img.as_ref().unwrap().type_, Some(ImageAssetType::Main));
+        assert_eq!(display.event[0].url, Some("https://buyer.com/i".to_string()));
+        assert_eq!(serialized, serde_json::to_string(&m).unwrap());
+    }
+}
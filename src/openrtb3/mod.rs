@@ -0,0 +1,39 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// OpenRTB 3.0 with AdCOM 1.0
+//
+// OpenRTB 3.0 splits the transaction layer (Openrtb, Request, Item, Response,
+// Seatbid, Bid) from the domain objects of AdCOM, which a request carries in
+// the Spec of each item and in its Context, and a response in the Media of
+// each bid. Lists AdCOM shares with OpenRTB 2.5 are re-exported from the
+// v2_5 and native modules. Requests convert from OpenRTB 2.5, and responses
+// convert back to it.
+
+mod context;
+mod convert;
+mod item;
+mod media;
+mod openrtb;
+mod operating_system;
+mod placement;
+mod request;
+mod response;
+
+pub use self::context::*;
+pub use self::convert::ConversionError;
+pub use self::item::*;
+pub use self::media::*;
+pub use self::openrtb::*;
+pub use self::operating_system::*;
+pub use self::placement::*;
+pub use self::request::*;
+pub use self::response::*;
+pub use crate::native::v1_2::{DataAssetType, ImageAssetType};
+pub use crate::serde_utils::Ext;
+pub use crate::v2_5::{AdPosition, AuctionType, Data, Gender, LossReason, Metric, NoBidReason, Segment, SupplyChain, SupplyChainNode};
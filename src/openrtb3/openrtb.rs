@@ -0,0 +1,111 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::{Request, Response};
+
+// Version of the OpenRTB transaction layer this module implements.
+pub const OPENRTB_VERSION: &str = "3.0";
+
+// Identifier and version of the domain specification this module implements.
+pub const ADCOM_SPEC: &str = "adcom";
+pub const ADCOM_VERSION: &str = "1.0";
+
+// The payload of an OpenRTB 3.0 request or response, whose only member is the
+// Openrtb object.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Body {
+    pub openrtb: Openrtb,
+}
+
+// Object: Openrtb
+//
+// This top-level object is the root for both request and response payloads.
+// It includes versioning information and references to the Layer-4 domain
+// model on which transactions are based.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Openrtb {
+    // Required. Version of the Layer-3 OpenRTB specification (e.g., "3.0").
+    pub ver: String,
+
+    // Required. Identifier of the Layer-4 domain model used to define
+    // items for sale, media associated with bids, etc.
+    pub domainspec: String,
+
+    // Required. Specification version of the Layer-4 domain model
+    // referenced in the domainspec attribute.
+    pub domainver: String,
+
+    // Bid request container. Required only for request payloads.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request: Option<Request>,
+
+    // Bid response container. Required only for response payloads.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<Response>,
+}
+
+impl Body {
+    /// Wraps a request in an OpenRTB 3.0 payload with the AdCOM 1.0 domain.
+    pub fn request(request: Request) -> Body {
+        Body {
+            openrtb: Openrtb {
+                request: Some(request),
+                ..Openrtb::new()
+            },
+        }
+    }
+
+    /// Wraps a response in an OpenRTB 3.0 payload with the AdCOM 1.0 domain.
+    pub fn response(response: Response) -> Body {
+        Body {
+            openrtb: Openrtb {
+                response: Some(response),
+                ..Openrtb::new()
+            },
+        }
+    }
+}
+
+impl Openrtb {
+    pub fn new() -> Openrtb {
+        Openrtb {
+            ver: OPENRTB_VERSION.to_string(),
+            domainspec: ADCOM_SPEC.to_string(),
+            domainver: ADCOM_VERSION.to_string(),
+            request: None,
+            response: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialize_request_body() {
+        let body = Body::request(Request::new("0123456789ABCDEF".to_string()));
+
+        let expected = r#"{"openrtb":{"ver":"3.0","domainspec":"adcom","domainver":"1.0","request":{"id":"0123456789ABCDEF"}}}"#;
+        let serialized = serde_json::to_string(&body).unwrap();
+
+        assert_eq!(expected, serialized)
+    }
+
+    #[test]
+    fn deserialize_response_body() {
+        let serialized = r#"{"openrtb":{"ver":"3.0","domainspec":"adcom","domainver":"1.0","response":{"id":"0123456789ABCDEF","nbr":2}}}"#;
+        let body: Body = serde_json::from_str(serialized).unwrap();
+
+        assert!(body.openrtb.request.is_none());
+        let response = body.openrtb.response.unwrap();
+        assert_eq!(response.id, "0123456789ABCDEF");
+        assert_eq!(response.nbr, Some(crate::openrtb3::NoBidReason::InvalidRequest));
+    }
+}
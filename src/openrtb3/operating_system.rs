@@ -0,0 +1,106 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// List: Operating Systems
+//
+// Operating systems, whether of a device or of the platform an ad is served
+// on. Values of 500 and above are reserved for vendor-specific usage.
+open_enum_list_module! {
+    OperatingSystem u32:
+        ThreeDsSystemSoftware 1,
+        Android 2,
+        AppleTvSoftware 3,
+        Asha 4,
+        Bada 5,
+        BlackBerry 6,
+        Brew 7,
+        ChromeOs 8,
+        Darwin 9,
+        FireOs 10,
+        FirefoxOs 11,
+        HelenOs 12,
+        Ios 13,
+        Linux 14,
+        MacOs 15,
+        MeeGo 16,
+        MorphOs 17,
+        NetBsd 18,
+        NucleusPlus 19,
+        PsVitaSystemSoftware 20,
+        Ps3SystemSoftware 21,
+        Ps4Software 22,
+        PspSystemSoftware 23,
+        Symbian 24,
+        Tizen 25,
+        WatchOs 26,
+        WebOs 27,
+        Windows 28
+}
+
+impl OperatingSystem {
+    /// Looks up an operating system by the name OpenRTB 2.x devices give it
+    /// (e.g., “iOS”, “Android”), ignoring case, spaces and dashes.
+    pub fn from_name(name: &str) -> Option<OperatingSystem> {
+        let name: String = name
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-' && *c != '_')
+            .collect::<String>()
+            .to_lowercase();
+
+        let os = match name.as_str() {
+            "android" => OperatingSystem::Android,
+            "tvos" | "appletv" => OperatingSystem::AppleTvSoftware,
+            "blackberry" | "rim" => OperatingSystem::BlackBerry,
+            "chromeos" => OperatingSystem::ChromeOs,
+            "fireos" => OperatingSystem::FireOs,
+            "firefoxos" => OperatingSystem::FirefoxOs,
+            "ios" | "iphoneos" | "ipados" => OperatingSystem::Ios,
+            "linux" => OperatingSystem::Linux,
+            "macos" | "macosx" | "osx" => OperatingSystem::MacOs,
+            "symbian" => OperatingSystem::Symbian,
+            "tizen" => OperatingSystem::Tizen,
+            "watchos" => OperatingSystem::WatchOs,
+            "webos" => OperatingSystem::WebOs,
+            "windows" | "windowsphone" | "windowsmobile" => OperatingSystem::Windows,
+            _ => return None,
+        };
+
+        Some(os)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialize_good() {
+        let x = OperatingSystem::Ios;
+        let expected = "13";
+        let serialized = serde_json::to_string(&x).unwrap();
+
+        assert_eq!(expected, serialized)
+    }
+
+    #[test]
+    fn deserialize_vendor_specific() {
+        let serialized = "501";
+        let x: OperatingSystem = serde_json::from_str(serialized).unwrap();
+
+        assert_eq!(OperatingSystem::Unknown(501), x);
+    }
+
+    #[test]
+    fn from_name() {
+        assert_eq!(OperatingSystem::from_name("iOS"), Some(OperatingSystem::Ios));
+        assert_eq!(OperatingSystem::from_name("Mac OS X"), Some(OperatingSystem::MacOs));
+        assert_eq!(OperatingSystem::from_name("Windows Phone"), Some(OperatingSystem::Windows));
+        assert_eq!(OperatingSystem::from_name("Plan 9"), None);
+    }
+}
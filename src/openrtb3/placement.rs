@@ -0,0 +1,593 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::serde_utils;
+
+use super::{AdPosition, DataAssetType, ImageAssetType};
+
+// Object: Placement
+//
+// This object represents the properties of a placement, which is the context
+// into which an ad is rendered. It has subtypes for display, video and audio
+// placements, of which the ones present indicate the media the placement
+// accepts.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Placement {
+    // Identifier for specific ad placement or ad tag that was used to
+    // initiate the auction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tagid: Option<String>,
+
+    // Indicates if server-side ad insertion (e.g., stitching an ad into an
+    // audio or video stream) is in use and the impact of this on asset and
+    // tracker retrieval, where 0 = status unknown, 1 = all client-side,
+    // 2 = assets stitched server-side but tracking pixels fired client-side
+    // and 3 = all server-side.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssai: Option<u32>,
+
+    // Name of ad mediation partner, SDK technology, or player responsible
+    // for rendering ad (typically video, audio, or mobile).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sdk: Option<String>,
+
+    // Version of the SDK specified in the sdk attribute.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sdkver: Option<String>,
+
+    // Indicates if this is a rewarded placement, where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub reward: Option<bool>,
+
+    // Allow list of permitted languages of the creative using ISO-639-1-alpha-2.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wlang: Vec<String>,
+
+    // Flag to indicate if the creative is required to use secure HTTPS URLs
+    // for its assets and trackers, where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub secure: Option<bool>,
+
+    // Indicates if including markup is supported (i.e., the adm attribute of
+    // the media subtypes), where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub admx: Option<bool>,
+
+    // Indicates if the markup may be retrieved by URL (i.e., the curl
+    // attribute of the media subtypes), where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub curlx: Option<bool>,
+
+    // Placement subtype indicating that a display ad (i.e., banner or
+    // native) can be accepted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<DisplayPlacement>,
+
+    // Placement subtype indicating that a video ad can be accepted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video: Option<VideoPlacement>,
+
+    // Placement subtype indicating that an audio ad can be accepted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<AudioPlacement>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: DisplayPlacement
+//
+// This object signals that the placement may be a display placement. It
+// provides additional detail about permitted display ads, either by size
+// (displayfmt) or by native asset requirements (nativefmt).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct DisplayPlacement {
+    // Placement position on screen.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pos: Option<AdPosition>,
+
+    // Indicates if this is an interstitial placement, where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub instl: Option<bool>,
+
+    // Indicates if the placement will be rendered in the top-level browser
+    // window or inside an iframe, where 0 = iframe, 1 = top frame.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub topframe: Option<bool>,
+
+    // Indicates the click type of the placement.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clktype: Option<u32>,
+
+    // The display placement type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ptype: Option<u32>,
+
+    // The context of the placement.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<u32>,
+
+    // Array of supported mime types (e.g., “image/jpeg”, “image/gif”).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mime: Vec<String>,
+
+    // List of supported APIs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api: Vec<u32>,
+
+    // Creative subtypes permitted for this placement.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ctype: Vec<u32>,
+
+    // Width of the placement in units specified by unit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub w: Option<u32>,
+
+    // Height of the placement in units specified by unit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub h: Option<u32>,
+
+    // Unit of size used for placement size (i.e., w and h attributes),
+    // where 1 = device independent pixels. Default 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<u32>,
+
+    // Array of DisplayFormat objects representing the sizes permitted. If
+    // none are specified, then use of the w and h attributes is highly
+    // recommended.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub displayfmt: Vec<DisplayFormat>,
+
+    // Permitted native ad formats, which signals that native ads are
+    // acceptable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nativefmt: Option<NativeFormat>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: DisplayFormat
+//
+// This object represents an allowed size (i.e., height and width
+// combination) for a banner display ad, either as an absolute size or as a
+// ratio for flexible sizes.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct DisplayFormat {
+    // Absolute width of the creative in units specified by
+    // DisplayPlacement.unit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub w: Option<u32>,
+
+    // Absolute height of the creative in units specified by
+    // DisplayPlacement.unit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub h: Option<u32>,
+
+    // Relative width of the creative when expressing size as a ratio.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wratio: Option<u32>,
+
+    // Relative height of the creative when expressing size as a ratio.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hratio: Option<u32>,
+
+    // Directions in which the creative is permitted to expand.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expdir: Vec<u32>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: NativeFormat
+//
+// This object specifies the assets a native ad for the placement is made of.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct NativeFormat {
+    // Array of AssetFormat objects, each of which defines an asset
+    // permitted in the native ad.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub asset: Vec<AssetFormat>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: AssetFormat
+//
+// This object represents the permitted specifications of a single asset of a
+// native ad. Exactly one of title, img, video or data must be present.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct AssetFormat {
+    // Required. Asset ID, unique within the scope of this placement
+    // specification.
+    pub id: u64,
+
+    // Indicator of whether or not this asset is required, where 0 = no,
+    // 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub req: Option<bool>,
+
+    // Indicates that this asset is a title.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<TitleAssetFormat>,
+
+    // Indicates that this asset is an image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub img: Option<ImageAssetFormat>,
+
+    // Indicates that this asset is a video.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video: Option<VideoPlacement>,
+
+    // Indicates that this asset is data (e.g., a rating or a price).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<DataAssetFormat>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: TitleAssetFormat
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct TitleAssetFormat {
+    // Required. The maximum allowed length of the title value.
+    pub len: u64,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: ImageAssetFormat
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct ImageAssetFormat {
+    // The type of image asset supported.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<ImageAssetType>,
+
+    // Array of supported mime types (e.g., “image/jpeg”, “image/gif”).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mime: Vec<String>,
+
+    // Absolute width of the image asset in device independent pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub w: Option<u64>,
+
+    // Absolute height of the image asset in device independent pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub h: Option<u64>,
+
+    // The minimum requested absolute width of the image in device
+    // independent pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wmin: Option<u64>,
+
+    // The minimum requested absolute height of the image in device
+    // independent pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hmin: Option<u64>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: DataAssetFormat
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DataAssetFormat {
+    // Required. The type of data asset supported.
+    #[serde(rename = "type")]
+    pub type_: DataAssetType,
+
+    // The maximum allowed length of the data value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub len: Option<u64>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: VideoPlacement
+//
+// This object signals that the placement may be a video placement and
+// provides additional detail about permitted video ads.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct VideoPlacement {
+    // Placement subtype, where 1 = in-stream, 2 = in-banner, 3 = in-article,
+    // 4 = in-feed and 5 = interstitial, slider or floating.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ptype: Option<u32>,
+
+    // Placement position on screen.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pos: Option<AdPosition>,
+
+    // Indicates the start delay in seconds for pre-roll, mid-roll, or
+    // post-roll ad placements.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delay: Option<i32>,
+
+    // Indicates if the placement imposes ad skippability, where 0 = no,
+    // 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub skip: Option<bool>,
+
+    // The placement allows creatives of total duration greater than this
+    // number of seconds to be skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipmin: Option<u32>,
+
+    // Number of seconds a creative must play before the placement enables
+    // skipping.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipafter: Option<u32>,
+
+    // Playback methods that may be in use.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub playmethod: Vec<u32>,
+
+    // The event that causes playback to end.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub playend: Option<u32>,
+
+    // Indicates the click type of the placement.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clktype: Option<u32>,
+
+    // Array of supported mime types (e.g., “video/mp4”).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mime: Vec<String>,
+
+    // List of supported APIs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api: Vec<u32>,
+
+    // Creative subtypes permitted for this placement (e.g., VAST versions).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ctype: Vec<u32>,
+
+    // Width of the placement in units specified by unit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub w: Option<u32>,
+
+    // Height of the placement in units specified by unit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub h: Option<u32>,
+
+    // Unit of size used for placement size (i.e., w and h attributes).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<u32>,
+
+    // Minimum creative duration in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mindur: Option<u32>,
+
+    // Maximum creative duration in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxdur: Option<u32>,
+
+    // Maximum extended creative duration if extension is allowed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxext: Option<i32>,
+
+    // Minimum bit rate of the creative in Kbps.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minbitr: Option<u32>,
+
+    // Maximum bit rate of the creative in Kbps.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxbitr: Option<u32>,
+
+    // Array of supported creative delivery methods.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub delivery: Vec<u32>,
+
+    // The maximum number of ads that can be played in an ad pod.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxseq: Option<u32>,
+
+    // Indicates if the creative must be linear, nonlinear, etc.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub linear: Option<u32>,
+
+    // Indicates if letter-boxing of 4:3 creatives into a 16:9 window is
+    // allowed, where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub boxing: Option<bool>,
+
+    // Supported companion ad types.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comptype: Vec<u32>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: AudioPlacement
+//
+// This object signals that the placement may be an audio placement and
+// provides additional detail about permitted audio ads.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct AudioPlacement {
+    // Indicates the start delay in seconds for pre-roll, mid-roll, or
+    // post-roll ad placements.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delay: Option<i32>,
+
+    // Indicates if the placement imposes ad skippability, where 0 = no,
+    // 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub skip: Option<bool>,
+
+    // The placement allows creatives of total duration greater than this
+    // number of seconds to be skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipmin: Option<u32>,
+
+    // Number of seconds a creative must play before the placement enables
+    // skipping.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipafter: Option<u32>,
+
+    // Playback methods that may be in use.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub playmethod: Vec<u32>,
+
+    // The event that causes playback to end.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub playend: Option<u32>,
+
+    // Type of audio feed of this placement.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feed: Option<u32>,
+
+    // Volume normalization mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nvol: Option<u32>,
+
+    // Array of supported mime types (e.g., “audio/mp4”).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mime: Vec<String>,
+
+    // List of supported APIs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api: Vec<u32>,
+
+    // Creative subtypes permitted for this placement (e.g., DAAST versions).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ctype: Vec<u32>,
+
+    // Minimum creative duration in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mindur: Option<u32>,
+
+    // Maximum creative duration in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxdur: Option<u32>,
+
+    // Maximum extended creative duration if extension is allowed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxext: Option<i32>,
+
+    // Minimum bit rate of the creative in Kbps.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minbitr: Option<u32>,
+
+    // Maximum bit rate of the creative in Kbps.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxbitr: Option<u32>,
+
+    // Array of supported creative delivery methods.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub delivery: Vec<u32>,
+
+    // The maximum number of ads that can be played in an ad pod.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxseq: Option<u32>,
+
+    // Supported companion ad types.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comptype: Vec<u32>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialization_skip_fields() {
+        let p = Placement {
+            tagid: Some("t1".to_string()),
+            display: Some(DisplayPlacement::default()),
+            ..Default::default()
+        };
+
+        let expected = r#"{"tagid":"t1","display":{}}"#;
+        let serialized = serde_json::to_string(&p).unwrap();
+
+        assert_eq!(expected, serialized)
+    }
+
+    #[test]
+    fn deserialize_native_format() {
+        let serialized = r#"{"display":{"pos":1,"nativefmt":{"asset":[{"id":1,"req":1,"title":{"len":90}},{"id":2,"img":{"type":3,"wmin":300,"hmin":250}},{"id":3,"data":{"type":2,"len":140}}]}}}"#;
+        let p: Placement = serde_json::from_str(serialized).unwrap();
+
+        let display = p.display.as_ref().unwrap();
+        assert_eq!(display.pos, Some(AdPosition::AboveTheFold));
+        let asset = &display.nativefmt.as_ref().unwrap().asset;
+        assert_eq!(asset[0].req, Some(true));
+        assert_eq!(asset[1].img.as_ref().unwrap().type_, Some(ImageAssetType::Main));
+        assert_eq!(asset[2].data.as_ref().unwrap().type_, DataAssetType::Description);
+        assert_eq!(serialized, serde_json::to_string(&p).unwrap());
+    }
+}
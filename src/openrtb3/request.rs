@@ -0,0 +1,186 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::serde_utils;
+
+use super::{AuctionType, Context, Item, SupplyChain};
+
+// Object: Request
+//
+// The Request object contains a globally unique bid request ID. This id
+// attribute is required as is an Item array with at least one object.
+// Other attributes establish rules and restrictions that apply to all items
+// being offered. This object also interfaces to Layer-4 domain objects for
+// context such as the user, device, site or app, etc.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Request {
+    // Required. Unique ID of the bid request; provided by the exchange.
+    pub id: String,
+
+    // Indicator of test mode in which auctions are not billable, where
+    // 0 = live mode, 1 = test mode.
+    #[serde(
+        default = "serde_utils::default_false",
+        skip_serializing_if = "serde_utils::is_false",
+        serialize_with = "serde_utils::bool_to_u8",
+        deserialize_with = "serde_utils::u8_to_bool"
+    )]
+    pub test: bool,
+
+    // Maximum time in milliseconds the exchange allows for bids to be
+    // received including Internet latency to avoid timeout.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tmax: Option<u64>,
+
+    // Auction type, where 1 = First Price, 2 = Second Price Plus. Values
+    // greater than 500 can be used for exchange-specific auction types.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub at: Option<AuctionType>,
+
+    // Array of accepted currencies for bids on this bid request using
+    // ISO-4217 alpha codes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cur: Vec<String>,
+
+    // Restriction list of buyer seats for bidding on this item. Knowledge
+    // of buyer’s customers and their seat IDs must be coordinated between
+    // parties a priori. Omission implies no restrictions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub seat: Vec<String>,
+
+    // Flag that determines the restriction interpretation of the seat
+    // array, where 0 = block list, 1 = allow list. Default 1.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub wseat: Option<bool>,
+
+    // Allows bidder to retrieve data set on its behalf in the exchange’s
+    // cookie if supported by the exchange.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cdata: Option<String>,
+
+    // A Source object that provides data about the inventory source and
+    // which entity makes the final decision.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<Source>,
+
+    // Required. Array of Item objects (at least one) that constitute the
+    // set of goods being offered for sale.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub item: Vec<Item>,
+
+    // Flag to indicate if the Exchange can verify that the items offered
+    // represent all of the items available in context (e.g., all on the
+    // web page, all video spots such as pre/mid/post roll) to support
+    // road-blocking, where 0 = no, 1 = yes.
+    #[serde(
+        default = "serde_utils::default_false",
+        skip_serializing_if = "serde_utils::is_false",
+        serialize_with = "serde_utils::bool_to_u8",
+        deserialize_with = "serde_utils::u8_to_bool"
+    )]
+    pub package: bool,
+
+    // Layer-4 domain object structure that provides context for the items
+    // being offered conforming to the specification and version referenced
+    // in openrtb.domainspec and openrtb.domainver.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<Context>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Source
+//
+// This object carries data about the source of the transaction including the
+// unique ID of the transaction itself, source authentication information,
+// and the chain of custody.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Source {
+    // Transaction ID that must be common across all participants throughout
+    // the entire supply chain of this transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tid: Option<String>,
+
+    // Timestamp when the request originated at the beginning of the supply
+    // chain in Unix format (i.e., milliseconds since the epoch).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ts: Option<u64>,
+
+    // Digital signature used to authenticate the origin of this request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ds: Option<String>,
+
+    // An ordered list of identifiers that indicates the attributes used to
+    // create the digital signature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dsmap: Option<String>,
+
+    // File name of the certificate (i.e., the public key) used to generate
+    // the digital signature in ds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cert: Option<String>,
+
+    // Payment ID chain string containing embedded syntax described in the
+    // TAG Payment ID Protocol.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pchain: Option<String>,
+
+    // The supply chain of the request, as defined by the SupplyChain object
+    // specification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schain: Option<SupplyChain>,
+
+    // Optional vendor-specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+impl Request {
+    pub fn new(id: String) -> Request {
+        Request {
+            id,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialization_skip_fields() {
+        let r = Request::new("0123456789ABCDEF".to_string());
+
+        let expected = r#"{"id":"0123456789ABCDEF"}"#;
+        let serialized = serde_json::to_string(&r).unwrap();
+
+        assert_eq!(expected, serialized)
+    }
+
+    #[test]
+    fn deserialize_flags() {
+        let serialized = r#"{"id":"1","test":1,"at":1,"seat":["s1"],"wseat":0,"item":[{"id":"1"}],"package":1}"#;
+        let r: Request = serde_json::from_str(serialized).unwrap();
+
+        assert!(r.test);
+        assert!(r.package);
+        assert_eq!(r.at, Some(AuctionType::FirstPrice));
+        assert_eq!(r.wseat, Some(false));
+        assert_eq!(r.item.len(), 1);
+        assert_eq!(serialized, serde_json::to_string(&r).unwrap());
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::serde_utils;
+
+use super::{Media, NoBidReason};
+
+// Object: Response
+//
+// This object is the bid response object under the Openrtb root. Its id
+// attribute is a reflection of the bid request ID. The bidid attribute is an
+// optional response tracking ID for bidders. If specified, it will be
+// available for use in substitution macros placed in markup and
+// notification URLs. At least one Seatbid object is required, which contains
+// at least one Bid for an item. Other attributes are optional.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Response {
+    // Required. ID of the bid request to which this is a response; must
+    // match the request.id attribute.
+    pub id: String,
+
+    // Bidder generated response ID to assist with logging/tracking.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bidid: Option<String>,
+
+    // Reason for not bidding if applicable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nbr: Option<NoBidReason>,
+
+    // Bid currency using ISO-4217 alpha codes. Default "USD".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cur: Option<String>,
+
+    // Allows bidder to set data in the exchange’s cookie, if supported by
+    // the exchange.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cdata: Option<String>,
+
+    // Array of Seatbid objects; 1+ required if a bid is to be made.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub seatbid: Vec<Seatbid>,
+
+    // Optional demand source specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Seatbid
+//
+// A bid response can contain multiple Seatbid objects, each on behalf of a
+// different buyer seat and each containing one or more individual bids.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Seatbid {
+    // ID of the buyer seat on whose behalf this bid is made.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seat: Option<String>,
+
+    // For offers with multiple items, this flag indicates if the bidder is
+    // willing to accept wins on a subset of bids or requires the full group
+    // as a package, where 0 = individual wins accepted; 1 = package win or
+    // loss only.
+    #[serde(
+        default = "serde_utils::default_false",
+        skip_serializing_if = "serde_utils::is_false",
+        serialize_with = "serde_utils::bool_to_u8",
+        deserialize_with = "serde_utils::u8_to_bool"
+    )]
+    pub package: bool,
+
+    // Required. Array of 1+ Bid objects each related to an item.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bid: Vec<Bid>,
+
+    // Optional demand source specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Bid
+//
+// A Seatbid object contains one or more Bid objects, each of which relates
+// to a specific item in the bid request offer via the item attribute and
+// constitutes an offer to buy that item for a given price.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Bid {
+    // Bidder generated bid ID to assist with logging/tracking.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+
+    // Required. ID of the item object in the related bid request;
+    // specifically item.id.
+    pub item: String,
+
+    // Required. Bid price expressed as CPM although the actual transaction
+    // is for a unit item only.
+    pub price: f64,
+
+    // Reference to a deal from the bid request if this bid pertains to a
+    // private marketplace deal; specifically deal.id.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deal: Option<String>,
+
+    // Campaign ID or other similar grouping of brand-related ads.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cid: Option<String>,
+
+    // Tactic ID to enable buyers to label bids for reporting to the exchange
+    // the tactic through which their bid was submitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tactic: Option<String>,
+
+    // Pending notice URL called by the exchange when a bid has been
+    // declared the winner within the scope of an OpenRTB compliant supply
+    // chain (i.e., there may still be non-compliant decisioning such as
+    // header bidding).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purl: Option<String>,
+
+    // Billing notice URL called by the exchange when a winning bid becomes
+    // billable based on exchange-specific business policy (e.g., markup
+    // rendered).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub burl: Option<String>,
+
+    // Loss notice URL called by the exchange when a bid is known to have
+    // been lost.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lurl: Option<String>,
+
+    // Advisory as to the number of seconds the buyer is willing to wait
+    // between auction and fulfilment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<u64>,
+
+    // ID to enable media to be specified by reference if previously
+    // uploaded to the exchange rather than including it by value in the
+    // domain objects.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mid: Option<String>,
+
+    // Array of Macro objects that enable bid specific values to be
+    // substituted into markup.
+    #[serde(rename = "macro", default, skip_serializing_if = "Vec::is_empty")]
+    pub macros: Vec<Macro>,
+
+    // Layer-4 domain object structure that specifies the media to be
+    // presented if the bid is won.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media: Option<Media>,
+
+    // Optional demand source specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// Object: Macro
+//
+// This object constitutes a buyer defined key/value pair used to inject
+// dynamic values into media markup, written in the markup as
+// “${CUSTOM_KEY}”.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Macro {
+    // Required. Name of a buyer specific macro.
+    pub key: String,
+
+    // Value to substitute for each instance of the macro found in markup.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+
+    // Optional demand source specific extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+impl Response {
+    pub fn new(id: String) -> Response {
+        Response {
+            id,
+            ..Default::default()
+        }
+    }
+}
+
+impl Macro {
+    /// Substitutes the macro’s value for each “${KEY}” found in `markup`.
+    pub fn expand(&self, markup: &str) -> String {
+        let name = format!("${{{}}}", self.key);
+        markup.replace(&name, self.value.as_deref().unwrap_or(""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialization_skip_fields() {
+        let r = Response::new("0123456789ABCDEF".to_string());
+
+        let expected = r#"{"id":"0123456789ABCDEF"}"#;
+        let serialized = serde_json::to_string(&r).unwrap();
+
+        assert_eq!(expected, serialized)
+    }
+
+    #[test]
+    fn deserialize_bid() {
+        let serialized = r#"{"id":"0","seatbid":[{"seat":"XYZ","package":1,"bid":[{"id":"yaddayadda","item":"1","price":1.5,"deal":"1234","purl":"https://x.com/p?p=${OPENRTB_PRICE}","macro":[{"key":"TIMESTAMP","value":"1127987134"}],"media":{"ad":{"id":"555555"}}}]}]}"#;
+        let r: Response = serde_json::from_str(serialized).unwrap();
+
+        let seatbid = &r.seatbid[0];
+        assert!(seatbid.package);
+        assert_eq!(seatbid.bid[0].item, "1");
+        assert_eq!(seatbid.bid[0].macros[0].key, "TIMESTAMP");
+        assert_eq!(serialized, serde_json::to_string(&r).unwrap());
+    }
+
+    #[test]
+    fn expand_macro() {
+        let m = Macro {
+            key: "CLICKTOKEN".to_string(),
+            value: Some("A7D800F2716DB".to_string()),
+            ext: None,
+        };
+
+        assert_eq!(m.expand("<a href=\"x?t=${CLICKTOKEN}\">"), "<a href=\"x?t=A7D800F2716DB\">");
+        assert_eq!(m.expand("${TIMESTAMP}"), "${TIMESTAMP}");
+    }
+}
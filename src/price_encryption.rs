@@ -0,0 +1,313 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Winning price encryption with the HMAC-SHA1 scheme introduced by
+// DoubleClick Ad Exchange and used by many exchanges since.
+//
+// The price, in micros of the currency, is encoded as 8 big-endian bytes and
+// XORed with the first 8 bytes of HMAC-SHA1(encryption key, IV). The message
+// is the 16-byte IV, the 8 encrypted bytes and the first 4 bytes of
+// HMAC-SHA1(integrity key, price bytes || IV), encoded as web-safe base64.
+// The IV starts with the seconds and microseconds of the time of encryption,
+// which lets the receiver reject stale messages.
+
+use std::error;
+use std::fmt::{self, Display};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+use crate::base64;
+use crate::v2_5::PriceEncryption;
+
+const IV_LENGTH: usize = 16;
+const PRICE_LENGTH: usize = 8;
+const SIGNATURE_LENGTH: usize = 4;
+const MESSAGE_LENGTH: usize = IV_LENGTH + PRICE_LENGTH + SIGNATURE_LENGTH;
+
+pub(crate) const MICROS_PER_UNIT: f64 = 1_000_000.0;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PriceEncryptionError {
+    // A key or the message contains a character outside of the base64
+    // alphabet.
+    InvalidCharacter(char),
+    // The message is not 28 bytes long once decoded.
+    InvalidLength(usize),
+    // The integrity signature does not match the decrypted price.
+    InvalidSignature,
+    // The IV is older than the maximum age, by the given duration.
+    StaleIv(Duration),
+}
+
+impl Display for PriceEncryptionError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PriceEncryptionError::InvalidCharacter(c) => write!(formatter, "invalid base64 character {:?}", c),
+            PriceEncryptionError::InvalidLength(n) => {
+                write!(formatter, "encrypted price must be {} bytes long, not {}", MESSAGE_LENGTH, n)
+            }
+            PriceEncryptionError::InvalidSignature => formatter.write_str("encrypted price has an invalid signature"),
+            PriceEncryptionError::StaleIv(age) => write!(formatter, "encrypted price is stale by {:?}", age),
+        }
+    }
+}
+
+impl error::Error for PriceEncryptionError {}
+
+/// Encrypts and decrypts winning prices with a pair of keys shared between
+/// the exchange and the bidder.
+pub struct PriceCrypter {
+    encryption_key: Vec<u8>,
+    integrity_key: Vec<u8>,
+    // Identifies this encrypter in the IVs it generates, along with a
+    // counter, so that IVs stay unique within a microsecond.
+    server_id: u32,
+    counter: AtomicU32,
+}
+
+impl PriceCrypter {
+    pub fn new(encryption_key: Vec<u8>, integrity_key: Vec<u8>) -> PriceCrypter {
+        PriceCrypter {
+            encryption_key,
+            integrity_key,
+            server_id: 0,
+            counter: AtomicU32::new(0),
+        }
+    }
+
+    /// Builds a crypter from keys encoded as web-safe base64, the form in
+    /// which exchanges usually hand them out.
+    pub fn from_base64_keys(encryption_key: &str, integrity_key: &str) -> Result<PriceCrypter, PriceEncryptionError> {
+        Ok(PriceCrypter::new(
+            base64::decode(encryption_key).map_err(PriceEncryptionError::InvalidCharacter)?,
+            base64::decode(integrity_key).map_err(PriceEncryptionError::InvalidCharacter)?,
+        ))
+    }
+
+    /// Sets the ID of this server in the IVs it generates.
+    pub fn with_server_id(mut self, server_id: u32) -> PriceCrypter {
+        self.server_id = server_id;
+        self
+    }
+
+    /// Encrypts a price in micros with the given IV.
+    pub fn encrypt_with_iv(&self, micros: u64, iv: [u8; IV_LENGTH]) -> String {
+        let price = micros.to_be_bytes();
+        let pad = hmac_sha1(&self.encryption_key, &[&iv]);
+
+        let mut message = Vec::with_capacity(MESSAGE_LENGTH);
+        message.extend_from_slice(&iv);
+        message.extend(price.iter().zip(pad.iter()).map(|(p, k)| p ^ k));
+        message.extend_from_slice(&hmac_sha1(&self.integrity_key, &[&price, &iv])[..SIGNATURE_LENGTH]);
+
+        base64::encode_url_safe(&message)
+    }
+
+    /// Encrypts a price in micros with an IV made of the given time, the
+    /// server ID and a counter.
+    pub fn encrypt_at(&self, micros: u64, time: SystemTime) -> String {
+        let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let counter = self.counter.fetch_add(1, Ordering::Relaxed);
+
+        let mut iv = [0u8; IV_LENGTH];
+        iv[0..4].copy_from_slice(&(since_epoch.as_secs() as u32).to_be_bytes());
+        iv[4..8].copy_from_slice(&since_epoch.subsec_micros().to_be_bytes());
+        iv[8..12].copy_from_slice(&self.server_id.to_be_bytes());
+        iv[12..16].copy_from_slice(&counter.to_be_bytes());
+
+        self.encrypt_with_iv(micros, iv)
+    }
+
+    /// Encrypts a price in micros with an IV made of the current time.
+    pub fn encrypt(&self, micros: u64) -> String {
+        self.encrypt_at(micros, SystemTime::now())
+    }
+
+    /// Decrypts a price in micros, checking its integrity signature.
+    pub fn decrypt(&self, encrypted: &str) -> Result<u64, PriceEncryptionError> {
+        self.decrypt_with_iv(encrypted).map(|(micros, _)| micros)
+    }
+
+    /// Decrypts a price in micros like `decrypt`, and checks that its IV is
+    /// at most `max_age` older than `now`.
+    pub fn decrypt_fresh(&self, encrypted: &str, now: SystemTime, max_age: Duration) -> Result<u64, PriceEncryptionError> {
+        let (micros, iv) = self.decrypt_with_iv(encrypted)?;
+
+        let age = now.duration_since(iv_time(&iv)).unwrap_or_default();
+        if age > max_age {
+            return Err(PriceEncryptionError::StaleIv(age - max_age));
+        }
+
+        Ok(micros)
+    }
+
+    fn decrypt_with_iv(&self, encrypted: &str) -> Result<(u64, [u8; IV_LENGTH]), PriceEncryptionError> {
+        let message = base64::decode(encrypted).map_err(PriceEncryptionError::InvalidCharacter)?;
+        if message.len() != MESSAGE_LENGTH {
+            return Err(PriceEncryptionError::InvalidLength(message.len()));
+        }
+
+        let (iv, rest) = message.split_at(IV_LENGTH);
+        let (encrypted_price, signature) = rest.split_at(PRICE_LENGTH);
+
+        let pad = hmac_sha1(&self.encryption_key, &[iv]);
+        let mut price = [0u8; PRICE_LENGTH];
+        for (i, byte) in price.iter_mut().enumerate() {
+            *byte = encrypted_price[i] ^ pad[i];
+        }
+
+        let mut mac = Hmac::<Sha1>::new_from_slice(&self.integrity_key).expect("HMAC accepts keys of any length");
+        mac.update(&price);
+        mac.update(iv);
+        mac.verify_truncated_left(signature)
+            .map_err(|_| PriceEncryptionError::InvalidSignature)?;
+
+        let mut iv_bytes = [0u8; IV_LENGTH];
+        iv_bytes.copy_from_slice(iv);
+
+        Ok((u64::from_be_bytes(price), iv_bytes))
+    }
+}
+
+// Plugs the crypter into auction macro expansion, for ${AUCTION_PRICE}.
+impl PriceEncryption for PriceCrypter {
+    fn encrypt_price(&self, price: f64) -> String {
+        self.encrypt(price_to_micros(price))
+    }
+}
+
+/// Converts a price in units of the currency to micros, rounding to the
+/// nearest micro. Negative prices become 0.
+pub fn price_to_micros(price: f64) -> u64 {
+    (price * MICROS_PER_UNIT).round().max(0.0) as u64
+}
+
+/// Converts a price in micros to units of the currency.
+pub fn micros_to_price(micros: u64) -> f64 {
+    micros as f64 / MICROS_PER_UNIT
+}
+
+fn hmac_sha1(key: &[u8], parts: &[&[u8]]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn iv_time(iv: &[u8; IV_LENGTH]) -> SystemTime {
+    let seconds = u32::from_be_bytes([iv[0], iv[1], iv[2], iv[3]]);
+    let micros = u32::from_be_bytes([iv[4], iv[5], iv[6], iv[7]]);
+
+    UNIX_EPOCH + Duration::from_secs(seconds as u64) + Duration::from_micros(micros as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::v2_5::{AuctionMacros, BidRequest, BidResponse};
+
+    // Test keys and vectors published with the DoubleClick Ad Exchange
+    // price decryption documentation.
+    const ENCRYPTION_KEY: &str = "skU7Ax_NL5pPAFyKdkfZjZz2-VhIN8bjj1rVFOaJ_5o=";
+    const INTEGRITY_KEY: &str = "arO23ykdNqUQ5LEoQ0FVmPkBd7xB5CO89PDZlSjpFxo=";
+
+    fn crypter() -> PriceCrypter {
+        PriceCrypter::from_base64_keys(ENCRYPTION_KEY, INTEGRITY_KEY).unwrap()
+    }
+
+    #[test]
+    fn decrypt_known_vector() {
+        let crypter = crypter();
+
+        assert_eq!(crypter.decrypt("YWJjMTIzZGVmNDU2Z2hpN7fhCuPemCce_6msaw"), Ok(100));
+        assert_eq!(crypter.decrypt("YWJjMTIzZGVmNDU2Z2hpN7fhCuPemCce_6msaw=="), Ok(100));
+    }
+
+    #[test]
+    fn encrypt_known_vector() {
+        let iv = *b"abc123def456ghi7";
+
+        assert_eq!(crypter().encrypt_with_iv(100, iv), "YWJjMTIzZGVmNDU2Z2hpN7fhCuPemCce_6msaw");
+    }
+
+    #[test]
+    fn encrypt_then_decrypt() {
+        let crypter = crypter().with_server_id(42);
+        let now = SystemTime::now();
+
+        let first = crypter.encrypt_at(1_234_567, now);
+        let second = crypter.encrypt_at(1_234_567, now);
+        assert_ne!(first, second);
+
+        assert_eq!(crypter.decrypt(&first), Ok(1_234_567));
+        assert_eq!(crypter.decrypt_fresh(&second, now, Duration::from_secs(1)), Ok(1_234_567));
+    }
+
+    #[test]
+    fn decrypt_invalid() {
+        let crypter = crypter();
+
+        assert_eq!(
+            crypter.decrypt("YWJjMTIzZGVmNDU2Z2hpN7fhCuPemCce_6msAw"),
+            Err(PriceEncryptionError::InvalidSignature)
+        );
+        assert_eq!(crypter.decrypt("YWJj"), Err(PriceEncryptionError::InvalidLength(3)));
+        assert_eq!(crypter.decrypt("YWJj*"), Err(PriceEncryptionError::InvalidCharacter('*')));
+
+        let other = PriceCrypter::new(b"encryption".to_vec(), b"integrity".to_vec());
+        assert_eq!(
+            other.decrypt("YWJjMTIzZGVmNDU2Z2hpN7fhCuPemCce_6msaw"),
+            Err(PriceEncryptionError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn decrypt_stale() {
+        let crypter = crypter();
+        let then = UNIX_EPOCH + Duration::from_secs(1_500_000_000);
+        let encrypted = crypter.encrypt_at(100, then);
+
+        assert_eq!(crypter.decrypt_fresh(&encrypted, then + Duration::from_secs(60), Duration::from_secs(60)), Ok(100));
+        assert_eq!(
+            crypter.decrypt_fresh(&encrypted, then + Duration::from_secs(90), Duration::from_secs(60)),
+            Err(PriceEncryptionError::StaleIv(Duration::from_secs(30)))
+        );
+    }
+
+    #[test]
+    fn price_conversions() {
+        assert_eq!(price_to_micros(1.25), 1_250_000);
+        assert_eq!(price_to_micros(0.0000015), 2);
+        assert_eq!(price_to_micros(-1.0), 0);
+        assert_eq!(micros_to_price(2_500_000), 2.5);
+    }
+
+    #[test]
+    fn expand_auction_price() {
+        let request: BidRequest = serde_json::from_str(r#"{"id":"1","imp":[{"id":"1","banner":{}}]}"#).unwrap();
+        let response: BidResponse = serde_json::from_str(
+            r#"{"id":"1","seatbid":[{"bid":[{"id":"1","impid":"1","price":3,"nurl":"https://win.example/?p=${AUCTION_PRICE}"}]}]}"#,
+        )
+        .unwrap();
+        let crypter = crypter();
+
+        let nurl = AuctionMacros::new(&request, &response, None, &response.seat_bid[0].bid[0], 2.75)
+            .with_price_encryption(&crypter)
+            .expand_bid()
+            .nurl
+            .unwrap();
+
+        let encrypted = nurl.trim_start_matches("https://win.example/?p=");
+        assert_eq!(crypter.decrypt(encrypted).map(micros_to_price), Ok(2.75));
+    }
+}
@@ -0,0 +1,245 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::collections::BTreeSet;
+
+use crate::base64;
+
+// Reads the bit fields of a web-safe base64 string (as used by TCF and GPP)
+// most significant bit first. Every character carries six bits, so there is
+// no need to go through bytes. Padding is ignored, and the standard alphabet
+// is accepted as well since some CMPs use it.
+pub(crate) struct BitReader {
+    sextets: Vec<u8>,
+    position: usize,
+}
+
+impl BitReader {
+    pub(crate) fn from_base64(s: &str) -> Result<BitReader, char> {
+        Ok(BitReader {
+            sextets: base64::decode_sextets(s)?,
+            position: 0,
+        })
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.sextets.len() * 6 - self.position
+    }
+
+    pub(crate) fn read(&mut self, n: usize) -> Option<u64> {
+        if n > 64 || n > self.remaining() {
+            return None;
+        }
+
+        let mut value = 0u64;
+        for _ in 0..n {
+            let sextet = self.sextets[self.position / 6];
+            let bit = (sextet >> (5 - self.position % 6)) & 1;
+            value = (value << 1) | bit as u64;
+            self.position += 1;
+        }
+
+        Some(value)
+    }
+
+    pub(crate) fn read_bool(&mut self) -> Option<bool> {
+        self.read(1).map(|bit| bit == 1)
+    }
+
+    // Reads n bits where bit i set means ID i + 1 is in the set.
+    pub(crate) fn read_bitfield(&mut self, n: usize) -> Option<BTreeSet<u16>> {
+        let mut ids = BTreeSet::new();
+        for id in 1..=n {
+            if self.read_bool()? {
+                ids.insert(id as u16);
+            }
+        }
+
+        Some(ids)
+    }
+
+    // Reads a Fibonacci-encoded integer: bit i stands for the (i + 2)th
+    // Fibonacci number, least significant first, and two consecutive set
+    // bits terminate the value.
+    pub(crate) fn read_fibonacci(&mut self) -> Option<u64> {
+        let (mut previous, mut current) = (1u64, 1u64);
+        let mut value = 0u64;
+        let mut last_bit = false;
+
+        loop {
+            let bit = self.read_bool()?;
+            if bit && last_bit {
+                return Some(value);
+            }
+            if bit {
+                value = value.checked_add(current)?;
+            }
+            let next = previous.checked_add(current)?;
+            previous = current;
+            current = next;
+            last_bit = bit;
+        }
+    }
+
+    // Reads a Fibonacci-encoded list of integer ranges, where each start is
+    // an offset from the previous end, and each end an offset from its start.
+    pub(crate) fn read_fibonacci_range(&mut self) -> Option<Vec<u16>> {
+        let mut ids = vec![];
+        let mut last = 0u64;
+        for _ in 0..self.read(12)? {
+            let is_range = self.read_bool()?;
+            let start = last.checked_add(self.read_fibonacci()?)?;
+            let end = if is_range { start.checked_add(self.read_fibonacci()?)? } else { start };
+            if end > u16::MAX as u64 {
+                return None;
+            }
+            ids.extend(start as u16..=end as u16);
+            last = end;
+        }
+
+        Some(ids)
+    }
+
+    // Reads a string of n letters, each encoded on six bits as an offset from ‘A’.
+    pub(crate) fn read_letters(&mut self, n: usize) -> Option<String> {
+        (0..n).map(|_| self.read(6).map(|letter| (b'A' + letter as u8) as char)).collect()
+    }
+}
+
+// Writes bit fields into a web-safe base64 string, for building test vectors.
+#[cfg(test)]
+pub(crate) struct BitWriter {
+    bits: Vec<bool>,
+}
+
+#[cfg(test)]
+impl BitWriter {
+    pub(crate) fn new() -> BitWriter {
+        BitWriter { bits: vec![] }
+    }
+
+    pub(crate) fn write(&mut self, value: u64, n: usize) -> &mut BitWriter {
+        for i in (0..n).rev() {
+            self.bits.push((value >> i) & 1 == 1);
+        }
+        self
+    }
+
+    pub(crate) fn write_bool(&mut self, value: bool) -> &mut BitWriter {
+        self.write(value as u64, 1)
+    }
+
+    pub(crate) fn write_bitfield(&mut self, ids: &[u16], n: usize) -> &mut BitWriter {
+        for id in 1..=n {
+            self.write_bool(ids.contains(&(id as u16)));
+        }
+        self
+    }
+
+    pub(crate) fn write_fibonacci(&mut self, value: u64) -> &mut BitWriter {
+        let mut fibonacci = vec![1u64, 2];
+        while fibonacci[fibonacci.len() - 1] <= value {
+            let next = fibonacci[fibonacci.len() - 1] + fibonacci[fibonacci.len() - 2];
+            fibonacci.push(next);
+        }
+
+        let mut bits = vec![false; fibonacci.len()];
+        let mut rest = value;
+        for i in (0..fibonacci.len()).rev() {
+            if fibonacci[i] <= rest {
+                bits[i] = true;
+                rest -= fibonacci[i];
+            }
+        }
+
+        while bits.last() == Some(&false) {
+            bits.pop();
+        }
+        for bit in bits {
+            self.write_bool(bit);
+        }
+        self.write_bool(true)
+    }
+
+    pub(crate) fn write_letters(&mut self, letters: &str) -> &mut BitWriter {
+        for letter in letters.bytes() {
+            self.write((letter - b'A') as u64, 6);
+        }
+        self
+    }
+
+    pub(crate) fn to_base64(&self) -> String {
+        let sextets = self
+            .bits
+            .chunks(6)
+            .map(|chunk| (0..6).fold(0, |acc, i| (acc << 1) | *chunk.get(i).unwrap_or(&false) as u8))
+            .collect::<Vec<_>>();
+
+        base64::encode_sextets(&sextets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_bits() {
+        // ‘C’ = 000010, ‘P’ = 001111
+        let mut reader = BitReader::from_base64("CP").unwrap();
+
+        assert_eq!(reader.remaining(), 12);
+        assert_eq!(reader.read(6), Some(2));
+        assert_eq!(reader.read_bool(), Some(false));
+        assert_eq!(reader.read(5), Some(15));
+        assert_eq!(reader.read(1), None);
+    }
+
+    #[test]
+    fn invalid_character() {
+        assert_eq!(BitReader::from_base64("CP.A").err(), Some('.'));
+    }
+
+    #[test]
+    fn read_fibonacci() {
+        for value in 1..100 {
+            let encoded = BitWriter::new().write_fibonacci(value).write(0, 6).to_base64();
+            assert_eq!(BitReader::from_base64(&encoded).unwrap().read_fibonacci(), Some(value));
+        }
+    }
+
+    #[test]
+    fn read_fibonacci_range() {
+        // 2 entries: 2 alone, then 6-8 as offsets 4 and 2.
+        let encoded = BitWriter::new()
+            .write(2, 12)
+            .write_bool(false)
+            .write_fibonacci(2)
+            .write_bool(true)
+            .write_fibonacci(4)
+            .write_fibonacci(2)
+            .to_base64();
+        let mut reader = BitReader::from_base64(&encoded).unwrap();
+
+        assert_eq!(reader.read_fibonacci_range(), Some(vec![2, 6, 7, 8]));
+    }
+
+    #[test]
+    fn write_then_read() {
+        let encoded = BitWriter::new()
+            .write(2, 6)
+            .write_letters("EN")
+            .write_bitfield(&[1, 3], 4)
+            .to_base64();
+        let mut reader = BitReader::from_base64(&encoded).unwrap();
+
+        assert_eq!(reader.read(6), Some(2));
+        assert_eq!(reader.read_letters(2), Some("EN".to_string()));
+        assert_eq!(reader.read_bitfield(4), Some([1, 3].iter().cloned().collect()));
+    }
+}
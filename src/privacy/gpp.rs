@@ -0,0 +1,450 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Decoder for IAB Tech Lab Global Privacy Platform strings, as carried in
+// `Regs.gpp` or `Regs.ext.gpp` along with the IDs of the applicable sections.
+//
+// A GPP string is a header followed by one encoded section per ID the header
+// lists, separated by ‘~’. The TCF EU v2, USP v1 and US National sections are
+// decoded; other sections are kept encoded.
+
+use std::error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use super::bits::BitReader;
+use super::tcf_v2::{TcString, TcfError};
+use super::us_privacy::{UsPrivacy, UsPrivacyError, UsPrivacyFlag};
+
+const SECTION_SEPARATOR: char = '~';
+const SUBSECTION_SEPARATOR: char = '.';
+
+const HEADER_TYPE: u64 = 3;
+
+pub const SECTION_TCF_EU_V2: u16 = 2;
+pub const SECTION_USP_V1: u16 = 6;
+pub const SECTION_US_NATIONAL: u16 = 7;
+
+const US_NATIONAL_SUBSECTION_GPC: u64 = 1;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GppError {
+    // The string is empty.
+    Empty,
+    // The header or a section contains a character outside of the base64
+    // alphabet.
+    InvalidCharacter(char),
+    // The header or a section ends before all of its fields could be read.
+    UnexpectedEnd,
+    // The header is not of type 3.
+    InvalidHeaderType(u8),
+    // The header lists a different number of sections (first) than the
+    // string carries (second).
+    SectionCountMismatch(usize, usize),
+    // A section (first) is of a version (second) this decoder does not know.
+    UnsupportedSectionVersion(u16, u8),
+    // A section (first) has a field with a value outside of its range.
+    InvalidSectionValue(u16),
+    // The TCF EU v2 section is not a valid TC string.
+    TcfEuV2(TcfError),
+    // The USP v1 section is not a valid US Privacy string.
+    UspV1(UsPrivacyError),
+}
+
+impl Display for GppError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GppError::Empty => formatter.write_str("GPP string is empty"),
+            GppError::InvalidCharacter(c) => write!(formatter, "GPP string contains invalid character {:?}", c),
+            GppError::UnexpectedEnd => formatter.write_str("GPP string is truncated"),
+            GppError::InvalidHeaderType(t) => write!(formatter, "GPP header has invalid type {}", t),
+            GppError::SectionCountMismatch(expected, found) => write!(
+                formatter,
+                "GPP header lists {} sections but the string has {}",
+                expected, found
+            ),
+            GppError::UnsupportedSectionVersion(id, v) => {
+                write!(formatter, "GPP section {} version {} is not supported", id, v)
+            }
+            GppError::InvalidSectionValue(id) => write!(formatter, "GPP section {} has an invalid value", id),
+            GppError::TcfEuV2(e) => write!(formatter, "GPP TCF EU v2 section is invalid: {}", e),
+            GppError::UspV1(e) => write!(formatter, "GPP USP v1 section is invalid: {}", e),
+        }
+    }
+}
+
+impl error::Error for GppError {}
+
+/// A decoded GPP string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Gpp {
+    pub version: u8,
+    /// The sections, in the order of the IDs listed by the header.
+    pub sections: Vec<GppSection>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GppSection {
+    TcfEuV2(Box<TcString>),
+    UspV1(UsPrivacy),
+    UsNational(UsNational),
+    /// A section this decoder does not know, by ID and encoded value.
+    Other(u16, String),
+}
+
+/// The US National Privacy section (version 1 or 2). The two-bit fields
+/// are either not applicable, yes or no; for the opt-out fields yes means
+/// the user opted out, and for the consent fields it means they did not
+/// consent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UsNational {
+    pub version: u8,
+    pub sharing_notice: UsPrivacyFlag,
+    pub sale_opt_out_notice: UsPrivacyFlag,
+    pub sharing_opt_out_notice: UsPrivacyFlag,
+    pub targeted_advertising_opt_out_notice: UsPrivacyFlag,
+    pub sensitive_data_processing_opt_out_notice: UsPrivacyFlag,
+    pub sensitive_data_limit_use_notice: UsPrivacyFlag,
+    pub sale_opt_out: UsPrivacyFlag,
+    pub sharing_opt_out: UsPrivacyFlag,
+    pub targeted_advertising_opt_out: UsPrivacyFlag,
+    /// One flag per sensitive data category, 12 in version 1 and 16 in version 2.
+    pub sensitive_data_processing: Vec<UsPrivacyFlag>,
+    /// One flag per child age band, 2 in version 1 and 3 in version 2.
+    pub known_child_sensitive_data_consents: Vec<UsPrivacyFlag>,
+    pub personal_data_consents: UsPrivacyFlag,
+    pub mspa_covered_transaction: UsPrivacyFlag,
+    pub mspa_opt_out_option_mode: UsPrivacyFlag,
+    pub mspa_service_provider_mode: UsPrivacyFlag,
+    /// The Global Privacy Control signal, if the subsection is present.
+    pub gpc: Option<bool>,
+}
+
+impl Gpp {
+    pub fn parse(s: &str) -> Result<Gpp, GppError> {
+        let mut parts = s.split(SECTION_SEPARATOR);
+
+        let (version, section_ids) = match parts.next() {
+            Some(header) if !header.is_empty() => parse_header(header)?,
+            _ => return Err(GppError::Empty),
+        };
+
+        let encoded: Vec<&str> = parts.collect();
+        if encoded.len() != section_ids.len() {
+            return Err(GppError::SectionCountMismatch(section_ids.len(), encoded.len()));
+        }
+
+        let sections = section_ids
+            .into_iter()
+            .zip(encoded)
+            .map(|(id, section)| GppSection::parse(id, section))
+            .collect::<Result<Vec<GppSection>, GppError>>()?;
+
+        Ok(Gpp { version, sections })
+    }
+
+    pub fn section_ids(&self) -> Vec<u16> {
+        self.sections.iter().map(GppSection::id).collect()
+    }
+
+    pub fn section(&self, id: u16) -> Option<&GppSection> {
+        self.sections.iter().find(|section| section.id() == id)
+    }
+
+    pub fn tcf_eu_v2(&self) -> Option<&TcString> {
+        match self.section(SECTION_TCF_EU_V2)? {
+            GppSection::TcfEuV2(tc_string) => Some(tc_string.as_ref()),
+            _ => None,
+        }
+    }
+
+    pub fn usp_v1(&self) -> Option<&UsPrivacy> {
+        match self.section(SECTION_USP_V1)? {
+            GppSection::UspV1(us_privacy) => Some(us_privacy),
+            _ => None,
+        }
+    }
+
+    pub fn us_national(&self) -> Option<&UsNational> {
+        match self.section(SECTION_US_NATIONAL)? {
+            GppSection::UsNational(us_national) => Some(us_national),
+            _ => None,
+        }
+    }
+
+    /// Whether any of the applicable sections signals that the user opted
+    /// out of the sale of their personal information. An empty list makes
+    /// every section applicable.
+    pub fn is_opted_out_of_sale(&self, applicable: &[u16]) -> bool {
+        self.applicable_sections(applicable).any(|section| match section {
+            GppSection::UspV1(us_privacy) => us_privacy.is_opted_out_of_sale(),
+            GppSection::UsNational(us_national) => us_national.is_opted_out_of_sale(),
+            _ => false,
+        })
+    }
+
+    /// Whether any of the applicable sections signals that the user opted
+    /// out of targeted advertising. An empty list makes every section
+    /// applicable.
+    pub fn is_opted_out_of_targeted_advertising(&self, applicable: &[u16]) -> bool {
+        self.applicable_sections(applicable).any(|section| match section {
+            GppSection::UsNational(us_national) => us_national.is_opted_out_of_targeted_advertising(),
+            _ => false,
+        })
+    }
+
+    fn applicable_sections<'a>(&'a self, applicable: &'a [u16]) -> impl Iterator<Item = &'a GppSection> {
+        self.sections
+            .iter()
+            .filter(move |section| applicable.is_empty() || applicable.contains(&section.id()))
+    }
+}
+
+impl FromStr for Gpp {
+    type Err = GppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Gpp::parse(s)
+    }
+}
+
+impl GppSection {
+    fn parse(id: u16, s: &str) -> Result<GppSection, GppError> {
+        match id {
+            SECTION_TCF_EU_V2 => TcString::parse(s)
+                .map(|tc_string| GppSection::TcfEuV2(Box::new(tc_string)))
+                .map_err(GppError::TcfEuV2),
+            SECTION_USP_V1 => s.parse().map(GppSection::UspV1).map_err(GppError::UspV1),
+            SECTION_US_NATIONAL => UsNational::parse(s).map(GppSection::UsNational),
+            _ => Ok(GppSection::Other(id, s.to_string())),
+        }
+    }
+
+    pub fn id(&self) -> u16 {
+        match self {
+            GppSection::TcfEuV2(_) => SECTION_TCF_EU_V2,
+            GppSection::UspV1(_) => SECTION_USP_V1,
+            GppSection::UsNational(_) => SECTION_US_NATIONAL,
+            GppSection::Other(id, _) => *id,
+        }
+    }
+}
+
+impl UsNational {
+    fn parse(s: &str) -> Result<UsNational, GppError> {
+        let mut subsections = s.split(SUBSECTION_SEPARATOR);
+
+        let mut reader = reader(subsections.next().unwrap_or_default())?;
+        let version = read(&mut reader, 6)? as u8;
+        let (sensitive_data_categories, child_age_bands) = match version {
+            1 => (12, 2),
+            2 => (16, 3),
+            _ => return Err(GppError::UnsupportedSectionVersion(SECTION_US_NATIONAL, version)),
+        };
+
+        let mut us_national = UsNational {
+            version,
+            sharing_notice: read_flag(&mut reader)?,
+            sale_opt_out_notice: read_flag(&mut reader)?,
+            sharing_opt_out_notice: read_flag(&mut reader)?,
+            targeted_advertising_opt_out_notice: read_flag(&mut reader)?,
+            sensitive_data_processing_opt_out_notice: read_flag(&mut reader)?,
+            sensitive_data_limit_use_notice: read_flag(&mut reader)?,
+            sale_opt_out: read_flag(&mut reader)?,
+            sharing_opt_out: read_flag(&mut reader)?,
+            targeted_advertising_opt_out: read_flag(&mut reader)?,
+            sensitive_data_processing: read_flags(&mut reader, sensitive_data_categories)?,
+            known_child_sensitive_data_consents: read_flags(&mut reader, child_age_bands)?,
+            personal_data_consents: read_flag(&mut reader)?,
+            mspa_covered_transaction: read_flag(&mut reader)?,
+            mspa_opt_out_option_mode: read_flag(&mut reader)?,
+            mspa_service_provider_mode: read_flag(&mut reader)?,
+            gpc: None,
+        };
+
+        for subsection in subsections {
+            let mut subsection = self::reader(subsection)?;
+            if read(&mut subsection, 2)? == US_NATIONAL_SUBSECTION_GPC {
+                us_national.gpc = Some(read(&mut subsection, 1)? == 1);
+            }
+        }
+
+        Ok(us_national)
+    }
+
+    /// Whether the user opted out of the sale of their personal information.
+    pub fn is_opted_out_of_sale(&self) -> bool {
+        self.sale_opt_out == UsPrivacyFlag::Yes
+    }
+
+    /// Whether the user opted out of targeted advertising, or of the sharing
+    /// of their personal information for cross-context behavioral advertising.
+    pub fn is_opted_out_of_targeted_advertising(&self) -> bool {
+        self.targeted_advertising_opt_out == UsPrivacyFlag::Yes || self.sharing_opt_out == UsPrivacyFlag::Yes
+    }
+}
+
+fn parse_header(s: &str) -> Result<(u8, Vec<u16>), GppError> {
+    let mut reader = reader(s)?;
+
+    let header_type = read(&mut reader, 6)?;
+    if header_type != HEADER_TYPE {
+        return Err(GppError::InvalidHeaderType(header_type as u8));
+    }
+    let version = read(&mut reader, 6)? as u8;
+    let section_ids = reader.read_fibonacci_range().ok_or(GppError::UnexpectedEnd)?;
+
+    Ok((version, section_ids))
+}
+
+fn reader(s: &str) -> Result<BitReader, GppError> {
+    BitReader::from_base64(s).map_err(GppError::InvalidCharacter)
+}
+
+fn read(reader: &mut BitReader, n: usize) -> Result<u64, GppError> {
+    reader.read(n).ok_or(GppError::UnexpectedEnd)
+}
+
+fn read_flag(reader: &mut BitReader) -> Result<UsPrivacyFlag, GppError> {
+    match read(reader, 2)? {
+        0 => Ok(UsPrivacyFlag::NotApplicable),
+        1 => Ok(UsPrivacyFlag::Yes),
+        2 => Ok(UsPrivacyFlag::No),
+        _ => Err(GppError::InvalidSectionValue(SECTION_US_NATIONAL)),
+    }
+}
+
+fn read_flags(reader: &mut BitReader, n: usize) -> Result<Vec<UsPrivacyFlag>, GppError> {
+    (0..n).map(|_| read_flag(reader)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::bits::BitWriter;
+    use super::*;
+
+    const TCF_EU_V2: &str = "CPXxRfAPXxRfAAfKABENB-CgAAAAAAAAAAYgAAAAAAAA";
+
+    // Writes a version 1 US National section with every notice given and
+    // the given sale and targeted advertising opt-outs.
+    fn us_national(sale_opt_out: u64, targeted_advertising_opt_out: u64) -> String {
+        let mut writer = BitWriter::new();
+        writer.write(1, 6);
+        for _ in 0..6 {
+            writer.write(1, 2);
+        }
+        writer.write(sale_opt_out, 2).write(2, 2).write(targeted_advertising_opt_out, 2);
+        for _ in 0..14 {
+            writer.write(0, 2);
+        }
+        writer.write(0, 2).write(1, 2).write(2, 2).write(1, 2);
+        writer.to_base64()
+    }
+
+    #[test]
+    fn parse_tcf_eu_v2() {
+        let gpp: Gpp = format!("DBABMA~{}", TCF_EU_V2).parse().unwrap();
+
+        assert_eq!(gpp.version, 1);
+        assert_eq!(gpp.section_ids(), vec![SECTION_TCF_EU_V2]);
+        assert_eq!(gpp.tcf_eu_v2(), Some(&TcString::parse(TCF_EU_V2).unwrap()));
+        assert_eq!(gpp.usp_v1(), None);
+        assert!(!gpp.is_opted_out_of_sale(&[]));
+    }
+
+    #[test]
+    fn parse_usp_v1() {
+        let gpp: Gpp = "DBABTA~1YYN".parse().unwrap();
+
+        assert_eq!(gpp.section_ids(), vec![SECTION_USP_V1]);
+        assert_eq!(gpp.usp_v1(), Some(&"1YYN".parse().unwrap()));
+        assert!(gpp.is_opted_out_of_sale(&[]));
+        assert!(gpp.is_opted_out_of_sale(&[SECTION_USP_V1]));
+        assert!(!gpp.is_opted_out_of_sale(&[SECTION_US_NATIONAL]));
+        assert!(!gpp.is_opted_out_of_targeted_advertising(&[]));
+    }
+
+    #[test]
+    fn parse_several_sections() {
+        let gpp: Gpp = format!("DBACNYA~{}~1YNN", TCF_EU_V2).parse().unwrap();
+
+        assert_eq!(gpp.section_ids(), vec![SECTION_TCF_EU_V2, SECTION_USP_V1]);
+        assert!(gpp.tcf_eu_v2().is_some());
+        assert!(gpp.usp_v1().is_some());
+    }
+
+    #[test]
+    fn parse_us_national() {
+        let gpp: Gpp = "DBABLA~BVQqAAAAAgA.QA".parse().unwrap();
+        let us_national = gpp.us_national().unwrap();
+
+        assert_eq!(us_national.version, 1);
+        assert_eq!(us_national.sharing_notice, UsPrivacyFlag::Yes);
+        assert_eq!(us_national.targeted_advertising_opt_out_notice, UsPrivacyFlag::Yes);
+        assert_eq!(us_national.sensitive_data_processing_opt_out_notice, UsPrivacyFlag::NotApplicable);
+        assert_eq!(us_national.sale_opt_out, UsPrivacyFlag::No);
+        assert_eq!(us_national.targeted_advertising_opt_out, UsPrivacyFlag::No);
+        assert_eq!(us_national.sensitive_data_processing.len(), 12);
+        assert_eq!(us_national.known_child_sensitive_data_consents.len(), 2);
+        assert_eq!(us_national.mspa_covered_transaction, UsPrivacyFlag::No);
+        assert_eq!(us_national.gpc, Some(false));
+        assert!(!gpp.is_opted_out_of_sale(&[]));
+        assert!(!gpp.is_opted_out_of_targeted_advertising(&[]));
+    }
+
+    #[test]
+    fn us_national_opt_outs() {
+        let gpp: Gpp = format!("DBABLA~{}", us_national(1, 2)).parse().unwrap();
+        assert!(gpp.is_opted_out_of_sale(&[SECTION_US_NATIONAL]));
+        assert!(!gpp.is_opted_out_of_targeted_advertising(&[SECTION_US_NATIONAL]));
+        assert_eq!(gpp.us_national().unwrap().mspa_service_provider_mode, UsPrivacyFlag::Yes);
+        assert_eq!(gpp.us_national().unwrap().gpc, None);
+
+        let gpp: Gpp = format!("DBABLA~{}", us_national(2, 1)).parse().unwrap();
+        assert!(!gpp.is_opted_out_of_sale(&[SECTION_US_NATIONAL]));
+        assert!(gpp.is_opted_out_of_targeted_advertising(&[SECTION_US_NATIONAL]));
+        assert!(!gpp.is_opted_out_of_targeted_advertising(&[SECTION_TCF_EU_V2]));
+    }
+
+    #[test]
+    fn keep_unknown_sections() {
+        // Section 8 (US California) is not decoded.
+        let header = BitWriter::new()
+            .write(3, 6)
+            .write(1, 6)
+            .write(1, 12)
+            .write_bool(false)
+            .write_fibonacci(8)
+            .to_base64();
+        let gpp: Gpp = format!("{}~BAAAAAAAAQA", header).parse().unwrap();
+
+        assert_eq!(gpp.sections, vec![GppSection::Other(8, "BAAAAAAAAQA".to_string())]);
+        assert!(!gpp.is_opted_out_of_sale(&[]));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(Gpp::parse(""), Err(GppError::Empty));
+        assert_eq!(Gpp::parse("DB*BMA~1YNN"), Err(GppError::InvalidCharacter('*')));
+        assert_eq!(Gpp::parse("CBABMA~1YNN"), Err(GppError::InvalidHeaderType(2)));
+        assert_eq!(Gpp::parse("DBAB"), Err(GppError::UnexpectedEnd));
+        assert_eq!(Gpp::parse("DBABTA"), Err(GppError::SectionCountMismatch(1, 0)));
+        assert_eq!(Gpp::parse("DBABTA~1YNN~1YNN"), Err(GppError::SectionCountMismatch(1, 2)));
+        assert_eq!(Gpp::parse("DBABTA~1YXN"), Err(GppError::UspV1(UsPrivacyError::InvalidFlag('X'))));
+        assert_eq!(Gpp::parse("DBABMA~"), Err(GppError::TcfEuV2(TcfError::Empty)));
+        assert_eq!(
+            Gpp::parse("DBABLA~DVQqAAAAAgA"),
+            Err(GppError::UnsupportedSectionVersion(SECTION_US_NATIONAL, 3))
+        );
+        assert_eq!(Gpp::parse("DBABLA~BVQq"), Err(GppError::UnexpectedEnd));
+        assert_eq!(
+            Gpp::parse("DBABLA~B_QqAAAAAgA"),
+            Err(GppError::InvalidSectionValue(SECTION_US_NATIONAL))
+        );
+    }
+}
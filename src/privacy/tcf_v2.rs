@@ -0,0 +1,550 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Decoder for IAB Europe Transparency & Consent Framework v2 consent strings
+// (“TC strings”), as carried in `User.ext.consent` when GDPR applies.
+
+use std::collections::BTreeSet;
+use std::error;
+use std::fmt::{self, Display};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use super::bits::BitReader;
+
+const SEGMENT_SEPARATOR: char = '.';
+
+const SEGMENT_DISCLOSED_VENDORS: u64 = 1;
+const SEGMENT_ALLOWED_VENDORS: u64 = 2;
+const SEGMENT_PUBLISHER_TC: u64 = 3;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TcfError {
+    // The string is empty.
+    Empty,
+    // The string contains a character outside of the base64 alphabet.
+    InvalidCharacter(char),
+    // A segment ends before all of its fields could be read.
+    UnexpectedEnd,
+    // The core segment is not of version 2.
+    UnsupportedVersion(u8),
+    // An optional segment has an unknown or misplaced type.
+    InvalidSegmentType(u8),
+    // A range of vendor IDs is reversed, or exceeds the section's maximum
+    // vendor ID.
+    InvalidRange(u16, u16),
+}
+
+impl Display for TcfError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TcfError::Empty => formatter.write_str("TC string is empty"),
+            TcfError::InvalidCharacter(c) => write!(formatter, "TC string contains invalid character {:?}", c),
+            TcfError::UnexpectedEnd => formatter.write_str("TC string segment is truncated"),
+            TcfError::UnsupportedVersion(v) => write!(formatter, "TC string version {} is not supported", v),
+            TcfError::InvalidSegmentType(t) => write!(formatter, "TC string has invalid segment type {}", t),
+            TcfError::InvalidRange(start, end) => write!(formatter, "TC string has invalid vendor range {}-{}", start, end),
+        }
+    }
+}
+
+impl error::Error for TcfError {}
+
+/// A decoded TC string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TcString {
+    pub core: CoreSegment,
+    /// Vendors the CMP disclosed to the user, if the segment is present.
+    pub disclosed_vendors: Option<BTreeSet<u16>>,
+    /// Vendors the publisher allows to use OOB signaling (TCF 2.0 only).
+    pub allowed_vendors: Option<BTreeSet<u16>>,
+    pub publisher_tc: Option<PublisherTc>,
+}
+
+/// The core segment, which every TC string starts with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoreSegment {
+    pub version: u8,
+    /// Epoch deciseconds when the string was created.
+    pub created: u64,
+    /// Epoch deciseconds when the string was last updated.
+    pub last_updated: u64,
+    pub cmp_id: u16,
+    pub cmp_version: u16,
+    pub consent_screen: u8,
+    /// Two-letter ISO 639-1 language code, in upper case.
+    pub consent_language: String,
+    pub vendor_list_version: u16,
+    pub tcf_policy_version: u8,
+    pub is_service_specific: bool,
+    pub use_non_standard_texts: bool,
+    pub special_feature_opt_ins: BTreeSet<u16>,
+    pub purposes_consent: BTreeSet<u16>,
+    pub purposes_legitimate_interest: BTreeSet<u16>,
+    pub purpose_one_treatment: bool,
+    /// Two-letter ISO 3166-1 alpha-2 country code of the publisher, in upper case.
+    pub publisher_country_code: String,
+    pub vendor_consents: BTreeSet<u16>,
+    pub vendor_legitimate_interests: BTreeSet<u16>,
+    pub publisher_restrictions: Vec<PublisherRestriction>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RestrictionType {
+    NotAllowed,
+    RequireConsent,
+    RequireLegitimateInterest,
+    Undefined,
+}
+
+/// A restriction the publisher put on the legal basis vendors may use for a purpose.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublisherRestriction {
+    pub purpose_id: u8,
+    pub restriction_type: RestrictionType,
+    /// The vendor ID ranges the restriction applies to, as encoded.
+    pub vendors: Vec<RangeInclusive<u16>>,
+}
+
+impl PublisherRestriction {
+    /// Whether the restriction applies to the given vendor.
+    pub fn applies_to(&self, vendor_id: u16) -> bool {
+        self.vendors.iter().any(|range| range.contains(&vendor_id))
+    }
+}
+
+/// The publisher transparency and consent segment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublisherTc {
+    pub purposes_consent: BTreeSet<u16>,
+    pub purposes_legitimate_interest: BTreeSet<u16>,
+    pub custom_purposes_consent: BTreeSet<u16>,
+    pub custom_purposes_legitimate_interest: BTreeSet<u16>,
+}
+
+impl TcString {
+    pub fn parse(s: &str) -> Result<TcString, TcfError> {
+        let mut segments = s.split(SEGMENT_SEPARATOR);
+
+        let core = match segments.next() {
+            Some(core) if !core.is_empty() => parse_core(&mut reader(core)?)?,
+            _ => return Err(TcfError::Empty),
+        };
+
+        let mut tc_string = TcString {
+            core,
+            disclosed_vendors: None,
+            allowed_vendors: None,
+            publisher_tc: None,
+        };
+
+        for segment in segments {
+            let mut reader = reader(segment)?;
+            match read(&mut reader, 3)? {
+                SEGMENT_DISCLOSED_VENDORS => tc_string.disclosed_vendors = Some(read_vendor_set(&mut reader)?),
+                SEGMENT_ALLOWED_VENDORS => tc_string.allowed_vendors = Some(read_vendor_set(&mut reader)?),
+                SEGMENT_PUBLISHER_TC => tc_string.publisher_tc = Some(parse_publisher_tc(&mut reader)?),
+                segment_type => return Err(TcfError::InvalidSegmentType(segment_type as u8)),
+            }
+        }
+
+        Ok(tc_string)
+    }
+
+    /// Whether the user consented to the given purpose.
+    pub fn purpose_consent(&self, purpose_id: u16) -> bool {
+        self.core.purposes_consent.contains(&purpose_id)
+    }
+
+    /// Whether the user was informed of, and did not object to, the given
+    /// purpose being processed under legitimate interest.
+    pub fn purpose_legitimate_interest(&self, purpose_id: u16) -> bool {
+        self.core.purposes_legitimate_interest.contains(&purpose_id)
+    }
+
+    /// Whether the user consented to the given vendor.
+    pub fn vendor_consent(&self, vendor_id: u16) -> bool {
+        self.core.vendor_consents.contains(&vendor_id)
+    }
+
+    /// Whether the given vendor may rely on legitimate interest.
+    pub fn vendor_legitimate_interest(&self, vendor_id: u16) -> bool {
+        self.core.vendor_legitimate_interests.contains(&vendor_id)
+    }
+
+    /// Whether the user opted in to the given special feature.
+    pub fn special_feature_opt_in(&self, feature_id: u16) -> bool {
+        self.core.special_feature_opt_ins.contains(&feature_id)
+    }
+
+    /// The restriction the publisher put on the given vendor for the given purpose, if any.
+    pub fn publisher_restriction(&self, vendor_id: u16, purpose_id: u8) -> Option<&RestrictionType> {
+        self.core
+            .publisher_restrictions
+            .iter()
+            .find(|restriction| restriction.purpose_id == purpose_id && restriction.applies_to(vendor_id))
+            .map(|restriction| &restriction.restriction_type)
+    }
+
+    /// Whether the given vendor may process data for the given purpose: the
+    /// publisher does not disallow it, and the vendor has consent for both
+    /// itself and the purpose, or legitimate interest for both, as the
+    /// publisher’s restrictions allow.
+    pub fn vendor_allowed_for_purpose(&self, vendor_id: u16, purpose_id: u8) -> bool {
+        let consent = self.vendor_consent(vendor_id) && self.purpose_consent(purpose_id as u16);
+        let legitimate_interest =
+            self.vendor_legitimate_interest(vendor_id) && self.purpose_legitimate_interest(purpose_id as u16);
+
+        match self.publisher_restriction(vendor_id, purpose_id) {
+            Some(RestrictionType::NotAllowed) => false,
+            Some(RestrictionType::RequireConsent) => consent,
+            Some(RestrictionType::RequireLegitimateInterest) => legitimate_interest,
+            _ => consent || legitimate_interest,
+        }
+    }
+}
+
+impl FromStr for TcString {
+    type Err = TcfError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TcString::parse(s)
+    }
+}
+
+fn reader(segment: &str) -> Result<BitReader, TcfError> {
+    BitReader::from_base64(segment).map_err(TcfError::InvalidCharacter)
+}
+
+fn read(reader: &mut BitReader, n: usize) -> Result<u64, TcfError> {
+    reader.read(n).ok_or(TcfError::UnexpectedEnd)
+}
+
+fn read_bool(reader: &mut BitReader) -> Result<bool, TcfError> {
+    reader.read_bool().ok_or(TcfError::UnexpectedEnd)
+}
+
+fn read_bitfield(reader: &mut BitReader, n: usize) -> Result<BTreeSet<u16>, TcfError> {
+    reader.read_bitfield(n).ok_or(TcfError::UnexpectedEnd)
+}
+
+fn read_letters(reader: &mut BitReader, n: usize) -> Result<String, TcfError> {
+    reader.read_letters(n).ok_or(TcfError::UnexpectedEnd)
+}
+
+fn parse_core(reader: &mut BitReader) -> Result<CoreSegment, TcfError> {
+    let version = read(reader, 6)? as u8;
+    if version != 2 {
+        return Err(TcfError::UnsupportedVersion(version));
+    }
+
+    Ok(CoreSegment {
+        version,
+        created: read(reader, 36)?,
+        last_updated: read(reader, 36)?,
+        cmp_id: read(reader, 12)? as u16,
+        cmp_version: read(reader, 12)? as u16,
+        consent_screen: read(reader, 6)? as u8,
+        consent_language: read_letters(reader, 2)?,
+        vendor_list_version: read(reader, 12)? as u16,
+        tcf_policy_version: read(reader, 6)? as u8,
+        is_service_specific: read_bool(reader)?,
+        use_non_standard_texts: read_bool(reader)?,
+        special_feature_opt_ins: read_bitfield(reader, 12)?,
+        purposes_consent: read_bitfield(reader, 24)?,
+        purposes_legitimate_interest: read_bitfield(reader, 24)?,
+        purpose_one_treatment: read_bool(reader)?,
+        publisher_country_code: read_letters(reader, 2)?,
+        vendor_consents: read_vendor_set(reader)?,
+        vendor_legitimate_interests: read_vendor_set(reader)?,
+        publisher_restrictions: read_publisher_restrictions(reader)?,
+    })
+}
+
+// A vendor section is either a bit field or a list of ranges, whichever the
+// CMP found shorter. Ranges may not go past the section's maximum vendor ID,
+// and may come in any order and overlap, so they are expanded in order and
+// each vendor ID once, which bounds the work to the size of the set.
+fn read_vendor_set(reader: &mut BitReader) -> Result<BTreeSet<u16>, TcfError> {
+    let max_vendor_id = read(reader, 16)? as u16;
+    if !read_bool(reader)? {
+        return read_bitfield(reader, max_vendor_id as usize);
+    }
+
+    let mut ranges = read_ranges(reader)?;
+    if let Some(range) = ranges.iter().find(|range| *range.end() > max_vendor_id) {
+        return Err(TcfError::InvalidRange(*range.start(), *range.end()));
+    }
+
+    ranges.sort_by_key(|range| *range.start());
+    let mut ids = BTreeSet::new();
+    let mut next = 0u32;
+    for range in ranges {
+        let end = *range.end() as u32;
+        ids.extend((next.max(*range.start() as u32)..=end).map(|id| id as u16));
+        next = next.max(end + 1);
+    }
+
+    Ok(ids)
+}
+
+fn read_ranges(reader: &mut BitReader) -> Result<Vec<RangeInclusive<u16>>, TcfError> {
+    let mut ranges = vec![];
+    for _ in 0..read(reader, 12)? {
+        let is_range = read_bool(reader)?;
+        let start = read(reader, 16)? as u16;
+        let end = if is_range { read(reader, 16)? as u16 } else { start };
+
+        if start > end {
+            return Err(TcfError::InvalidRange(start, end));
+        }
+        ranges.push(start..=end);
+    }
+
+    Ok(ranges)
+}
+
+fn read_publisher_restrictions(reader: &mut BitReader) -> Result<Vec<PublisherRestriction>, TcfError> {
+    // Strings created before publisher restrictions existed may end here.
+    if reader.remaining() < 12 {
+        return Ok(vec![]);
+    }
+
+    let mut restrictions = vec![];
+    for _ in 0..read(reader, 12)? {
+        let purpose_id = read(reader, 6)? as u8;
+        let restriction_type = match read(reader, 2)? {
+            0 => RestrictionType::NotAllowed,
+            1 => RestrictionType::RequireConsent,
+            2 => RestrictionType::RequireLegitimateInterest,
+            _ => RestrictionType::Undefined,
+        };
+        restrictions.push(PublisherRestriction {
+            purpose_id,
+            restriction_type,
+            vendors: read_ranges(reader)?,
+        });
+    }
+
+    Ok(restrictions)
+}
+
+fn parse_publisher_tc(reader: &mut BitReader) -> Result<PublisherTc, TcfError> {
+    let purposes_consent = read_bitfield(reader, 24)?;
+    let purposes_legitimate_interest = read_bitfield(reader, 24)?;
+    let custom_purposes = read(reader, 6)? as usize;
+
+    Ok(PublisherTc {
+        purposes_consent,
+        purposes_legitimate_interest,
+        custom_purposes_consent: read_bitfield(reader, custom_purposes)?,
+        custom_purposes_legitimate_interest: read_bitfield(reader, custom_purposes)?,
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::super::bits::BitWriter;
+    use super::*;
+
+    // Core segment with vendor consents for 1-3 and 755 (range encoded),
+    // legitimate interest for vendor 2 (bit field encoded), and a publisher
+    // restriction requiring consent for purpose 2 of vendors 5-10.
+    pub(crate) fn core_segment() -> String {
+        core_fields(&mut BitWriter::new())
+            // vendor consents, range encoded
+            .write(755, 16)
+            .write_bool(true)
+            .write(2, 12)
+            .write_bool(true)
+            .write(1, 16)
+            .write(3, 16)
+            .write_bool(false)
+            .write(755, 16)
+            // vendor legitimate interests, bit field encoded
+            .write(3, 16)
+            .write_bool(false)
+            .write_bitfield(&[2], 3)
+            // publisher restrictions
+            .write(1, 12)
+            .write(2, 6)
+            .write(1, 2)
+            .write(1, 12)
+            .write_bool(true)
+            .write(5, 16)
+            .write(10, 16)
+            .to_base64()
+    }
+
+    // The core segment fields that precede the vendor sections.
+    fn core_fields(writer: &mut BitWriter) -> &mut BitWriter {
+        writer
+            .write(2, 6)
+            .write(16_000_000_000, 36)
+            .write(16_000_000_100, 36)
+            .write(7, 12)
+            .write(1, 12)
+            .write(1, 6)
+            .write_letters("EN")
+            .write(150, 12)
+            .write(4, 6)
+            .write_bool(false)
+            .write_bool(false)
+            .write_bitfield(&[1], 12)
+            .write_bitfield(&[1, 2, 3, 4, 7, 9, 10], 24)
+            .write_bitfield(&[2, 7, 8, 9, 10], 24)
+            .write_bool(false)
+            .write_letters("DE")
+    }
+
+    // A core segment whose vendor consents are the given ranges, with no
+    // legitimate interests and no publisher restrictions.
+    fn core_segment_with_ranges(max_vendor_id: u64, ranges: &[(u64, u64)]) -> String {
+        let mut writer = BitWriter::new();
+        core_fields(&mut writer).write(max_vendor_id, 16).write_bool(true).write(ranges.len() as u64, 12);
+        for &(start, end) in ranges {
+            writer.write_bool(true).write(start, 16).write(end, 16);
+        }
+        writer.write(0, 16).write_bool(false).write(0, 12).to_base64()
+    }
+
+    fn disclosed_vendors_segment() -> String {
+        BitWriter::new()
+            .write(1, 3)
+            .write(6, 16)
+            .write_bool(false)
+            .write_bitfield(&[1, 2, 3, 6], 6)
+            .to_base64()
+    }
+
+    fn publisher_tc_segment() -> String {
+        BitWriter::new()
+            .write(3, 3)
+            .write_bitfield(&[1], 24)
+            .write_bitfield(&[2], 24)
+            .write(2, 6)
+            .write_bitfield(&[1], 2)
+            .write_bitfield(&[2], 2)
+            .to_base64()
+    }
+
+    #[test]
+    fn parse_core_segment() {
+        let tc = TcString::parse(&core_segment()).unwrap();
+
+        assert_eq!(tc.core.version, 2);
+        assert_eq!(tc.core.created, 16_000_000_000);
+        assert_eq!(tc.core.last_updated, 16_000_000_100);
+        assert_eq!(tc.core.cmp_id, 7);
+        assert_eq!(tc.core.consent_language, "EN");
+        assert_eq!(tc.core.vendor_list_version, 150);
+        assert_eq!(tc.core.tcf_policy_version, 4);
+        assert_eq!(tc.core.publisher_country_code, "DE");
+        assert!(tc.special_feature_opt_in(1));
+        assert!(!tc.special_feature_opt_in(2));
+        assert!(tc.purpose_consent(1));
+        assert!(!tc.purpose_consent(5));
+        assert!(tc.purpose_legitimate_interest(7));
+        assert!(tc.vendor_consent(1));
+        assert!(tc.vendor_consent(3));
+        assert!(!tc.vendor_consent(4));
+        assert!(tc.vendor_consent(755));
+        assert!(tc.vendor_legitimate_interest(2));
+        assert!(!tc.vendor_legitimate_interest(1));
+        assert_eq!(tc.publisher_restriction(7, 2), Some(&RestrictionType::RequireConsent));
+        assert_eq!(tc.publisher_restriction(4, 2), None);
+        assert_eq!(tc.core.publisher_restrictions[0].vendors, vec![5..=10]);
+        assert_eq!(tc.disclosed_vendors, None);
+        assert_eq!(tc.publisher_tc, None);
+    }
+
+    #[test]
+    fn parse_optional_segments() {
+        let s = format!("{}.{}.{}", core_segment(), disclosed_vendors_segment(), publisher_tc_segment());
+        let tc: TcString = s.parse().unwrap();
+
+        assert_eq!(tc.disclosed_vendors, Some([1, 2, 3, 6].iter().cloned().collect()));
+        let publisher_tc = tc.publisher_tc.unwrap();
+        assert_eq!(publisher_tc.purposes_consent, [1].iter().cloned().collect());
+        assert_eq!(publisher_tc.purposes_legitimate_interest, [2].iter().cloned().collect());
+        assert_eq!(publisher_tc.custom_purposes_consent, [1].iter().cloned().collect());
+        assert_eq!(publisher_tc.custom_purposes_legitimate_interest, [2].iter().cloned().collect());
+    }
+
+    #[test]
+    fn vendor_allowed_for_purpose() {
+        let tc = TcString::parse(&core_segment()).unwrap();
+
+        // Consent for both the vendor and the purpose.
+        assert!(tc.vendor_allowed_for_purpose(1, 1));
+        // Legitimate interest for both the vendor and the purpose.
+        assert!(tc.vendor_allowed_for_purpose(2, 8));
+        // Neither consent nor legitimate interest for purpose 5.
+        assert!(!tc.vendor_allowed_for_purpose(1, 5));
+        // Vendor 755 has no legitimate interest and purpose 8 no consent.
+        assert!(!tc.vendor_allowed_for_purpose(755, 8));
+    }
+
+    #[test]
+    fn parse_real_world_strings() {
+        // Example string from the IAB Europe TCF v2 documentation.
+        let tc = TcString::parse("COvFyGBOvFyGBAbAAAENAPCAAOAAAAAAAAAAAEEUACCKAAA").unwrap();
+
+        assert_eq!(tc.core.version, 2);
+        assert_eq!(tc.core.created, 15_822_430_593);
+        assert_eq!(tc.core.cmp_id, 27);
+        assert_eq!(tc.core.consent_language, "EN");
+        assert_eq!(tc.core.vendor_list_version, 15);
+        assert_eq!(tc.core.tcf_policy_version, 2);
+        assert_eq!(tc.core.purposes_consent, [1, 2, 3].iter().cloned().collect());
+        assert_eq!(tc.core.vendor_consents, [2, 6, 8].iter().cloned().collect());
+        assert_eq!(tc.core.vendor_legitimate_interests, [2, 6, 8].iter().cloned().collect());
+        assert!(tc.core.publisher_restrictions.is_empty());
+
+        // Service-specific string as sent by exchanges in user.ext.consent.
+        let tc = TcString::parse("CPXxRfAPXxRfAAfKABENB-CgAAAAAAAAAAYgAAAAAAAA").unwrap();
+
+        assert_eq!(tc.core.cmp_id, 31);
+        assert_eq!(tc.core.consent_language, "EN");
+        assert_eq!(tc.core.publisher_country_code, "DE");
+        assert!(tc.core.is_service_specific);
+        assert!(tc.core.vendor_consents.is_empty());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(TcString::parse(""), Err(TcfError::Empty));
+        assert_eq!(TcString::parse("CO*"), Err(TcfError::InvalidCharacter('*')));
+        assert_eq!(TcString::parse("BOEFEAyOEFEAyAHABDENAI4AAAB9vABAASA"), Err(TcfError::UnsupportedVersion(1)));
+        assert_eq!(TcString::parse("CPXxRfAPXxRf"), Err(TcfError::UnexpectedEnd));
+        assert_eq!(
+            TcString::parse(&format!("{}.AAAA", core_segment())),
+            Err(TcfError::InvalidSegmentType(0))
+        );
+    }
+
+    #[test]
+    fn parse_ranges() {
+        let tc = TcString::parse(&core_segment_with_ranges(20, &[(1, 3), (5, 20)])).unwrap();
+        assert_eq!(tc.core.vendor_consents.len(), 19);
+
+        assert_eq!(
+            TcString::parse(&core_segment_with_ranges(20, &[(9, 4)])),
+            Err(TcfError::InvalidRange(9, 4))
+        );
+        // Ranges may come in any order and overlap.
+        let tc = TcString::parse(&core_segment_with_ranges(20, &[(5, 8), (1, 3), (2, 6), (7, 7)])).unwrap();
+        assert_eq!(tc.core.vendor_consents, (1..=8).collect());
+        // A range past the maximum vendor ID would let a short string
+        // expand into a huge set.
+        assert_eq!(
+            TcString::parse(&core_segment_with_ranges(20, &[(1, 65535)])),
+            Err(TcfError::InvalidRange(1, 65535))
+        );
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// IAB CCPA Compliance Framework US Privacy string (e.g. “1YNN”), as carried in
+// `Regs.ext.us_privacy` or `Regs.us_privacy`.
+
+use std::error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+const VERSION: u8 = 1;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UsPrivacyError {
+    // The string is not four characters long.
+    InvalidLength(String),
+    // The specification version is not 1.
+    UnsupportedVersion(char),
+    // A flag is not one of ‘Y’, ‘N’ or ‘-’.
+    InvalidFlag(char),
+}
+
+impl Display for UsPrivacyError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UsPrivacyError::InvalidLength(s) => write!(formatter, "US Privacy string must have 4 characters: {:?}", s),
+            UsPrivacyError::UnsupportedVersion(v) => write!(formatter, "US Privacy version {:?} is not supported", v),
+            UsPrivacyError::InvalidFlag(f) => write!(formatter, "US Privacy flag must be Y, N or -: {:?}", f),
+        }
+    }
+}
+
+impl error::Error for UsPrivacyError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UsPrivacyFlag {
+    Yes,
+    No,
+    NotApplicable,
+}
+
+impl UsPrivacyFlag {
+    fn parse(c: char) -> Result<UsPrivacyFlag, UsPrivacyError> {
+        match c.to_ascii_uppercase() {
+            'Y' => Ok(UsPrivacyFlag::Yes),
+            'N' => Ok(UsPrivacyFlag::No),
+            '-' => Ok(UsPrivacyFlag::NotApplicable),
+            _ => Err(UsPrivacyError::InvalidFlag(c)),
+        }
+    }
+
+    fn as_char(&self) -> char {
+        match self {
+            UsPrivacyFlag::Yes => 'Y',
+            UsPrivacyFlag::No => 'N',
+            UsPrivacyFlag::NotApplicable => '-',
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UsPrivacy {
+    pub version: u8,
+    // Whether the user was given notice and the opportunity to opt out.
+    pub notice_given: UsPrivacyFlag,
+    // Whether the user opted out of the sale of their personal information.
+    pub opt_out_sale: UsPrivacyFlag,
+    // Whether the publisher is a signatory to the IAB Limited Service
+    // Provider Agreement.
+    pub lspa_covered: UsPrivacyFlag,
+}
+
+impl UsPrivacy {
+    /// Whether the user opted out of the sale of their personal information.
+    pub fn is_opted_out_of_sale(&self) -> bool {
+        self.opt_out_sale == UsPrivacyFlag::Yes
+    }
+}
+
+impl FromStr for UsPrivacy {
+    type Err = UsPrivacyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+        if chars.len() != 4 {
+            return Err(UsPrivacyError::InvalidLength(s.to_string()));
+        }
+
+        if chars[0].to_digit(10) != Some(VERSION as u32) {
+            return Err(UsPrivacyError::UnsupportedVersion(chars[0]));
+        }
+
+        Ok(UsPrivacy {
+            version: VERSION,
+            notice_given: UsPrivacyFlag::parse(chars[1])?,
+            opt_out_sale: UsPrivacyFlag::parse(chars[2])?,
+            lspa_covered: UsPrivacyFlag::parse(chars[3])?,
+        })
+    }
+}
+
+impl Display for UsPrivacy {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{}{}{}{}",
+            self.version,
+            self.notice_given.as_char(),
+            self.opt_out_sale.as_char(),
+            self.lspa_covered.as_char()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let p: UsPrivacy = "1YNN".parse().unwrap();
+
+        assert_eq!(p.version, 1);
+        assert_eq!(p.notice_given, UsPrivacyFlag::Yes);
+        assert_eq!(p.opt_out_sale, UsPrivacyFlag::No);
+        assert_eq!(p.lspa_covered, UsPrivacyFlag::No);
+        assert!(!p.is_opted_out_of_sale());
+        assert_eq!(p.to_string(), "1YNN");
+    }
+
+    #[test]
+    fn parse_opt_out() {
+        let p: UsPrivacy = "1yyn".parse().unwrap();
+        assert!(p.is_opted_out_of_sale());
+        assert_eq!(p.to_string(), "1YYN");
+
+        let p: UsPrivacy = "1---".parse().unwrap();
+        assert_eq!(p.opt_out_sale, UsPrivacyFlag::NotApplicable);
+        assert!(!p.is_opted_out_of_sale());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!("1YN".parse::<UsPrivacy>(), Err(UsPrivacyError::InvalidLength("1YN".to_string())));
+        assert_eq!("2YNN".parse::<UsPrivacy>(), Err(UsPrivacyError::UnsupportedVersion('2')));
+        assert_eq!("1YXN".parse::<UsPrivacy>(), Err(UsPrivacyError::InvalidFlag('X')));
+    }
+}
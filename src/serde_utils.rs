@@ -6,11 +6,47 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use serde::de::Visitor;
-use serde::Deserialize;
+use serde::de::{DeserializeOwned, Visitor};
+use serde::{Deserialize, Serialize};
 
 pub type Ext = serde_json::map::Map<String, serde_json::value::Value>;
 
+// Keys under which OpenRTB 2.5 requests carry in ext the signals later
+// versions give fields of their own, which are also the names of the fields.
+pub(crate) const EXT_SCHAIN: &str = "schain";
+pub(crate) const EXT_EIDS: &str = "eids";
+pub(crate) const EXT_CONSENT: &str = "consent";
+pub(crate) const EXT_GDPR: &str = "gdpr";
+pub(crate) const EXT_US_PRIVACY: &str = "us_privacy";
+pub(crate) const EXT_GPP: &str = "gpp";
+pub(crate) const EXT_GPP_SID: &str = "gpp_sid";
+
+// Removes a value from ext if it parses, leaving ext as None if it empties.
+pub(crate) fn take_ext<T: DeserializeOwned>(ext: &mut Option<Ext>, key: &str) -> Option<T> {
+    let value = serde_json::from_value(ext.as_ref()?.get(key)?.clone()).ok()?;
+    remove_ext(ext, key);
+    Some(value)
+}
+
+pub(crate) fn put_ext<T: Serialize>(ext: &mut Option<Ext>, key: &str, value: T) {
+    let value = serde_json::to_value(value).expect("ext values are always representable as JSON");
+    ext.get_or_insert_with(Ext::new).insert(key.to_string(), value);
+}
+
+// Removes a value from ext, leaving ext as None if it empties. Returns
+// whether there was a value.
+pub(crate) fn remove_ext(ext: &mut Option<Ext>, key: &str) -> bool {
+    let Some(ref mut map) = ext else {
+        return false;
+    };
+
+    let removed = map.remove(key).is_some();
+    if map.is_empty() {
+        *ext = None;
+    }
+    removed
+}
+
 struct BoolVisitor;
 
 impl<'de> Visitor<'de> for BoolVisitor {
@@ -225,51 +261,6 @@ where
 
 use crate::v2_5::Category;
 
-struct StringOrVecVisitor;
-
-impl<'de> Visitor<'de> for StringOrVecVisitor {
-    type Value = Vec<Category>;
-
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("a string or array of strings")
-    }
-
-    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
-    where
-        E: serde::de::Error,
-    {
-        // Parse category using the same logic as Category's deserializer
-        let category = match crate::v2_5::category::TO_CATEGORY.get(value).cloned() {
-            Some(c) => c,
-            None => Category::Unknown(value.to_string()),
-        };
-        Ok(vec![category])
-    }
-
-    fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
-    where
-        E: serde::de::Error,
-    {
-        self.visit_str(&value)
-    }
-
-    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-    where
-        A: serde::de::SeqAccess<'de>,
-    {
-        let mut vec = Vec::new();
-        while let Some(value) = seq.next_element::<String>()? {
-            // Parse category using the same logic as Category's deserializer
-            let category = match crate::v2_5::category::TO_CATEGORY.get(value.as_str()).cloned() {
-                Some(c) => c,
-                None => Category::Unknown(value),
-            };
-            vec.push(category);
-        }
-        Ok(vec)
-    }
-}
-
 /// Deserialize a field that can be either a single string or an array of strings into Vec<Category>
 pub fn string_or_vec_category<'de, D>(deserializer: D) -> Result<Option<Vec<Category>>, D::Error>
 where
@@ -335,3 +326,19 @@ where
         }
     }).transpose()
 }
+
+/// Deserialize a field that can be either an integer or a numeric string into u32
+pub fn int_or_string_to_u32<'de, D>(deserializer: D) -> Result<Option<u32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Option::deserialize(deserializer)?.map(|v| {
+        match v {
+            serde_json::Value::Number(n) => n.as_u64().and_then(|i| u32::try_from(i).ok())
+                .ok_or_else(|| serde::de::Error::custom("expected unsigned integer")),
+            serde_json::Value::String(s) => s.parse()
+                .map_err(|_| serde::de::Error::custom("expected numeric string")),
+            _ => Err(serde::de::Error::custom("expected integer or string"))
+        }
+    }).transpose()
+}
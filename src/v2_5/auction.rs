@@ -0,0 +1,556 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// An auction over the responses of several bidders to the same request.
+//
+// Bids the request does not allow are rejected first, with the reasons of
+// `BidRequest::bid_rejections`. The highest remaining bid for each Imp wins,
+// the earliest one on ties. Seat bids with group = 1 are won or lost as a
+// whole: while one of their bids does not win, all of them are dropped and
+// the Imps they were bidding on are auctioned again. The bids of a group that
+// lost for another of its bids get the group loss reason.
+
+use std::collections::HashMap;
+
+use crate::price_encryption::MICROS_PER_UNIT;
+
+use super::auction_macros::AuctionMacros;
+use super::bid::Bid;
+use super::bid_request::{AuctionType, BidRequest};
+use super::bid_response::BidResponse;
+use super::imp::Imp;
+use super::loss_reason::LossReason;
+use super::pmp::Deal;
+use super::rejection::{RejectionReason, DEFAULT_CURRENCY};
+use super::seat_bid::SeatBid;
+
+// Amount a second price auction adds to the price to beat.
+pub const DEFAULT_INCREMENT: f64 = 0.01;
+
+// Loss reason of a bid that was not itself outbid or rejected, but lost with
+// the rest of its group. OpenRTB has no code for this, so by default it is
+// the first exchange-specific one.
+pub const DEFAULT_GROUP_LOSS_REASON: u32 = 1000;
+
+// Deal auction type where the bid floor is the agreed upon deal price.
+const AGREED_DEAL_PRICE: u32 = 3;
+
+/// The outcome of the auction for one bid of one of the responses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuctionBid {
+    // Indexes of the response, of the seat bid in the response and of the
+    // bid in the seat bid.
+    pub response: usize,
+    pub seat_bid: usize,
+    pub bid: usize,
+    pub imp_id: String,
+    // BidWon for the winning bid of its Imp.
+    pub loss_reason: LossReason,
+    // Price the Imp of the bid cleared at, if a bid won it.
+    pub clearing_price: Option<f64>,
+}
+
+impl AuctionBid {
+    pub fn is_winner(&self) -> bool {
+        self.loss_reason == LossReason::BidWon
+    }
+
+    /// JSON path of the bid in the responses, e.g. `$[1].seatbid[0].bid[2]`.
+    pub fn path(&self) -> String {
+        format!("$[{}].seatbid[{}].bid[{}]", self.response, self.seat_bid, self.bid)
+    }
+
+    /// The substitution macros for the notice URLs and markup of the bid,
+    /// given the request and responses of the auction.
+    pub fn macros<'a>(&self, request: &'a BidRequest, responses: &'a [BidResponse]) -> AuctionMacros<'a> {
+        let response = &responses[self.response];
+        let seat_bid = &response.seat_bid[self.seat_bid];
+
+        let clearing_price = self.clearing_price.unwrap_or_default();
+        AuctionMacros::new(request, response, seat_bid.seat.as_deref(), &seat_bid.bid[self.bid], clearing_price)
+            .with_loss_reason(self.loss_reason.clone())
+    }
+}
+
+/// The winning bids of an auction as a response to its request, and the
+/// outcome of every bid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuctionResult {
+    // Response with the winning bids, in their seat bids. Bids keep the
+    // price they were made at.
+    pub response: BidResponse,
+    // All bids, in the order of the responses.
+    pub bids: Vec<AuctionBid>,
+}
+
+impl AuctionResult {
+    pub fn winners(&self) -> impl Iterator<Item = &AuctionBid> {
+        self.bids.iter().filter(|bid| bid.is_winner())
+    }
+}
+
+/// An auction for the Imps of a request.
+///
+/// In a second price auction the winner pays the increment over the price
+/// to beat, which is the highest other bid or the floor, whichever is
+/// higher, but never more than its own bid. With neither another bid nor a
+/// floor, the price to beat is zero, so a lone bid clears at the increment:
+/// `DEFAULT_INCREMENT` unless set with `with_increment`.
+pub struct Auction<'a> {
+    request: &'a BidRequest,
+    increment: f64,
+    group_loss_reason: LossReason,
+}
+
+// A bid taking part in the auction, until it loses.
+struct Entry<'a> {
+    response: usize,
+    seat_bid: usize,
+    bid_index: usize,
+    bid: &'a Bid,
+    group: bool,
+    loss_reason: Option<LossReason>,
+}
+
+impl<'a> Auction<'a> {
+    pub fn new(request: &'a BidRequest) -> Auction<'a> {
+        Auction {
+            request,
+            increment: DEFAULT_INCREMENT,
+            group_loss_reason: LossReason::from(DEFAULT_GROUP_LOSS_REASON),
+        }
+    }
+
+    /// Sets the amount a second price auction adds to the price to beat.
+    pub fn with_increment(mut self, increment: f64) -> Auction<'a> {
+        self.increment = increment;
+        self
+    }
+
+    /// Sets the loss reason of bids that lose only because another bid of
+    /// their group did.
+    pub fn with_group_loss_reason(mut self, reason: LossReason) -> Auction<'a> {
+        self.group_loss_reason = reason;
+        self
+    }
+
+    /// The currency of the auction: the first currency the request allows,
+    /// or USD. Responses in other currencies are rejected, as prices are
+    /// not converted.
+    pub fn currency(&self) -> &'a str {
+        self.request.currency.first().map_or(DEFAULT_CURRENCY, String::as_str)
+    }
+
+    /// Runs the auction over the responses to the request.
+    pub fn run(&self, responses: &[BidResponse]) -> AuctionResult {
+        let currency = self.currency();
+        let mut entries = vec![];
+
+        for (r, response) in responses.iter().enumerate() {
+            let response_loss_reason = if response.id != self.request.id {
+                Some(RejectionReason::ResponseIdMismatch(response.id.clone()).loss_reason())
+            } else if response.currency.as_deref().unwrap_or(DEFAULT_CURRENCY) != currency {
+                Some(RejectionReason::CurrencyNotAllowed(response.currency.clone().unwrap_or_default()).loss_reason())
+            } else {
+                None
+            };
+
+            for (s, seat_bid) in response.seat_bid.iter().enumerate() {
+                let first = entries.len();
+                for (b, bid) in seat_bid.bid.iter().enumerate() {
+                    let loss_reason = response_loss_reason.clone().or_else(|| self.rejection(seat_bid, currency, bid));
+                    entries.push(Entry {
+                        response: r,
+                        seat_bid: s,
+                        bid_index: b,
+                        bid,
+                        group: is_grouped(seat_bid),
+                        loss_reason,
+                    });
+                }
+
+                // A group with a rejected bid loses all of its bids.
+                if is_grouped(seat_bid) && entries[first..].iter().any(|entry| entry.loss_reason.is_some()) {
+                    for entry in &mut entries[first..] {
+                        entry.loss_reason.get_or_insert_with(|| self.group_loss_reason.clone());
+                    }
+                }
+            }
+        }
+
+        let winners = loop {
+            let winners = winners(&entries);
+
+            let lost_group = entries
+                .iter()
+                .enumerate()
+                .find(|&(i, entry)| entry.group && entry.loss_reason.is_none() && winners[entry.bid.imp_id.as_str()] != i);
+            let (response, seat_bid) = match lost_group {
+                Some((_, entry)) => (entry.response, entry.seat_bid),
+                None => break winners,
+            };
+
+            // Bids of the group that were outbid lost to the winner of their
+            // Imp, the others only with their group.
+            let reasons = entries
+                .iter()
+                .enumerate()
+                .filter(|&(_, entry)| entry.response == response && entry.seat_bid == seat_bid)
+                .filter(|&(_, entry)| entry.loss_reason.is_none())
+                .map(|(i, entry)| match winners[entry.bid.imp_id.as_str()] {
+                    winner if winner == i => (i, self.group_loss_reason.clone()),
+                    winner => (i, lost_to(entry.bid, entries[winner].bid)),
+                })
+                .collect::<Vec<_>>();
+            for (i, reason) in reasons {
+                entries[i].loss_reason = Some(reason);
+            }
+        };
+
+        let mut clearing_prices = HashMap::new();
+        for (&imp_id, &i) in &winners {
+            let second = entries
+                .iter()
+                .enumerate()
+                .filter(|&(j, entry)| j != i && entry.loss_reason.is_none() && entry.bid.imp_id == imp_id)
+                .map(|(_, entry)| entry.bid.price)
+                .fold(None, |second: Option<f64>, price| Some(second.map_or(price, |second| second.max(price))));
+            clearing_prices.insert(imp_id, self.clearing_price(entries[i].bid, second, currency));
+        }
+
+        let bids = entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let imp_id = entry.bid.imp_id.as_str();
+                let loss_reason = match (&entry.loss_reason, winners.get(imp_id)) {
+                    (Some(reason), _) => reason.clone(),
+                    (None, Some(&winner)) if winner == i => LossReason::BidWon,
+                    (None, Some(&winner)) => lost_to(entry.bid, entries[winner].bid),
+                    // Bids left always have a winner for their Imp.
+                    (None, None) => LossReason::InternalError,
+                };
+
+                AuctionBid {
+                    response: entry.response,
+                    seat_bid: entry.seat_bid,
+                    bid: entry.bid_index,
+                    imp_id: imp_id.to_string(),
+                    loss_reason,
+                    clearing_price: clearing_prices.get(imp_id).cloned(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        AuctionResult {
+            response: self.merged_response(responses, &bids),
+            bids,
+        }
+    }
+
+    // The loss reason of the first reason to reject the bid.
+    fn rejection(&self, seat_bid: &SeatBid, currency: &str, bid: &Bid) -> Option<LossReason> {
+        self.request
+            .bid_rejections(seat_bid.seat.as_deref(), currency, bid)
+            .into_iter()
+            .next()
+            .map(|reason| reason.loss_reason())
+    }
+
+    fn imp(&self, bid: &Bid) -> Option<&'a Imp> {
+        self.request.imp.iter().find(|imp| imp.id == bid.imp_id)
+    }
+
+    fn deal(&self, bid: &Bid) -> Option<&'a Deal> {
+        let deal_id = bid.deal_id.as_ref()?;
+        self.imp(bid)?.pmp.as_ref()?.deal(deal_id)
+    }
+
+    // The floor of the deal of the bid if it has one, or else of its Imp,
+    // when in the currency of the auction.
+    fn floor(&self, bid: &Bid, currency: &str) -> Option<f64> {
+        let (floor, floor_currency) = match self.deal(bid) {
+            Some(deal) if deal.bid_floor.is_some() => (deal.bid_floor, deal.bid_floor_cur.as_deref()),
+            _ => {
+                let imp = self.imp(bid)?;
+                (imp.bid_floor, imp.bid_floor_cur.as_deref())
+            }
+        };
+
+        floor
+            .filter(|_| floor_currency.unwrap_or(DEFAULT_CURRENCY) == currency)
+            .map(|floor| floor as f64)
+    }
+
+    // The auction type of the deal of the winning bid overrides the one of
+    // the request. Exchange-specific auction types clear at the bid price.
+    fn clearing_price(&self, winner: &Bid, second: Option<f64>, currency: &str) -> f64 {
+        let deal = self.deal(winner);
+        let auction_type = deal
+            .and_then(|deal| deal.auction_type.clone())
+            .or_else(|| self.request.auction_type.clone())
+            .unwrap_or_default();
+        let floor = self.floor(winner, currency);
+
+        let price = match auction_type {
+            AuctionType::SecondPricePlus => {
+                let to_beat = second.unwrap_or_default().max(floor.unwrap_or_default());
+                (to_beat + self.increment).min(winner.price)
+            }
+            AuctionType::ExchangeSpecific(AGREED_DEAL_PRICE) if deal.is_some() => floor.unwrap_or(winner.price),
+            _ => winner.price,
+        };
+
+        (price * MICROS_PER_UNIT).round() / MICROS_PER_UNIT
+    }
+
+    fn merged_response(&self, responses: &[BidResponse], bids: &[AuctionBid]) -> BidResponse {
+        let mut response = BidResponse::new(self.request.id.clone());
+        response.currency = Some(self.currency().to_string());
+
+        // Bids are in order, so the winners of a seat bid follow each other.
+        let mut last = None;
+        for winner in bids.iter().filter(|bid| bid.is_winner()) {
+            let seat_bid = &responses[winner.response].seat_bid[winner.seat_bid];
+            let bid = seat_bid.bid[winner.bid].clone();
+
+            match response.seat_bid.last_mut() {
+                Some(merged) if last == Some((winner.response, winner.seat_bid)) => merged.bid.push(bid),
+                _ => {
+                    response.seat_bid.push(SeatBid {
+                        bid: vec![bid],
+                        seat: seat_bid.seat.clone(),
+                        group: seat_bid.group,
+                        ext: seat_bid.ext.clone(),
+                    });
+                    last = Some((winner.response, winner.seat_bid));
+                }
+            }
+        }
+
+        response
+    }
+}
+
+// Whether the seat bid must be won or lost as a group.
+fn is_grouped(seat_bid: &SeatBid) -> bool {
+    seat_bid.group == Some(1)
+}
+
+// The loss reason of a bid the winner outbid.
+fn lost_to(bid: &Bid, winner: &Bid) -> LossReason {
+    if winner.deal_id.is_some() && bid.deal_id.is_none() {
+        LossReason::LostToPmpDeal
+    } else {
+        LossReason::LostToHigherBid
+    }
+}
+
+// The index of the winning entry of each Imp with bids left.
+fn winners<'a>(entries: &[Entry<'a>]) -> HashMap<&'a str, usize> {
+    let mut winners: HashMap<&str, usize> = HashMap::new();
+
+    for (i, entry) in entries.iter().enumerate() {
+        if entry.loss_reason.is_some() {
+            continue;
+        }
+
+        let winner = winners.entry(entry.bid.imp_id.as_str()).or_insert(i);
+        if entry.bid.price > entries[*winner].bid.price {
+            *winner = i;
+        }
+    }
+
+    winners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    fn request(at: u32) -> BidRequest {
+        serde_json::from_str(&format!(
+            r#"{{
+                "id": "req",
+                "at": {},
+                "imp": [
+                    {{"id": "1", "banner": {{}}, "bidfloor": 1}},
+                    {{"id": "2", "banner": {{}}}},
+                    {{
+                        "id": "3",
+                        "banner": {{}},
+                        "bidfloor": 0.5,
+                        "pmp": {{"deals": [{{"id": "deal-1", "bidfloor": 4}}, {{"id": "deal-2", "bidfloor": 2.5, "at": 3}}]}}
+                    }}
+                ]
+            }}"#,
+            at
+        ))
+        .unwrap()
+    }
+
+    fn responses() -> Vec<BidResponse> {
+        serde_json::from_str(
+            r#"[
+                {"id": "req", "seatbid": [
+                    {"seat": "a", "bid": [
+                        {"id": "a1", "impid": "1", "price": 3},
+                        {"id": "a2", "impid": "2", "price": 1.2},
+                        {"id": "a3", "impid": "3", "price": 3.5, "dealid": "deal-1"}
+                    ]}
+                ]},
+                {"id": "req", "seatbid": [
+                    {"seat": "b", "bid": [
+                        {"id": "b1", "impid": "1", "price": 2.5, "lurl": "https://loss.example/?r=${AUCTION_LOSS}&p=${AUCTION_PRICE}"},
+                        {"id": "b2", "impid": "3", "price": 3}
+                    ]}
+                ]},
+                {"id": "req", "cur": "EUR", "seatbid": [{"bid": [{"id": "c1", "impid": "1", "price": 10}]}]},
+                {"id": "other", "seatbid": [{"bid": [{"id": "d1", "impid": "2", "price": 10}]}]}
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    fn outcomes(result: &AuctionResult) -> Vec<(String, LossReason, Option<f64>)> {
+        result
+            .bids
+            .iter()
+            .map(|bid| (bid.path(), bid.loss_reason.clone(), bid.clearing_price))
+            .collect()
+    }
+
+    #[test]
+    fn second_price() {
+        let request = request(2);
+        let responses = responses();
+        let result = Auction::new(&request).run(&responses);
+
+        assert_eq!(
+            outcomes(&result),
+            vec![
+                ("$[0].seatbid[0].bid[0]".to_string(), LossReason::BidWon, Some(2.51)),
+                ("$[0].seatbid[0].bid[1]".to_string(), LossReason::BidWon, Some(0.01)),
+                ("$[0].seatbid[0].bid[2]".to_string(), LossReason::BelowDealFloor, Some(0.51)),
+                ("$[1].seatbid[0].bid[0]".to_string(), LossReason::LostToHigherBid, Some(2.51)),
+                ("$[1].seatbid[0].bid[1]".to_string(), LossReason::BidWon, Some(0.51)),
+                ("$[2].seatbid[0].bid[0]".to_string(), LossReason::InvalidBidResponse, Some(2.51)),
+                ("$[3].seatbid[0].bid[0]".to_string(), LossReason::InvalidAuctionId, Some(0.01)),
+            ]
+        );
+
+        let bid_ids = |seat_bid: &SeatBid| seat_bid.bid.iter().map(|bid| bid.id.clone()).collect::<Vec<_>>();
+        assert_eq!(result.response.id, "req");
+        assert_eq!(result.response.currency, Some("USD".to_string()));
+        assert_eq!(result.response.seat_bid.len(), 2);
+        assert_eq!(result.response.seat_bid[0].seat, Some("a".to_string()));
+        assert_eq!(bid_ids(&result.response.seat_bid[0]), vec!["a1", "a2"]);
+        assert_eq!(bid_ids(&result.response.seat_bid[1]), vec!["b2"]);
+        assert_eq!(result.winners().count(), 3);
+
+        let loser = &result.bids[3];
+        assert_eq!(
+            loser.macros(&request, &responses).expand_bid().lurl,
+            Some("https://loss.example/?r=102&p=2.51".to_string())
+        );
+    }
+
+    #[test]
+    fn first_price_and_increment() {
+        let request = request(1);
+        let responses = responses();
+        let result = Auction::new(&request).run(&responses);
+        let prices = result.winners().map(|bid| bid.clearing_price).collect::<Vec<_>>();
+        assert_eq!(prices, vec![Some(3.0), Some(1.2), Some(3.0)]);
+
+        let request = self::request(2);
+        let result = Auction::new(&request).with_increment(0.5).run(&responses);
+        let prices = result.winners().map(|bid| bid.clearing_price).collect::<Vec<_>>();
+        assert_eq!(prices, vec![Some(3.0), Some(0.5), Some(1.0)]);
+    }
+
+    #[test]
+    fn second_price_lone_bid_without_floor() {
+        let request: BidRequest = serde_json::from_str(r#"{"id": "req", "at": 2, "imp": [{"id": "1"}]}"#).unwrap();
+        let responses: Vec<BidResponse> =
+            serde_json::from_str(r#"[{"id": "req", "seatbid": [{"bid": [{"id": "a1", "impid": "1", "price": 3}]}]}]"#)
+                .unwrap();
+
+        let result = Auction::new(&request).run(&responses);
+        assert_eq!(result.bids[0].clearing_price, Some(DEFAULT_INCREMENT));
+
+        let result = Auction::new(&request).with_increment(0.25).run(&responses);
+        assert_eq!(result.bids[0].clearing_price, Some(0.25));
+    }
+
+    #[test]
+    fn deal_auction_type() {
+        let request = request(2);
+        let responses: Vec<BidResponse> = serde_json::from_str(
+            r#"[
+                {"id": "req", "seatbid": [{"bid": [{"id": "a", "impid": "3", "price": 3, "dealid": "deal-2"}]}]},
+                {"id": "req", "seatbid": [{"bid": [{"id": "b", "impid": "3", "price": 2.9}]}]}
+            ]"#,
+        )
+        .unwrap();
+        let result = Auction::new(&request).run(&responses);
+
+        assert_eq!(
+            outcomes(&result),
+            vec![
+                ("$[0].seatbid[0].bid[0]".to_string(), LossReason::BidWon, Some(2.5)),
+                ("$[1].seatbid[0].bid[0]".to_string(), LossReason::LostToPmpDeal, Some(2.5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn groups_win_or_lose_together() {
+        let request = request(2);
+        let responses: Vec<BidResponse> = serde_json::from_str(
+            r#"[
+                {"id": "req", "seatbid": [{"seat": "a", "group": 1, "bid": [
+                    {"id": "a1", "impid": "1", "price": 5},
+                    {"id": "a2", "impid": "2", "price": 1}
+                ]}]},
+                {"id": "req", "seatbid": [{"seat": "b", "bid": [
+                    {"id": "b1", "impid": "1", "price": 2},
+                    {"id": "b2", "impid": "2", "price": 2}
+                ]}]},
+                {"id": "req", "seatbid": [{"seat": "c", "group": 1, "bid": [
+                    {"id": "c1", "impid": "1", "price": 9},
+                    {"id": "c2", "impid": "9", "price": 9}
+                ]}]}
+            ]"#,
+        )
+        .unwrap();
+        let result = Auction::new(&request).run(&responses);
+
+        assert_eq!(
+            outcomes(&result),
+            vec![
+                // a1 was winning Imp 1 until a2 lost Imp 2.
+                ("$[0].seatbid[0].bid[0]".to_string(), LossReason::Unknown(DEFAULT_GROUP_LOSS_REASON), Some(1.01)),
+                ("$[0].seatbid[0].bid[1]".to_string(), LossReason::LostToHigherBid, Some(0.01)),
+                ("$[1].seatbid[0].bid[0]".to_string(), LossReason::BidWon, Some(1.01)),
+                ("$[1].seatbid[0].bid[1]".to_string(), LossReason::BidWon, Some(0.01)),
+                // c1 is valid, but c2 is for an Imp the request does not have.
+                ("$[2].seatbid[0].bid[0]".to_string(), LossReason::Unknown(DEFAULT_GROUP_LOSS_REASON), Some(1.01)),
+                ("$[2].seatbid[0].bid[1]".to_string(), LossReason::InvalidBidResponse, None),
+            ]
+        );
+        assert_eq!(result.response.seat_bid.len(), 1);
+        assert_eq!(result.response.seat_bid[0].bid.len(), 2);
+
+        let result = Auction::new(&request)
+            .with_group_loss_reason(LossReason::Unknown(1042))
+            .run(&responses);
+        assert_eq!(result.bids[0].loss_reason, LossReason::Unknown(1042));
+    }
+}
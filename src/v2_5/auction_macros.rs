@@ -0,0 +1,273 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// 4.4 Substitution Macros
+//
+// The exchange replaces these macros in the win notice, billing notice and
+// loss notice URLs and in the markup of a bid with data about the auction.
+// Appending “:B64” to a macro name (e.g., ${AUCTION_PRICE:B64}) substitutes
+// the Base64 encoding of the value instead.
+
+use crate::base64;
+
+use super::bid::Bid;
+use super::bid_request::BidRequest;
+use super::bid_response::BidResponse;
+use super::loss_reason::LossReason;
+use super::rejection::DEFAULT_CURRENCY;
+
+const MACRO_START: &str = "${";
+const MACRO_END: char = '}';
+const SUFFIX_SEPARATOR: char = ':';
+const SUFFIX_BASE64: &str = "B64";
+
+pub const AUCTION_ID: &str = "AUCTION_ID";
+pub const AUCTION_BID_ID: &str = "AUCTION_BID_ID";
+pub const AUCTION_IMP_ID: &str = "AUCTION_IMP_ID";
+pub const AUCTION_SEAT_ID: &str = "AUCTION_SEAT_ID";
+pub const AUCTION_AD_ID: &str = "AUCTION_AD_ID";
+pub const AUCTION_PRICE: &str = "AUCTION_PRICE";
+pub const AUCTION_CURRENCY: &str = "AUCTION_CURRENCY";
+pub const AUCTION_MBR: &str = "AUCTION_MBR";
+pub const AUCTION_LOSS: &str = "AUCTION_LOSS";
+
+/// A hook to encrypt the clearing price substituted for ${AUCTION_PRICE},
+/// so that it does not travel in the clear.
+pub trait PriceEncryption {
+    fn encrypt_price(&self, price: f64) -> String;
+}
+
+/// The notice URLs and markup of a bid, with the macros expanded.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ExpandedBid {
+    pub nurl: Option<String>,
+    pub burl: Option<String>,
+    pub lurl: Option<String>,
+    pub adm: Option<String>,
+}
+
+/// The values of the substitution macros for a bid of an auction.
+pub struct AuctionMacros<'a> {
+    request: &'a BidRequest,
+    response: &'a BidResponse,
+    bid: &'a Bid,
+    seat: Option<&'a str>,
+    clearing_price: f64,
+    loss_reason: LossReason,
+    price_encryption: Option<&'a dyn PriceEncryption>,
+}
+
+impl<'a> AuctionMacros<'a> {
+    /// The macros of a bid of the response, made by the given seat, which
+    /// cleared at the given price.
+    pub fn new(
+        request: &'a BidRequest,
+        response: &'a BidResponse,
+        seat: Option<&'a str>,
+        bid: &'a Bid,
+        clearing_price: f64,
+    ) -> AuctionMacros<'a> {
+        AuctionMacros {
+            request,
+            response,
+            bid,
+            seat,
+            clearing_price,
+            loss_reason: LossReason::BidWon,
+            price_encryption: None,
+        }
+    }
+
+    /// Sets the value of ${AUCTION_LOSS}, which is 0 (bid won) by default.
+    pub fn with_loss_reason(mut self, loss_reason: LossReason) -> AuctionMacros<'a> {
+        self.loss_reason = loss_reason;
+        self
+    }
+
+    /// Encrypts the value of ${AUCTION_PRICE} with the given hook.
+    pub fn with_price_encryption(mut self, price_encryption: &'a dyn PriceEncryption) -> AuctionMacros<'a> {
+        self.price_encryption = Some(price_encryption);
+        self
+    }
+
+    /// The value of a macro, by name without the ${} delimiters, or `None`
+    /// if it is not an auction macro.
+    pub fn value(&self, name: &str) -> Option<String> {
+        let value = match name {
+            AUCTION_ID => self.request.id.clone(),
+            AUCTION_BID_ID => self.response.bid_id.clone().unwrap_or_default(),
+            AUCTION_IMP_ID => self.bid.imp_id.clone(),
+            AUCTION_SEAT_ID => self.seat.unwrap_or_default().to_string(),
+            AUCTION_AD_ID => self.bid.adid.clone().unwrap_or_default(),
+            AUCTION_PRICE => match self.price_encryption {
+                Some(price_encryption) => price_encryption.encrypt_price(self.clearing_price),
+                None => self.clearing_price.to_string(),
+            },
+            AUCTION_CURRENCY => self.response.currency.as_deref().unwrap_or(DEFAULT_CURRENCY).to_string(),
+            AUCTION_MBR => {
+                if self.bid.price > 0.0 {
+                    (self.clearing_price / self.bid.price).to_string()
+                } else {
+                    "0".to_string()
+                }
+            }
+            AUCTION_LOSS => u32::from(self.loss_reason.clone()).to_string(),
+            _ => return None,
+        };
+
+        Some(value)
+    }
+
+    /// Replaces the auction macros in the template. Unknown macros and
+    /// suffixes are left as they are.
+    pub fn expand(&self, template: &str) -> String {
+        let mut expanded = String::with_capacity(template.len());
+        let mut rest = template;
+
+        while let Some(start) = rest.find(MACRO_START) {
+            expanded.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let end = match rest.find(MACRO_END) {
+                Some(end) => end,
+                None => break,
+            };
+
+            let name = &rest[MACRO_START.len()..end];
+            match self.macro_value(name) {
+                Some(value) => expanded.push_str(&value),
+                None => expanded.push_str(&rest[..=end]),
+            }
+            rest = &rest[end + 1..];
+        }
+
+        expanded.push_str(rest);
+        expanded
+    }
+
+    /// Expands the macros in the notice URLs and the markup of the bid.
+    pub fn expand_bid(&self) -> ExpandedBid {
+        let expand = |template: &Option<String>| template.as_ref().map(|template| self.expand(template));
+
+        ExpandedBid {
+            nurl: expand(&self.bid.nurl),
+            burl: expand(&self.bid.burl),
+            lurl: expand(&self.bid.lurl),
+            adm: expand(&self.bid.adm),
+        }
+    }
+
+    fn macro_value(&self, name: &str) -> Option<String> {
+        match name.split_once(SUFFIX_SEPARATOR) {
+            // Encodes the value as substituted without the suffix, so an
+            // encrypted price stays encrypted.
+            Some((name, SUFFIX_BASE64)) => self.value(name).map(|value| base64::encode(value.as_bytes())),
+            Some(_) => None,
+            None => self.value(name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    struct Reversed;
+
+    impl PriceEncryption for Reversed {
+        fn encrypt_price(&self, price: f64) -> String {
+            price.to_string().chars().rev().collect()
+        }
+    }
+
+    fn auction() -> (BidRequest, BidResponse) {
+        let request = serde_json::from_str(r#"{"id":"req-1","imp":[{"id":"imp-1","banner":{}}]}"#).unwrap();
+        let response = serde_json::from_str(
+            r#"{
+                "id": "req-1",
+                "bidid": "resp-1",
+                "cur": "EUR",
+                "seatbid": [
+                    {"seat": "seat-1", "bid": [{
+                        "id": "bid-1",
+                        "impid": "imp-1",
+                        "price": 2.5,
+                        "adid": "ad-1",
+                        "nurl": "https://win.example/?price=${AUCTION_PRICE}&cur=${AUCTION_CURRENCY}&mbr=${AUCTION_MBR}",
+                        "burl": "https://bill.example/?auction=${AUCTION_ID}&bid=${AUCTION_BID_ID}&imp=${AUCTION_IMP_ID}",
+                        "lurl": "https://loss.example/?reason=${AUCTION_LOSS}&seat=${AUCTION_SEAT_ID}&ad=${AUCTION_AD_ID}",
+                        "adm": "<img src=\"https://imp.example/?p=${AUCTION_PRICE:B64}&x=${OTHER}&y=${AUCTION_ID:X}\">"
+                    }]},
+                    {"bid": [{"id": "bid-2", "impid": "imp-1", "price": 1}]}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        (request, response)
+    }
+
+    #[test]
+    fn expand_bid() {
+        let (request, response) = auction();
+        let seat_bid = &response.seat_bid[0];
+        let macros = AuctionMacros::new(&request, &response, seat_bid.seat.as_deref(), &seat_bid.bid[0], 2.0);
+        let expanded = macros.expand_bid();
+
+        assert_eq!(
+            expanded,
+            ExpandedBid {
+                nurl: Some("https://win.example/?price=2&cur=EUR&mbr=0.8".to_string()),
+                burl: Some("https://bill.example/?auction=req-1&bid=resp-1&imp=imp-1".to_string()),
+                lurl: Some("https://loss.example/?reason=0&seat=seat-1&ad=ad-1".to_string()),
+                adm: Some("<img src=\"https://imp.example/?p=Mg==&x=${OTHER}&y=${AUCTION_ID:X}\">".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn expand_loss_without_seat() {
+        let (request, response) = auction();
+        let bid = &response.seat_bid[1].bid[0];
+        let macros =
+            AuctionMacros::new(&request, &response, None, bid, 0.0).with_loss_reason(LossReason::LostToHigherBid);
+
+        assert_eq!(macros.expand("${AUCTION_LOSS}|${AUCTION_SEAT_ID}|${AUCTION_AD_ID}|${AUCTION_MBR}"), "102|||0");
+        assert_eq!(macros.expand_bid(), ExpandedBid::default());
+    }
+
+    #[test]
+    fn expand_cloned_bid() {
+        let (request, response) = auction();
+        let bid = response.seat_bid[0].bid[0].clone();
+
+        let macros = AuctionMacros::new(&request, &response, Some("seat-1"), &bid, 1.0);
+        assert_eq!(macros.expand("${AUCTION_SEAT_ID}|${AUCTION_BID_ID}"), "seat-1|resp-1");
+    }
+
+    #[test]
+    fn expand_encrypted_price() {
+        let (request, response) = auction();
+        let bid = &response.seat_bid[0].bid[0];
+        let macros =
+            AuctionMacros::new(&request, &response, Some("seat-1"), bid, 1.25).with_price_encryption(&Reversed);
+
+        assert_eq!(macros.expand("${AUCTION_PRICE}/${AUCTION_PRICE:B64}"), "52.1/NTIuMQ==");
+    }
+
+    #[test]
+    fn expand_malformed() {
+        let (request, response) = auction();
+        let macros = AuctionMacros::new(&request, &response, None, &response.seat_bid[0].bid[0], 1.0);
+
+        assert_eq!(macros.expand("a${AUCTION_ID"), "a${AUCTION_ID");
+        assert_eq!(macros.expand("${}${AUCTION_ID}$"), "${}req-1$");
+        assert_eq!(macros.value("AUCTION_UNKNOWN"), None);
+    }
+}
@@ -6,5 +6,133 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
-pub struct Audio {}
+use crate::serde_utils;
+
+use super::banner::Banner;
+
+/// 3.2.8 Object: Audio
+///
+/// This object represents an audio type impression. Many of the fields are non-essential for minimally
+/// viable transactions, but are included to offer fine control when needed. Audio in OpenRTB generally
+/// assumes compliance with the DAAST standard. As such, the notion of companion ads is supported by
+/// optionally including an array of Banner objects that define these companion ads.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Audio {
+    /// Content MIME types supported (e.g., “audio/mp4”).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mimes: Vec<String>,
+    /// Minimum audio ad duration in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minduration: Option<u32>,
+    /// Maximum audio ad duration in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxduration: Option<u32>,
+    /// Array of supported audio protocols.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protocols: Vec<u32>,
+    /// Indicates the start delay in seconds for pre-roll, mid-roll, or post-roll ad placements,
+    /// where 0 = pre-roll, -1 = generic mid-roll and -2 = generic post-roll.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub startdelay: Option<i32>,
+    /// If multiple ad impressions are offered in the same bid request, the sequence number will
+    /// allow for the coordinated delivery of multiple creatives.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<u32>,
+    /// Blocked creative attributes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub battr: Vec<u64>,
+    /// Maximum extended ad duration if extension is allowed. If blank or 0, extension is not
+    /// allowed. If -1, extension is allowed, and there is no time limit imposed. If greater
+    /// than 0, then the value represents the number of seconds of extended play supported
+    /// beyond the maxduration value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxextended: Option<i32>,
+    /// Minimum bit rate in Kbps.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minbitrate: Option<u32>,
+    /// Maximum bit rate in Kbps.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxbitrate: Option<u32>,
+    /// Supported delivery methods (e.g., streaming, progressive).
+    /// If none specified, assume all are supported.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub delivery: Vec<u32>,
+    /// Array of Banner objects if companion ads are available.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub companionad: Vec<Banner>,
+    /// List of supported API frameworks for this impression.
+    /// If an API is not explicitly listed, it is assumed not to be supported.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api: Vec<u32>,
+    /// Supported DAAST companion ad types. Recommended if companion Banner objects
+    /// are included via the companionad array.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub companiontype: Vec<u32>,
+    /// The maximum number of ads that can be played in an ad pod.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxseq: Option<u32>,
+    /// Type of audio feed, where 1 = music service, 2 = FM/AM broadcast
+    /// and 3 = podcast.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feed: Option<u32>,
+    /// Indicates if the ad is stitched with audio content or delivered
+    /// independently, where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub stitched: Option<bool>,
+    /// Volume normalization mode, where 0 = none, 1 = average volume,
+    /// 2 = peak volume, 3 = loudness and 4 = custom volume.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nvol: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialization_skip_fields() {
+        let a = Audio {
+            mimes: vec!["audio/mp4".to_string()],
+            ..Default::default()
+        };
+
+        let expected = r#"{"mimes":["audio/mp4"]}"#;
+        let serialized = serde_json::to_string(&a).unwrap();
+
+        assert_eq!(expected, serialized)
+    }
+
+    #[test]
+    fn deserialize_podcast() {
+        let json = r#"{
+            "mimes": ["audio/mpeg", "audio/mp4"],
+            "minduration": 5,
+            "maxduration": 30,
+            "protocols": [9, 10],
+            "startdelay": -1,
+            "maxextended": -1,
+            "companionad": [{"w": 300, "h": 250}],
+            "companiontype": [1, 2],
+            "feed": 3,
+            "stitched": 1,
+            "nvol": 1
+        }"#;
+        let audio: Audio = serde_json::from_str(json).unwrap();
+
+        assert_eq!(audio.mimes.len(), 2);
+        assert_eq!(audio.startdelay, Some(-1));
+        assert_eq!(audio.maxextended, Some(-1));
+        assert_eq!(audio.companionad[0].w, Some(300));
+        assert_eq!(audio.feed, Some(3));
+        assert_eq!(audio.stitched, Some(true));
+        assert_eq!(audio.nvol, Some(1));
+    }
+}
@@ -1,4 +1,6 @@
 use super::category::Category;
+use super::imp::Imp;
+use crate::native::v1_2::{MarkupError, Response as NativeResponse};
 use crate::serde_utils;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -90,4 +92,139 @@ impl Bid {
             ext: None,
         }
     }
+
+    /// Parses the adm as a native response, whether or not it is wrapped
+    /// in a `{"native": ...}` object.
+    pub fn parse_native_adm(&self) -> Result<NativeResponse, MarkupError> {
+        match self.adm {
+            Some(ref adm) => NativeResponse::from_markup(adm),
+            None => Err(MarkupError::MissingMarkup),
+        }
+    }
+
+    /// Parses the adm as a native response and checks its assets against
+    /// the native request of the impression the bid is for.
+    pub fn parse_native_adm_for(&self, imp: &Imp) -> Result<NativeResponse, MarkupError> {
+        let request = match imp.native {
+            Some(ref native) => native.parse_request()?,
+            None => return Err(MarkupError::MissingNativeRequest),
+        };
+
+        let response = self.parse_native_adm()?;
+        response.check_assets(&request)?;
+
+        Ok(response)
+    }
+
+    /// Sets the adm to the given native response in the 1.2 form.
+    pub fn set_native_adm(&mut self, response: &NativeResponse) -> Result<(), MarkupError> {
+        self.adm = Some(response.to_markup()?);
+        Ok(())
+    }
+
+    /// Sets the adm to the given native response wrapped in a
+    /// `{"native": ...}` object.
+    pub fn set_wrapped_native_adm(&mut self, response: &NativeResponse) -> Result<(), MarkupError> {
+        self.adm = Some(response.to_wrapped_markup()?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::native::v1_2::{DestinationLink, Request as NativeRequest};
+    use crate::v2_5::Native;
+
+    fn native_imp() -> Imp {
+        let request = NativeRequest::from_markup(
+            r#"{"assets":[{"id":1,"required":1,"title":{"len":90}},{"id":2,"data":{"type":1}}]}"#,
+        )
+        .unwrap();
+
+        Imp {
+            id: "1".to_string(),
+            native: Some(Native::from_request(&request).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_native_adm() {
+        let mut bid = Bid::new("1".to_string(), "1".to_string(), 1.0);
+        bid.adm = Some(
+            r#"{"native":{"link":{"url":"http://landing.com"},"assets":[{"id":1,"title":{"text":"Hello"}}]}}"#
+                .to_string(),
+        );
+
+        let response = bid.parse_native_adm_for(&native_imp()).unwrap();
+        assert_eq!(response.link.url, "http://landing.com");
+        assert_eq!(response.assets[0].title.as_ref().unwrap().text, "Hello");
+    }
+
+    #[test]
+    fn parse_native_adm_checks_assets() {
+        let mut bid = Bid::new("1".to_string(), "1".to_string(), 1.0);
+
+        bid.adm = Some(r#"{"link":{"url":"http://landing.com"},"assets":[{"id":3,"title":{"text":"Hello"}}]}"#.to_string());
+        match bid.parse_native_adm_for(&native_imp()) {
+            Err(MarkupError::UnknownAsset(3)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        bid.adm = Some(r#"{"link":{"url":"http://landing.com"},"assets":[{"id":2,"data":{"value":"Brand"}}]}"#.to_string());
+        match bid.parse_native_adm_for(&native_imp()) {
+            Err(MarkupError::MissingRequiredAsset(1)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // An asset without an ID cannot stand in for the required one.
+        bid.adm = Some(r#"{"link":{"url":"http://landing.com"},"assets":[{"title":{"text":"Hello"}}]}"#.to_string());
+        match bid.parse_native_adm_for(&native_imp()) {
+            Err(MarkupError::MissingAssetId) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        bid.adm = Some(r#"{"link":{"url":"http://landing.com"},"assetsurl":"http://assets.com/1"}"#.to_string());
+        assert!(bid.parse_native_adm_for(&native_imp()).is_ok());
+    }
+
+    #[test]
+    fn parse_native_adm_errors() {
+        let mut bid = Bid::new("1".to_string(), "1".to_string(), 1.0);
+        match bid.parse_native_adm() {
+            Err(MarkupError::MissingMarkup) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        bid.adm = Some("<div>banner</div>".to_string());
+        match bid.parse_native_adm() {
+            Err(MarkupError::Json(_)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        match bid.parse_native_adm_for(&Imp::default()) {
+            Err(MarkupError::MissingNativeRequest) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn set_native_adm() {
+        let mut bid = Bid::new("1".to_string(), "1".to_string(), 1.0);
+        let mut response = NativeResponse::new(DestinationLink::new("http://landing.com".to_string()));
+        response.ver = None;
+
+        bid.set_native_adm(&response).unwrap();
+        assert_eq!(bid.adm, Some(r#"{"link":{"url":"http://landing.com"}}"#.to_string()));
+        assert_eq!(bid.parse_native_adm().unwrap(), response);
+
+        bid.set_wrapped_native_adm(&response).unwrap();
+        assert_eq!(
+            bid.adm,
+            Some(r#"{"native":{"link":{"url":"http://landing.com"}}}"#.to_string())
+        );
+        assert_eq!(bid.parse_native_adm().unwrap(), response);
+    }
 }
@@ -17,21 +17,18 @@ use super::imp::Imp;
 use super::regulations::Regulations;
 use super::site::Site;
 use super::source::Source;
+use super::supply_chain::SupplyChain;
 use super::user::User;
+use super::validation::Violation;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub enum AuctionType {
     FirstPrice,
+    #[default]
     SecondPricePlus,
     ExchangeSpecific(u32),
 }
 
-impl Default for AuctionType {
-    fn default() -> Self {
-        Self::SecondPricePlus
-    }
-}
-
 // 3.2.1 Object: BidRequest
 //
 // The top-level bid request object contains a globally unique bid request or
@@ -198,8 +195,16 @@ impl BidRequest {
         }
     }
 
+    /// Returns the supply chain of the request, from either `source.schain`
+    /// or `source.ext.schain`.
+    pub fn supply_chain(&self) -> Option<SupplyChain> {
+        self.source.as_ref().and_then(|source| source.supply_chain())
+    }
+
+    /// Whether the request breaks none of the requirements of the
+    /// specification; see `violations` for the details.
     pub fn validate(&self) -> bool {
-        !self.imp.is_empty()
+        !self.violations().iter().any(Violation::is_error)
     }
 }
 
@@ -6,6 +6,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use super::no_bid_reason::NoBidReason;
 use super::seat_bid::SeatBid;
 use crate::serde_utils;
 
@@ -38,8 +39,9 @@ pub struct BidResponse {
     #[serde(rename = "customdata", skip_serializing_if = "Option::is_none")]
     pub custom_data: Option<String>,
 
+    // Reason for not bidding. Refer to List 5.24.
     #[serde(rename = "nbr", skip_serializing_if = "Option::is_none")]
-    pub no_bidding_reason: Option<u32>,
+    pub no_bidding_reason: Option<NoBidReason>,
 
     // Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -58,6 +60,15 @@ impl BidResponse {
             ext: None,
         }
     }
+
+    /// Builds a response to the request with the given ID that bids on
+    /// nothing, for the given reason.
+    pub fn no_bid(id: String, reason: NoBidReason) -> BidResponse {
+        BidResponse {
+            no_bidding_reason: Some(reason),
+            ..BidResponse::new(id)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -108,4 +119,14 @@ mod tests {
 
         assert_eq!(expected.id, b.id);
     }
+
+    #[test]
+    fn no_bid() {
+        let b = BidResponse::no_bid("1234".to_string(), NoBidReason::UnsupportedDevice);
+        assert_eq!(serde_json::to_string(&b).unwrap(), r#"{"id":"1234","nbr":6}"#);
+
+        let b: BidResponse = serde_json::from_str(r#"{"id":"1234","nbr":501}"#).unwrap();
+        assert_eq!(b.no_bidding_reason, Some(NoBidReason::Unknown(501)));
+        assert!(b.seat_bid.is_empty());
+    }
 }
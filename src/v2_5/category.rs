@@ -6,8 +6,6 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::str::FromStr;
-
 use phf::phf_map;
 use serde;
 
@@ -33,7 +31,7 @@ macro_rules! categories {
         }
 
         impl Category {
-            fn value(&self) -> String {
+            pub(crate) fn value(&self) -> String {
                 match *self {
                     $(
                         // match parent category
@@ -47,6 +45,21 @@ macro_rules! categories {
                     Category::Unknown(ref s) => s.clone(),
                 }
             }
+
+            /// The top-level category this category belongs to, which is
+            /// itself for top-level and unknown categories.
+            pub fn parent(&self) -> Category {
+                match *self {
+                    $( Category::$parent(_) => Category::$parent($parent::$parent), )*
+                    Category::Unknown(ref s) => Category::Unknown(s.clone()),
+                }
+            }
+
+            /// Whether this category is the given one or one of its
+            /// subcategories.
+            pub fn is_within(&self, category: &Category) -> bool {
+                self == category || self.parent() == *category
+            }
         }
 
         pub static TO_CATEGORY: phf::Map<&'static str, Category> = phf_map! {
@@ -480,6 +493,15 @@ categories! {
     CopyrightInfringement "IAB26-4"
 }
 
+impl Category {
+    pub(crate) fn from_value(value: &str) -> Category {
+        match TO_CATEGORY.get(value).cloned() {
+            Some(c) => c,
+            None => Category::Unknown(value.to_string()),
+        }
+    }
+}
+
 impl serde::Serialize for Category {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -495,10 +517,7 @@ impl<'de> serde::Deserialize<'de> for Category {
         D: serde::Deserializer<'de>,
     {
         match serde::Deserialize::deserialize(deserializer) {
-            Ok(s) => match TO_CATEGORY.get(s).cloned() {
-                Some(c) => Ok(c),
-                None => Ok(Category::Unknown(FromStr::from_str(s).unwrap())),
-            },
+            Ok(s) => Ok(Category::from_value(s)),
             Err(e) => Err(e),
         }
     }
@@ -509,6 +528,19 @@ mod tests {
     use super::*;
     use serde_json;
 
+    #[test]
+    fn parent() {
+        let sub = Category::ArtsAndEntertainment(ArtsAndEntertainment::Humor);
+        let parent = Category::ArtsAndEntertainment(ArtsAndEntertainment::ArtsAndEntertainment);
+
+        assert_eq!(sub.parent(), parent);
+        assert_eq!(parent.parent(), parent);
+        assert!(sub.is_within(&parent));
+        assert!(sub.is_within(&sub));
+        assert!(!parent.is_within(&sub));
+        assert!(!sub.is_within(&Category::Unknown("IAB1".to_string())));
+    }
+
     #[test]
     fn serialize_parent() {
         let c = Category::ArtsAndEntertainment(ArtsAndEntertainment::ArtsAndEntertainment);
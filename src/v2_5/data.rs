@@ -15,6 +15,9 @@ pub struct Data {
     // The data extension object that contains community extensions.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ext: Option<serde_utils::Ext>,
+    // Exchange-specific ID for the data provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     // The unique domain of the business entity who is stating the additional information about the user or content.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -0,0 +1,186 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use serde_json::Value;
+
+use crate::serde_utils::{self, Ext, EXT_EIDS};
+
+// 3.2.27 Object: EID
+//
+// Extended identifiers support in the OpenRTB specification allows buyers
+// to use audience data in real-time bidding. This object can contain one or
+// more UIDs from a single source or a technology provider.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Eid {
+    // Source or technology provider responsible for the set of included
+    // IDs, expressed as a top-level domain (e.g., “liveramp.com”).
+    pub source: String,
+
+    // Array of extended ID UID objects (Section 3.2.28) from the given
+    // source.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub uids: Vec<Uid>,
+
+    // Placeholder for advertising-system specific extensions to this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// 3.2.28 Object: UID
+//
+// This object contains a single user identifier provided as part of
+// extended identifiers.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Uid {
+    // The identifier for the user.
+    pub id: String,
+
+    // Type of user agent the ID is from, where 1 = a device ID,
+    // 2 = an ID tied to a web browser or app and 3 = a person-based ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub atype: Option<u32>,
+
+    // Placeholder for advertising-system specific extensions to this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+impl Eid {
+    /// Whether the IDs are from the given source domain, ignoring case.
+    pub fn is_from(&self, source: &str) -> bool {
+        self.source.eq_ignore_ascii_case(source)
+    }
+
+    /// The first UID of the given type.
+    pub fn uid_with_atype(&self, atype: u32) -> Option<&Uid> {
+        self.uids.iter().find(|uid| uid.atype == Some(atype))
+    }
+}
+
+// Reads the extended identifiers from ext.eids, skipping the ones that do not
+// parse.
+pub(crate) fn ext_eids(ext: &Option<Ext>) -> Vec<Eid> {
+    match ext.as_ref().and_then(|ext| ext.get(EXT_EIDS)) {
+        Some(Value::Array(values)) => values
+            .iter()
+            .filter_map(|value| serde_json::from_value(value.clone()).ok())
+            .collect(),
+        _ => vec![],
+    }
+}
+
+// Stores the extended identifiers in ext.eids, removing the key when there
+// are none.
+pub(crate) fn set_ext_eids(ext: &mut Option<Ext>, eids: Vec<Eid>) {
+    if eids.is_empty() {
+        remove_ext_eids(ext);
+        return;
+    }
+
+    serde_utils::put_ext(ext, EXT_EIDS, eids);
+}
+
+// Removes ext.eids, and ext itself if nothing else is left in it. Returns
+// whether there was an ext.eids.
+pub(crate) fn remove_ext_eids(ext: &mut Option<Ext>) -> bool {
+    serde_utils::remove_ext(ext, EXT_EIDS)
+}
+
+// Removes the extended identifiers of the given sources from ext.eids, and
+// returns how many were removed. Entries are matched on their source alone,
+// so ones that do not otherwise parse are removed too.
+pub(crate) fn strip_ext_eids(ext: &mut Option<Ext>, sources: &[&str]) -> usize {
+    let (removed, emptied) = match ext.as_mut().and_then(|ext| ext.get_mut(EXT_EIDS)) {
+        Some(Value::Array(values)) => {
+            let count = values.len();
+            values.retain(|value| {
+                !value
+                    .get("source")
+                    .and_then(Value::as_str)
+                    .is_some_and(|source| is_listed(source, sources))
+            });
+            (count - values.len(), values.is_empty())
+        }
+        _ => return 0,
+    };
+
+    if removed > 0 && emptied {
+        remove_ext_eids(ext);
+    }
+    removed
+}
+
+// Removes the extended identifiers of the given sources, and returns how many
+// were removed.
+pub(crate) fn strip_eids(eids: &mut Vec<Eid>, sources: &[&str]) -> usize {
+    let count = eids.len();
+    eids.retain(|eid| !is_listed(&eid.source, sources));
+    count - eids.len()
+}
+
+pub(crate) fn uids_with_atype(eids: Vec<Eid>, atype: u32) -> Vec<Uid> {
+    eids.into_iter()
+        .flat_map(|eid| eid.uids)
+        .filter(|uid| uid.atype == Some(atype))
+        .collect()
+}
+
+fn is_listed(source: &str, sources: &[&str]) -> bool {
+    sources.iter().any(|listed| listed.eq_ignore_ascii_case(source))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+    use serde_json::json;
+
+    fn ext(value: Value) -> Option<Ext> {
+        value.as_object().cloned()
+    }
+
+    #[test]
+    fn uid_with_atype() {
+        let eid: Eid = serde_json::from_str(
+            r#"{"source":"uid2.com","uids":[{"id":"A4AAAAs","atype":3},{"id":"B5BBBBt","atype":1}]}"#,
+        )
+        .unwrap();
+
+        assert!(eid.is_from("UID2.com"));
+        assert_eq!(eid.uid_with_atype(1).unwrap().id, "B5BBBBt");
+        assert_eq!(eid.uid_with_atype(2), None);
+    }
+
+    #[test]
+    fn ext_eids_skips_malformed() {
+        let ext = ext(json!({"eids": [
+            {"source": "pubcid.org", "uids": [{"id": "1"}]},
+            {"source": "id5-sync.com", "uids": "invalid"},
+            {"source": "liveramp.com", "uids": [{"id": "2", "atype": 3}]}
+        ]}));
+
+        let eids = ext_eids(&ext);
+        assert_eq!(eids.len(), 2);
+        assert_eq!(eids[1].source, "liveramp.com");
+        assert_eq!(ext_eids(&None), vec![]);
+    }
+
+    #[test]
+    fn strip_ext() {
+        let mut e = ext(json!({"eids": [
+            {"source": "pubcid.org", "uids": [{"id": "1"}]},
+            {"source": "id5-sync.com", "uids": "invalid"}
+        ], "other": 1}));
+
+        assert_eq!(strip_ext_eids(&mut e, &["ID5-sync.com"]), 1);
+        assert_eq!(ext_eids(&e).len(), 1);
+        assert_eq!(strip_ext_eids(&mut e, &["uid2.com"]), 0);
+        assert_eq!(strip_ext_eids(&mut e, &["pubcid.org"]), 1);
+        assert_eq!(e, ext(json!({"other": 1})));
+    }
+}
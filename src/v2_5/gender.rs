@@ -0,0 +1,97 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use serde;
+
+// Gender of a user, where “M” = male, “F” = female, “O” = known to be other
+// (i.e., omitted is unknown). Values the specification does not define are
+// kept in the `Unknown` variant, so they survive a round trip.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Gender {
+    Male,
+    Female,
+    Other,
+    Unknown(String),
+}
+
+impl Gender {
+    fn value(&self) -> &str {
+        match *self {
+            Gender::Male => "M",
+            Gender::Female => "F",
+            Gender::Other => "O",
+            Gender::Unknown(ref s) => s,
+        }
+    }
+}
+
+impl From<String> for Gender {
+    fn from(value: String) -> Gender {
+        match value.as_str() {
+            "M" => Gender::Male,
+            "F" => Gender::Female,
+            "O" => Gender::Other,
+            _ => Gender::Unknown(value),
+        }
+    }
+}
+
+impl From<Gender> for String {
+    fn from(value: Gender) -> String {
+        match value {
+            Gender::Unknown(s) => s,
+            known => known.value().to_string(),
+        }
+    }
+}
+
+impl serde::Serialize for Gender {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.value())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Gender {
+    fn deserialize<D>(deserializer: D) -> Result<Gender, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s: String = serde::Deserialize::deserialize(deserializer)?;
+        Ok(s.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialize_ok() {
+        assert_eq!(r#""F""#, serde_json::to_string(&Gender::Female).unwrap());
+        assert_eq!(r#""X""#, serde_json::to_string(&Gender::Unknown("X".to_string())).unwrap());
+    }
+
+    #[test]
+    fn deserialize_ok() {
+        let g: Gender = serde_json::from_str(r#""O""#).unwrap();
+        assert_eq!(Gender::Other, g);
+
+        let g: Gender = serde_json::from_str(r#""male""#).unwrap();
+        assert_eq!(Gender::Unknown("male".to_string()), g);
+    }
+
+    #[test]
+    fn deserialize_bad() {
+        let result: Result<Gender, serde_json::Error> = serde_json::from_str("1");
+        assert!(result.is_err());
+    }
+}
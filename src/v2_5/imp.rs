@@ -111,6 +111,18 @@ impl Default for Imp {
     }
 }
 
+impl Imp {
+    /// Returns the first metric of the given type (e.g., “viewability”), if any.
+    pub fn find_metric(&self, type_: &str) -> Option<&Metric> {
+        self.metric.iter().find(|metric| metric.type_ == type_)
+    }
+
+    /// Returns the value of the first metric of the given type, if any.
+    pub fn metric_value(&self, type_: &str) -> Option<f32> {
+        self.find_metric(type_).map(|metric| metric.value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,4 +162,23 @@ mod tests {
             "7a5156a2-50f5-4dea-9eeb-a767f975d500",
         )
     }
+
+    #[test]
+    fn find_metric() {
+        let i: Imp = serde_json::from_str(
+            r#"{
+                "id": "1",
+                "metric": [
+                    {"type": "viewability", "value": 0.85, "vendor": "EXCHANGE"},
+                    {"type": "click_through_rate", "value": 0.01}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(i.find_metric("viewability").unwrap().vendor, Some("EXCHANGE".to_string()));
+        assert_eq!(i.metric_value("viewability"), Some(0.85));
+        assert_eq!(i.metric_value("click_through_rate"), Some(0.01));
+        assert_eq!(i.metric_value("session_depth"), None);
+    }
 }
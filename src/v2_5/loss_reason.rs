@@ -0,0 +1,79 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// 5.25 Loss Reason Codes
+//
+// Options for an exchange to inform a bidder as to the reason why they did
+// not win an impression, as substituted for the ${AUCTION_LOSS} macro.
+// Values of 1000 and above are reserved for exchange-specific usage.
+open_enum_list_module! {
+    LossReason u32:
+        BidWon 0,
+        InternalError 1,
+        ImpressionOpportunityExpired 2,
+        InvalidBidResponse 3,
+        InvalidDealId 4,
+        InvalidAuctionId 5,
+        InvalidAdvertiserDomain 6,
+        MissingMarkup 7,
+        MissingCreativeId 8,
+        MissingBidPrice 9,
+        MissingMinimumCreativeApprovalData 10,
+        BelowAuctionFloor 100,
+        BelowDealFloor 101,
+        LostToHigherBid 102,
+        LostToPmpDeal 103,
+        BuyerSeatBlocked 104,
+        CreativeFiltered 200,
+        CreativePendingProcessing 201,
+        CreativeDisapproved 202,
+        CreativeSizeNotAllowed 203,
+        IncorrectCreativeFormat 204,
+        AdvertiserExclusions 205,
+        ApplicationBundleExclusions 206,
+        CreativeNotSecure 207,
+        LanguageExclusions 208,
+        CategoryExclusions 209,
+        CreativeAttributeExclusions 210,
+        AdTypeExclusions 211,
+        AnimationTooLong 212,
+        NotAllowedInPmpDeal 213
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialize_good() {
+        let x = LossReason::LostToHigherBid;
+        let expected = "102";
+        let serialized = serde_json::to_string(&x).unwrap();
+
+        assert_eq!(expected, serialized)
+    }
+
+    #[test]
+    fn deserialize_good() {
+        let serialized = "209";
+        let expected = LossReason::CategoryExclusions;
+        let x = serde_json::from_str(serialized).unwrap();
+
+        assert_eq!(expected, x)
+    }
+
+    #[test]
+    fn deserialize_exchange_specific() {
+        let serialized = "1001";
+        let x: LossReason = serde_json::from_str(serialized).unwrap();
+
+        assert_eq!(LossReason::Unknown(1001), x);
+        assert_eq!("1001", serde_json::to_string(&x).unwrap());
+    }
+}
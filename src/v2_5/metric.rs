@@ -6,5 +6,70 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
-pub struct Metric {}
+use crate::serde_utils;
+
+// 3.2.5 Object: Metric
+//
+// This object is associated with an impression as an array of metrics. These
+// metrics can offer insight into the impression to assist with decisioning
+// such as average recent viewability, click-through rate, etc. Each metric is
+// identified by its type, reports the value of the metric, and optionally
+// identifies the source or vendor measuring the value.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Metric {
+    // Required. Type of metric being presented using exchange curated
+    // string names which should be published to bidders a priori.
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    // Required. Number representing the value of the metric.
+    // Probabilities must be in the range 0.0 – 1.0.
+    pub value: f32,
+
+    // Source of the value using exchange curated string names which
+    // should be published to bidders a priori. If the exchange itself
+    // is the source versus a third party, “EXCHANGE” is recommended.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vendor: Option<String>,
+
+    // Placeholder for exchange-specific extensions to OpenRTB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+impl Metric {
+    pub fn new(type_: String, value: f32) -> Metric {
+        Metric {
+            type_,
+            value,
+            vendor: None,
+            ext: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialization_skip_fields() {
+        let m = Metric::new("viewability".to_string(), 0.5);
+
+        let expected = r#"{"type":"viewability","value":0.5}"#;
+        let serialized = serde_json::to_string(&m).unwrap();
+
+        assert_eq!(expected, serialized)
+    }
+
+    #[test]
+    fn deserialize_with_vendor() {
+        let serialized = r#"{"type":"click_through_rate","value":0.02,"vendor":"EXCHANGE"}"#;
+        let m: Metric = serde_json::from_str(serialized).unwrap();
+
+        assert_eq!(m.type_, "click_through_rate");
+        assert_eq!(m.value, 0.02);
+        assert_eq!(m.vendor, Some("EXCHANGE".to_string()));
+    }
+}
@@ -7,6 +7,8 @@
 // except according to those terms.
 
 mod ad_position;
+mod auction;
+mod auction_macros;
 mod app;
 mod audio;
 mod banner;
@@ -18,27 +20,36 @@ pub(crate) mod category;
 mod content;
 mod data;
 mod device;
+pub(crate) mod eid;
 mod format;
+mod gender;
 mod geo;
 mod imp;
+mod loss_reason;
 mod metric;
 mod native;
+mod no_bid_reason;
 mod pmp;
 mod publisher;
 mod regulations;
+mod rejection;
+mod scrub;
 mod seat_bid;
 mod segment;
 mod site;
 mod source;
 mod supply_chain;
 mod user;
+mod validation;
 mod video;
 
-pub use self::ad_position::*;
+pub use self::ad_position::AdPosition;
 pub use self::app::*;
+pub use self::auction::*;
+pub use self::auction_macros::*;
 pub use self::audio::*;
 pub use self::banner::*;
-pub use self::banner_ad_type::*;
+pub use self::banner_ad_type::BannerAdType;
 pub use self::bid::*;
 pub use self::bid_request::*;
 pub use self::bid_response::*;
@@ -46,19 +57,26 @@ pub use self::category::*;
 pub use self::content::*;
 pub use self::data::*;
 pub use self::device::*;
+pub use self::eid::*;
 pub use self::format::*;
+pub use self::gender::Gender;
 pub use self::geo::*;
 pub use self::imp::*;
+pub use self::loss_reason::LossReason;
 pub use self::metric::*;
 pub use self::native::*;
+pub use self::no_bid_reason::NoBidReason;
 pub use self::pmp::*;
 pub use self::publisher::*;
 pub use self::regulations::*;
+pub use self::rejection::*;
+pub use self::scrub::*;
 pub use self::seat_bid::*;
 pub use self::segment::*;
 pub use self::site::*;
 pub use self::source::*;
 pub use self::supply_chain::*;
 pub use self::user::*;
+pub use self::validation::*;
 pub use self::video::*;
 pub use crate::serde_utils::Ext;
@@ -6,6 +6,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use crate::native::v1_2::{MarkupError, Request};
 use crate::serde_utils;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -32,3 +33,89 @@ impl Default for Native {
         }
     }
 }
+
+impl Native {
+    /// Builds a Native object carrying the given request, serialized in the 1.2 form.
+    pub fn from_request(request: &Request) -> Result<Native, MarkupError> {
+        Ok(Native {
+            request: request.to_markup()?,
+            ver: request.ver.clone(),
+            ..Default::default()
+        })
+    }
+
+    /// Builds a Native object carrying the given request wrapped in a `{"native": ...}`
+    /// object, for exchanges that still expect the 1.0 and 1.1 form.
+    pub fn from_wrapped_request(request: &Request) -> Result<Native, MarkupError> {
+        Ok(Native {
+            request: request.to_wrapped_markup()?,
+            ver: request.ver.clone(),
+            ..Default::default()
+        })
+    }
+
+    /// Parses the request payload, whether or not it is wrapped in a `{"native": ...}` object.
+    pub fn parse_request(&self) -> Result<Request, MarkupError> {
+        Request::from_markup(&self.request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    use crate::native::v1_2::DataAssetType;
+
+    #[test]
+    fn parse_request() {
+        let serialized = r#"{
+            "request": "{\"ver\":\"1.2\",\"assets\":[{\"id\":1,\"required\":1,\"data\":{\"type\":1}}]}",
+            "ver": "1.2"
+        }"#;
+        let native: Native = serde_json::from_str(serialized).unwrap();
+        let request = native.parse_request().unwrap();
+
+        assert_eq!(request.assets.len(), 1);
+        assert_eq!(
+            request.assets[0].data.as_ref().unwrap().type_,
+            DataAssetType::Sponsored
+        );
+    }
+
+    #[test]
+    fn parse_wrapped_request() {
+        let native = Native {
+            request: r#"{"native":{"ver":"1.1","plcmtcnt":2}}"#.to_string(),
+            ..Default::default()
+        };
+        let request = native.parse_request().unwrap();
+
+        assert_eq!(request.ver, Some("1.1".to_string()));
+        assert_eq!(request.placement_count, Some(2));
+    }
+
+    #[test]
+    fn parse_invalid_request() {
+        let native = Native {
+            request: "{".to_string(),
+            ..Default::default()
+        };
+
+        assert!(native.parse_request().is_err());
+    }
+
+    #[test]
+    fn build_request() {
+        let request = Request::from_markup(r#"{"ver":"1.2","plcmttype":1}"#).unwrap();
+
+        let native = Native::from_request(&request).unwrap();
+        assert_eq!(native.request, r#"{"ver":"1.2","plcmttype":1}"#);
+        assert_eq!(native.ver, Some("1.2".to_string()));
+        assert_eq!(native.parse_request().unwrap(), request);
+
+        let native = Native::from_wrapped_request(&request).unwrap();
+        assert_eq!(native.request, r#"{"native":{"ver":"1.2","plcmttype":1}}"#);
+        assert_eq!(native.parse_request().unwrap(), request);
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// 5.24 No-Bid Reason Codes
+//
+// Options for a bidder to signal the exchange as to why it did not offer a
+// bid for the impression. Values of 500 and above are reserved for
+// exchange-specific usage.
+open_enum_list_module! {
+    NoBidReason u32:
+        UnknownError 0,
+        TechnicalError 1,
+        InvalidRequest 2,
+        KnownWebSpider 3,
+        SuspectedNonHumanTraffic 4,
+        CloudDataCenterOrProxyIp 5,
+        UnsupportedDevice 6,
+        BlockedPublisherOrSite 7,
+        UnmatchedUser 8,
+        DailyUserCapMet 9,
+        DailyDomainCapMet 10,
+        AdsTxtAuthorizationUnavailable 11,
+        AdsTxtAuthorizationViolation 12,
+        AdsCertAuthenticationUnavailable 13,
+        AdsCertAuthenticationViolation 14,
+        InsufficientAuctionTime 15,
+        IncompleteSupplyChain 16,
+        BlockedSupplyChainNode 17
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialize_good() {
+        let x = NoBidReason::UnmatchedUser;
+        let expected = "8";
+        let serialized = serde_json::to_string(&x).unwrap();
+
+        assert_eq!(expected, serialized)
+    }
+
+    #[test]
+    fn deserialize_good() {
+        let serialized = "0";
+        let expected = NoBidReason::UnknownError;
+        let x = serde_json::from_str(serialized).unwrap();
+
+        assert_eq!(expected, x)
+    }
+
+    #[test]
+    fn deserialize_exchange_specific() {
+        let serialized = "502";
+        let x: NoBidReason = serde_json::from_str(serialized).unwrap();
+
+        assert_eq!(NoBidReason::Unknown(502), x);
+        assert_eq!("502", serde_json::to_string(&x).unwrap());
+    }
+}
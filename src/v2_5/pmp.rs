@@ -6,5 +6,157 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
-pub struct PMP {}
+use crate::serde_utils;
+
+use super::bid_request::AuctionType;
+
+// 3.2.11 Object: Pmp
+//
+// This object is the private marketplace container for direct deals between
+// buyers and sellers that may pertain to this impression. The actual deals are
+// represented as a collection of Deal objects. Refer to Section 7.3 for more
+// details.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct PMP {
+    // Indicator of auction eligibility to seats named in the Direct
+    // Deals object, where 0 = all bids are accepted, 1 = bids are
+    // restricted to the deals specified and the terms thereof.
+    #[serde(
+        default = "serde_utils::default_false",
+        skip_serializing_if = "serde_utils::is_false",
+        serialize_with = "serde_utils::bool_to_u8",
+        deserialize_with = "serde_utils::u8_to_bool"
+    )]
+    pub private_auction: bool,
+
+    // Array of Deal (Section 3.2.12) objects that convey the specific
+    // deals applicable to this impression.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deals: Vec<Deal>,
+
+    // Placeholder for exchange-specific extensions to OpenRTB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+impl PMP {
+    pub fn deal(&self, id: &str) -> Option<&Deal> {
+        self.deals.iter().find(|deal| deal.id == id)
+    }
+}
+
+// 3.2.12 Object: Deal
+//
+// This object constitutes a specific deal that was struck a priori between a
+// buyer and a seller. Its presence with the Pmp collection indicates that this
+// impression is available under the terms of that deal. Refer to Section 7.3
+// for more details.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Deal {
+    // Required. A unique identifier for the direct deal.
+    pub id: String,
+
+    // Minimum bid for this impression expressed in CPM.
+    #[serde(rename = "bidfloor", skip_serializing_if = "Option::is_none")]
+    pub bid_floor: Option<f32>,
+
+    // Currency specified using ISO-4217 alpha codes. This may be
+    // different from bid currency returned by bidder if this is
+    // allowed by the exchange.
+    #[serde(rename = "bidfloorcur", skip_serializing_if = "Option::is_none")]
+    pub bid_floor_cur: Option<String>,
+
+    // Optional override of the overall auction type of the bid
+    // request, where 1 = First Price, 2 = Second Price Plus, 3 = the
+    // value passed in bidfloor is the agreed upon deal price.
+    // Additional auction types can be defined by the exchange.
+    #[serde(rename = "at", skip_serializing_if = "Option::is_none")]
+    pub auction_type: Option<AuctionType>,
+
+    // Whitelist of buyer seats (e.g., advertisers, agencies) allowed to
+    // bid on this deal. IDs of seats and the buyer’s customers to
+    // which they refer must be coordinated between bidders and the
+    // exchange a priori. Omission implies no seat restrictions.
+    #[serde(rename = "wseat", default, skip_serializing_if = "Vec::is_empty")]
+    pub seat_whitelist: Vec<String>,
+
+    // Array of advertiser domains (e.g., advertiser.com) allowed to
+    // bid on this deal. Omission implies no advertiser restrictions.
+    #[serde(rename = "wadomain", default, skip_serializing_if = "Vec::is_empty")]
+    pub advertiser_domain_whitelist: Vec<String>,
+
+    // Placeholder for exchange-specific extensions to OpenRTB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+impl Deal {
+    pub fn new(id: String) -> Deal {
+        Deal {
+            id,
+            bid_floor: None,
+            bid_floor_cur: None,
+            auction_type: None,
+            seat_whitelist: vec![],
+            advertiser_domain_whitelist: vec![],
+            ext: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialization_skip_fields() {
+        let p = PMP {
+            private_auction: false,
+            deals: vec![Deal::new("AB-Agency1-0001".to_string())],
+            ext: None,
+        };
+
+        let expected = r#"{"deals":[{"id":"AB-Agency1-0001"}]}"#;
+        let serialized = serde_json::to_string(&p).unwrap();
+
+        assert_eq!(expected, serialized)
+    }
+
+    #[test]
+    fn deserialize_deals() {
+        let serialized = r#"{
+            "private_auction": 1,
+            "deals": [
+                {
+                    "id": "AB-Agency1-0001",
+                    "at": 1,
+                    "bidfloor": 2.5,
+                    "wseat": ["Agency1"]
+                },
+                {
+                    "id": "XY-Agency2-0001",
+                    "at": 2,
+                    "bidfloor": 2,
+                    "bidfloorcur": "EUR",
+                    "wadomain": ["advertiser.com"]
+                }
+            ]
+        }"#;
+
+        let p: PMP = serde_json::from_str(serialized).unwrap();
+
+        assert!(p.private_auction);
+        assert_eq!(p.deals.len(), 2);
+        assert_eq!(p.deals[0].auction_type, Some(AuctionType::FirstPrice));
+        assert_eq!(p.deals[0].bid_floor, Some(2.5));
+        assert_eq!(p.deals[0].seat_whitelist, vec!["Agency1".to_string()]);
+        assert_eq!(p.deals[1].bid_floor_cur, Some("EUR".to_string()));
+        assert_eq!(
+            p.deals[1].advertiser_domain_whitelist,
+            vec!["advertiser.com".to_string()]
+        );
+        assert_eq!(p.deal("XY-Agency2-0001"), Some(&p.deals[1]));
+        assert_eq!(p.deal("unknown"), None);
+    }
+}
@@ -6,12 +6,20 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use crate::serde_utils;
+use serde_json::Value;
+
+use crate::privacy::gpp::{Gpp, GppError};
+use crate::privacy::us_privacy::{UsPrivacy, UsPrivacyError};
+use crate::serde_utils::{self, EXT_GDPR, EXT_GPP, EXT_GPP_SID, EXT_US_PRIVACY};
 
 // This object contains any legal, governmental, or industry regulations
 // that apply to the request. The coppa flag signals whether or not
 // the request falls under the United States Federal Trade Commission’s
 // regulations for the United States Children’s Online Privacy Protection Act (“COPPA”).
+//
+// OpenRTB 2.5 requests carry the GDPR and US Privacy signals in ext, while
+// OpenRTB 2.6 promotes them to fields of their own. The accessor methods read
+// whichever form the request used.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
 pub struct Regulations {
     // Flag indicating if this request is subject to the COPPA
@@ -24,11 +32,142 @@ pub struct Regulations {
     )]
     pub coppa: bool,
 
+    // Flag that indicates whether or not the request is subject to
+    // GDPR regulations, where 0 = no, 1 = yes, and omission indicates
+    // unknown.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub gdpr: Option<bool>,
+
+    // Communicates signals regarding consumer privacy under US privacy
+    // regulation under CCPA and LSPA (e.g., “1YNN”).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub us_privacy: Option<String>,
+
+    // Contains the Global Privacy Platform’s consent string.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpp: Option<String>,
+
+    // Array of the section(s) of the GPP string which should be applied
+    // for this transaction.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gpp_sid: Vec<u32>,
+
     // Placeholder for exchange-specific extensions to OpenRTB.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ext: Option<serde_utils::Ext>,
 }
 
+impl Regulations {
+    /// Whether GDPR applies to the request, from `gdpr` or `ext.gdpr`.
+    /// `None` means unknown.
+    pub fn gdpr_applies(&self) -> Option<bool> {
+        if self.gdpr.is_some() {
+            return self.gdpr;
+        }
+
+        match self.ext_value(EXT_GDPR)? {
+            Value::Bool(b) => Some(*b),
+            Value::Number(n) => match n.as_u64() {
+                Some(0) => Some(false),
+                Some(1) => Some(true),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The US Privacy string, from `us_privacy` or `ext.us_privacy`.
+    pub fn us_privacy_string(&self) -> Option<&str> {
+        match self.us_privacy {
+            Some(ref us_privacy) => Some(us_privacy),
+            None => self.ext_value(EXT_US_PRIVACY)?.as_str(),
+        }
+    }
+
+    /// Parses the US Privacy string, if there is one.
+    pub fn parse_us_privacy(&self) -> Option<Result<UsPrivacy, UsPrivacyError>> {
+        self.us_privacy_string().map(str::parse)
+    }
+
+    /// Whether the US Privacy string signals that the user opted out of the
+    /// sale of their personal information. A missing or invalid string
+    /// signals nothing.
+    pub fn us_privacy_opt_out_sale(&self) -> bool {
+        match self.parse_us_privacy() {
+            Some(Ok(us_privacy)) => us_privacy.is_opted_out_of_sale(),
+            _ => false,
+        }
+    }
+
+    /// The GPP string, from `gpp` or `ext.gpp`.
+    pub fn gpp_string(&self) -> Option<&str> {
+        match self.gpp {
+            Some(ref gpp) => Some(gpp),
+            None => self.ext_value(EXT_GPP)?.as_str(),
+        }
+    }
+
+    /// The applicable GPP section IDs, from `gpp_sid` or `ext.gpp_sid`.
+    pub fn gpp_section_ids(&self) -> Vec<u32> {
+        if !self.gpp_sid.is_empty() {
+            return self.gpp_sid.clone();
+        }
+
+        self.ext_value(EXT_GPP_SID)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Parses the GPP string, if there is one.
+    pub fn parse_gpp(&self) -> Option<Result<Gpp, GppError>> {
+        self.gpp_string().map(Gpp::parse)
+    }
+
+    /// Whether any applicable section of the GPP string signals that the
+    /// user opted out of the sale of their personal information. Without
+    /// section IDs every section applies. A missing or invalid string
+    /// signals nothing.
+    pub fn gpp_opt_out_sale(&self) -> bool {
+        self.gpp_opt_out(Gpp::is_opted_out_of_sale)
+    }
+
+    /// Whether any applicable section of the GPP string signals that the
+    /// user opted out of targeted advertising.
+    pub fn gpp_opt_out_targeted_advertising(&self) -> bool {
+        self.gpp_opt_out(Gpp::is_opted_out_of_targeted_advertising)
+    }
+
+    /// Whether the user opted out of sale or targeted advertising under
+    /// either the GPP string or the US Privacy string.
+    pub fn opted_out_of_sale_or_targeted_advertising(&self) -> bool {
+        self.gpp_opt_out_sale() || self.gpp_opt_out_targeted_advertising() || self.us_privacy_opt_out_sale()
+    }
+
+    fn gpp_opt_out(&self, opted_out: fn(&Gpp, &[u16]) -> bool) -> bool {
+        let gpp = match self.parse_gpp() {
+            Some(Ok(gpp)) => gpp,
+            _ => return false,
+        };
+
+        let section_ids = self.gpp_section_ids();
+        let applicable: Vec<u16> = section_ids.iter().filter_map(|&id| u16::try_from(id).ok()).collect();
+        if applicable.is_empty() && !section_ids.is_empty() {
+            return false;
+        }
+
+        opted_out(&gpp, &applicable)
+    }
+
+    fn ext_value(&self, key: &str) -> Option<&Value> {
+        self.ext.as_ref()?.get(key)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -38,6 +177,10 @@ mod tests {
     fn serialization_skip_fields() {
         let r = Regulations {
             coppa: false,
+            gdpr: None,
+            us_privacy: None,
+            gpp: None,
+            gpp_sid: vec![],
             ext: None,
         };
 
@@ -51,6 +194,10 @@ mod tests {
     fn deserialize_defaults() {
         let expected = Regulations {
             coppa: false,
+            gdpr: None,
+            us_privacy: None,
+            gpp: None,
+            gpp_sid: vec![],
             ext: None,
         };
 
@@ -59,4 +206,79 @@ mod tests {
 
         assert_eq!(expected, deserialized)
     }
+
+    #[test]
+    fn read_ext_form() {
+        let r: Regulations = serde_json::from_str(
+            r#"{"ext":{"gdpr":1,"us_privacy":"1YNN","gpp":"DBABMA~CPXxRfAPXxRfAAfKABENB-CgAAAAAAAAAAYgAAAAAAAA","gpp_sid":[2]}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(r.gdpr, None);
+        assert_eq!(r.gdpr_applies(), Some(true));
+        assert_eq!(r.us_privacy_string(), Some("1YNN"));
+        assert_eq!(r.gpp_string(), Some("DBABMA~CPXxRfAPXxRfAAfKABENB-CgAAAAAAAAAAYgAAAAAAAA"));
+        assert_eq!(r.gpp_section_ids(), vec![2]);
+    }
+
+    #[test]
+    fn read_top_level_form() {
+        let r: Regulations = serde_json::from_str(
+            r#"{"gdpr":0,"us_privacy":"1NNN","gpp":"DBABTA~1YNN","gpp_sid":[6],"ext":{"gdpr":1,"us_privacy":"1YYN"}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(r.gdpr_applies(), Some(false));
+        assert_eq!(r.us_privacy_string(), Some("1NNN"));
+        assert_eq!(r.gpp_string(), Some("DBABTA~1YNN"));
+        assert_eq!(r.gpp_section_ids(), vec![6]);
+    }
+
+    #[test]
+    fn us_privacy_opt_out_sale() {
+        let r: Regulations = serde_json::from_str(r#"{"ext":{"us_privacy":"1YYN"}}"#).unwrap();
+        assert!(r.us_privacy_opt_out_sale());
+        assert_eq!(r.parse_us_privacy().unwrap().unwrap().to_string(), "1YYN");
+
+        let r: Regulations = serde_json::from_str(r#"{"us_privacy":"1YNY"}"#).unwrap();
+        assert!(!r.us_privacy_opt_out_sale());
+
+        let r: Regulations = serde_json::from_str(r#"{"us_privacy":"YY"}"#).unwrap();
+        assert!(r.parse_us_privacy().unwrap().is_err());
+        assert!(!r.us_privacy_opt_out_sale());
+        assert!(!Regulations::default().us_privacy_opt_out_sale());
+    }
+
+    #[test]
+    fn gpp_opt_outs() {
+        let r: Regulations = serde_json::from_str(r#"{"gpp":"DBACNYA~CPXxRfAPXxRfAAfKABENB-CgAAAAAAAAAAYgAAAAAAAA~1YYN","gpp_sid":[6]}"#).unwrap();
+        assert_eq!(r.parse_gpp().unwrap().unwrap().section_ids(), vec![2, 6]);
+        assert!(r.gpp_opt_out_sale());
+        assert!(!r.gpp_opt_out_targeted_advertising());
+        assert!(r.opted_out_of_sale_or_targeted_advertising());
+
+        // The USP v1 section does not apply.
+        let r: Regulations = serde_json::from_str(r#"{"ext":{"gpp":"DBABTA~1YYN","gpp_sid":[2]}}"#).unwrap();
+        assert!(!r.gpp_opt_out_sale());
+        assert!(!r.opted_out_of_sale_or_targeted_advertising());
+
+        let r: Regulations = serde_json::from_str(r#"{"gpp":"DBABTA~1YYN"}"#).unwrap();
+        assert!(r.gpp_opt_out_sale());
+
+        let r: Regulations = serde_json::from_str(r#"{"gpp":"DBABTA~1YX","us_privacy":"1YYN"}"#).unwrap();
+        assert!(r.parse_gpp().unwrap().is_err());
+        assert!(!r.gpp_opt_out_sale());
+        assert!(r.opted_out_of_sale_or_targeted_advertising());
+        assert!(!Regulations::default().opted_out_of_sale_or_targeted_advertising());
+    }
+
+    #[test]
+    fn read_unknown() {
+        let r: Regulations = serde_json::from_str(r#"{"ext":{"gdpr":"yes"}}"#).unwrap();
+
+        assert_eq!(r.gdpr_applies(), None);
+        assert_eq!(r.us_privacy_string(), None);
+        assert_eq!(r.gpp_string(), None);
+        assert!(r.gpp_section_ids().is_empty());
+    }
 }
@@ -0,0 +1,460 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt::{self, Display};
+
+use super::bid::Bid;
+use super::bid_request::BidRequest;
+use super::bid_response::BidResponse;
+use super::category::Category;
+use super::imp::Imp;
+use super::loss_reason::LossReason;
+
+// Currency of bid floors and bids when none is specified.
+pub const DEFAULT_CURRENCY: &str = "USD";
+
+// Why an exchange rejects a response or one of its bids.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RejectionReason {
+    // The response ID is not the ID of the request.
+    ResponseIdMismatch(String),
+    // The bid currency is not one of the currencies the request allows.
+    CurrencyNotAllowed(String),
+    // The bid refers to an Imp the request does not have.
+    UnknownImp(String),
+    // The price is below the bid floor of the Imp.
+    BelowBidFloor(f64),
+    // The price is below the floor of the deal the bid is for, which takes
+    // the place of the floor of the Imp.
+    BelowDealFloor(f64),
+    // The floor that applies to the bid is in another currency than the
+    // bid, so the price cannot be compared to it.
+    FloorCurrencyMismatch(String),
+    // An advertiser domain is blocked by the request.
+    BlockedAdvertiser(String),
+    // An advertiser domain, or the lack of one, is not on the advertiser
+    // whitelist of the deal.
+    AdvertiserNotAllowed(Option<String>),
+    // A category is blocked by the request, either itself or its parent.
+    BlockedCategory(Category),
+    // The bid refers to a deal the Imp does not have.
+    UnknownDeal(String),
+    // The Imp is a private auction and the bid is not for one of its deals.
+    DealRequired,
+    // A creative attribute is blocked by the Imp.
+    BlockedAttribute(u32),
+    // The creative size is none of the sizes the Imp allows.
+    SizeNotAllowed(u64, u64),
+    // The seat is not on the seat whitelist of the request or of the deal,
+    // or is on the seat blocklist of the request.
+    SeatNotAllowed(Option<String>),
+}
+
+impl RejectionReason {
+    /// The loss reason to notify the bidder of, e.g. for `${AUCTION_LOSS}`.
+    pub fn loss_reason(&self) -> LossReason {
+        match self {
+            RejectionReason::ResponseIdMismatch(_) => LossReason::InvalidAuctionId,
+            RejectionReason::CurrencyNotAllowed(_) | RejectionReason::UnknownImp(_) => LossReason::InvalidBidResponse,
+            RejectionReason::BelowBidFloor(_) => LossReason::BelowAuctionFloor,
+            RejectionReason::BelowDealFloor(_) => LossReason::BelowDealFloor,
+            RejectionReason::FloorCurrencyMismatch(_) => LossReason::InternalError,
+            RejectionReason::BlockedAdvertiser(_) | RejectionReason::AdvertiserNotAllowed(_) => {
+                LossReason::AdvertiserExclusions
+            }
+            RejectionReason::BlockedCategory(_) => LossReason::CategoryExclusions,
+            RejectionReason::UnknownDeal(_) | RejectionReason::DealRequired => LossReason::InvalidDealId,
+            RejectionReason::BlockedAttribute(_) => LossReason::CreativeAttributeExclusions,
+            RejectionReason::SizeNotAllowed(_, _) => LossReason::CreativeSizeNotAllowed,
+            RejectionReason::SeatNotAllowed(_) => LossReason::BuyerSeatBlocked,
+        }
+    }
+}
+
+impl Display for RejectionReason {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RejectionReason::ResponseIdMismatch(id) => write!(formatter, "response ID {:?} does not match", id),
+            RejectionReason::CurrencyNotAllowed(cur) => write!(formatter, "currency {} is not allowed", cur),
+            RejectionReason::UnknownImp(id) => write!(formatter, "imp {:?} does not exist", id),
+            RejectionReason::BelowBidFloor(floor) => write!(formatter, "price is below the bid floor {}", floor),
+            RejectionReason::BelowDealFloor(floor) => write!(formatter, "price is below the deal floor {}", floor),
+            RejectionReason::FloorCurrencyMismatch(cur) => write!(formatter, "floor is in another currency, {}", cur),
+            RejectionReason::BlockedAdvertiser(domain) => write!(formatter, "advertiser {} is blocked", domain),
+            RejectionReason::AdvertiserNotAllowed(domain) => write!(formatter, "advertiser {:?} is not allowed", domain),
+            RejectionReason::BlockedCategory(cat) => write!(formatter, "category {:?} is blocked", cat),
+            RejectionReason::UnknownDeal(id) => write!(formatter, "deal {:?} does not exist", id),
+            RejectionReason::DealRequired => formatter.write_str("private auction requires a deal"),
+            RejectionReason::BlockedAttribute(attr) => write!(formatter, "creative attribute {} is blocked", attr),
+            RejectionReason::SizeNotAllowed(w, h) => write!(formatter, "size {}x{} is not allowed", w, h),
+            RejectionReason::SeatNotAllowed(seat) => write!(formatter, "seat {:?} is not allowed", seat),
+        }
+    }
+}
+
+/// A reason to reject a response or one of its bids, and where.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rejection {
+    /// JSON path of the rejected value, e.g. `$.seatbid[0].bid[1]` for a
+    /// bid, or `$.id` when the whole response is rejected.
+    pub path: String,
+    pub reason: RejectionReason,
+}
+
+impl Display for Rejection {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{} at {}", self.reason, self.path)
+    }
+}
+
+impl BidResponse {
+    /// Checks the response against the request it answers, returning every
+    /// reason to reject it or its bids. Bids at paths without rejections
+    /// are valid unless the response itself is rejected.
+    pub fn rejections(&self, request: &BidRequest) -> Vec<Rejection> {
+        let mut rejections = vec![];
+
+        if self.id != request.id {
+            rejections.push(Rejection {
+                path: "$.id".to_string(),
+                reason: RejectionReason::ResponseIdMismatch(self.id.clone()),
+            });
+        }
+
+        let currency = self.currency.as_deref().unwrap_or(DEFAULT_CURRENCY);
+        if !request.currency.is_empty() && !request.currency.iter().any(|allowed| allowed == currency) {
+            rejections.push(Rejection {
+                path: "$.cur".to_string(),
+                reason: RejectionReason::CurrencyNotAllowed(currency.to_string()),
+            });
+        }
+
+        for (i, seat_bid) in self.seat_bid.iter().enumerate() {
+            for (j, bid) in seat_bid.bid.iter().enumerate() {
+                let path = format!("$.seatbid[{}].bid[{}]", i, j);
+                for reason in request.bid_rejections(seat_bid.seat.as_deref(), currency, bid) {
+                    rejections.push(Rejection {
+                        path: path.clone(),
+                        reason,
+                    });
+                }
+            }
+        }
+
+        rejections
+    }
+}
+
+impl BidRequest {
+    /// Checks a bid of the given seat, priced in the given currency, against
+    /// the restrictions of the request and of the Imp it is for.
+    ///
+    /// Prices are not converted, so a floor in another currency than the bid
+    /// is a reason to reject it.
+    pub fn bid_rejections(&self, seat: Option<&str>, currency: &str, bid: &Bid) -> Vec<RejectionReason> {
+        let mut reasons = vec![];
+
+        let imp = match self.imp.iter().find(|imp| imp.id == bid.imp_id) {
+            Some(imp) => imp,
+            None => return vec![RejectionReason::UnknownImp(bid.imp_id.clone())],
+        };
+
+        if !is_seat_whitelisted(&self.seat_whitelist, seat) || is_seat_listed(&self.seat_blocklist, seat) {
+            reasons.push(RejectionReason::SeatNotAllowed(seat.map(str::to_string)));
+        }
+
+        for domain in &bid.adomain {
+            if self.advertiser_blocklist.iter().any(|blocked| is_domain_within(domain, blocked)) {
+                reasons.push(RejectionReason::BlockedAdvertiser(domain.clone()));
+            }
+        }
+
+        for category in &bid.cat {
+            if self.category_blocklist.iter().any(|blocked| category.is_within(blocked)) {
+                reasons.push(RejectionReason::BlockedCategory(category.clone()));
+            }
+        }
+
+        let mut floor = (imp.bid_floor, imp.bid_floor_cur.as_deref(), false);
+        let pmp = imp.pmp.as_ref();
+        match bid.deal_id {
+            Some(ref deal_id) => match pmp.and_then(|pmp| pmp.deal(deal_id)) {
+                Some(deal) => {
+                    if !is_seat_whitelisted(&deal.seat_whitelist, seat) {
+                        reasons.push(RejectionReason::SeatNotAllowed(seat.map(str::to_string)));
+                    }
+                    let wadomain = &deal.advertiser_domain_whitelist;
+                    if !wadomain.is_empty() && bid.adomain.is_empty() {
+                        reasons.push(RejectionReason::AdvertiserNotAllowed(None));
+                    }
+                    for domain in &bid.adomain {
+                        if !wadomain.is_empty() && !wadomain.iter().any(|allowed| is_domain_within(domain, allowed)) {
+                            reasons.push(RejectionReason::AdvertiserNotAllowed(Some(domain.clone())));
+                        }
+                    }
+                    if deal.bid_floor.is_some() {
+                        floor = (deal.bid_floor, deal.bid_floor_cur.as_deref(), true);
+                    }
+                }
+                None => reasons.push(RejectionReason::UnknownDeal(deal_id.clone())),
+            },
+            None => {
+                if pmp.is_some_and(|pmp| pmp.private_auction) {
+                    reasons.push(RejectionReason::DealRequired);
+                }
+            }
+        }
+
+        if let (Some(bid_floor), floor_currency, is_deal_floor) = floor {
+            let floor_currency = floor_currency.unwrap_or(DEFAULT_CURRENCY);
+            if floor_currency != currency {
+                reasons.push(RejectionReason::FloorCurrencyMismatch(floor_currency.to_string()));
+            } else if bid.price < bid_floor as f64 {
+                reasons.push(if is_deal_floor {
+                    RejectionReason::BelowDealFloor(bid_floor as f64)
+                } else {
+                    RejectionReason::BelowBidFloor(bid_floor as f64)
+                });
+            }
+        }
+
+        let blocked_attributes = blocked_attributes(imp);
+        for &attr in &bid.attr {
+            if blocked_attributes.contains(&(attr as u64)) {
+                reasons.push(RejectionReason::BlockedAttribute(attr));
+            }
+        }
+
+        if let (Some(w), Some(h)) = (bid.w, bid.h) {
+            if !is_size_allowed(imp, w, h) {
+                reasons.push(RejectionReason::SizeNotAllowed(w, h));
+            }
+        }
+
+        reasons
+    }
+}
+
+fn is_seat_listed(seats: &[String], seat: Option<&str>) -> bool {
+    seat.is_some_and(|seat| seats.iter().any(|s| s == seat))
+}
+
+// An empty whitelist implies no seat restrictions.
+fn is_seat_whitelisted(whitelist: &[String], seat: Option<&str>) -> bool {
+    whitelist.is_empty() || is_seat_listed(whitelist, seat)
+}
+
+// Whether the domain is the blocked domain or one of its subdomains.
+fn is_domain_within(domain: &str, blocked: &str) -> bool {
+    let domain = domain.to_ascii_lowercase();
+    let blocked = blocked.to_ascii_lowercase();
+
+    domain == blocked || domain.ends_with(&format!(".{}", blocked))
+}
+
+// The bid does not say which of the media types of the Imp it is for, so the
+// attributes blocked by any of them apply.
+fn blocked_attributes(imp: &Imp) -> Vec<u64> {
+    let mut battr = vec![];
+    if let Some(ref banner) = imp.banner {
+        battr.extend(&banner.battr);
+    }
+    if let Some(ref video) = imp.video {
+        battr.extend(&video.battr);
+    }
+    if let Some(ref audio) = imp.audio {
+        battr.extend(&audio.battr);
+    }
+    if let Some(ref native) = imp.native {
+        battr.extend(&native.battr);
+    }
+    battr
+}
+
+// Sizes are only restricted by banner Imps which list some, either as
+// Formats or as w/h. A flexible Format allows any size of its ratio from
+// its minimum width.
+fn is_size_allowed(imp: &Imp, w: u64, h: u64) -> bool {
+    let banner = match imp.banner {
+        Some(ref banner) if imp.video.is_none() => banner,
+        _ => return true,
+    };
+
+    if banner.format.is_empty() {
+        return match (banner.w, banner.h) {
+            (Some(banner_w), Some(banner_h)) => banner_w as u64 == w && banner_h as u64 == h,
+            _ => true,
+        };
+    }
+
+    banner.format.iter().any(|format| match (format.w, format.h, format.w_ratio, format.h_ratio) {
+        (Some(format_w), Some(format_h), _, _) => format_w as u64 == w && format_h as u64 == h,
+        (_, _, Some(w_ratio), Some(h_ratio)) => {
+            w * h_ratio as u64 == h * w_ratio as u64 && format.w_min.is_none_or(|w_min| w >= w_min as u64)
+        }
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    fn request() -> BidRequest {
+        serde_json::from_str(
+            r#"{
+                "id": "req",
+                "imp": [
+                    {
+                        "id": "1",
+                        "banner": {"format": [{"w": 300, "h": 250}, {"wratio": 2, "hratio": 1, "w_min": 320}], "battr": [1, 3]},
+                        "bidfloor": 1.5
+                    },
+                    {
+                        "id": "2",
+                        "video": {"mimes": ["video/mp4"], "battr": [8]},
+                        "pmp": {"private_auction": 1, "deals": [{"id": "deal-1", "bidfloor": 5, "wseat": ["seat-a"], "wadomain": ["brand.com"]}]}
+                    }
+                ],
+                "cur": ["USD", "EUR"],
+                "bseat": ["seat-z"],
+                "badv": ["blocked.com"],
+                "bcat": ["IAB25", "IAB7-39"]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn response(json: &str) -> BidResponse {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn reasons(response: &BidResponse) -> Vec<(String, RejectionReason)> {
+        response
+            .rejections(&request())
+            .into_iter()
+            .map(|rejection| (rejection.path, rejection.reason))
+            .collect()
+    }
+
+    #[test]
+    fn valid_response() {
+        let r = response(
+            r#"{
+                "id": "req",
+                "seatbid": [
+                    {"seat": "seat-a", "bid": [
+                        {"id": "a", "impid": "1", "price": 1.5, "w": 300, "h": 250, "attr": [2], "adomain": ["brand.com"], "cat": ["IAB7-1"]},
+                        {"id": "b", "impid": "1", "price": 2, "w": 640, "h": 320},
+                        {"id": "c", "impid": "2", "price": 5, "dealid": "deal-1", "attr": [1], "adomain": ["shop.brand.com"]}
+                    ]}
+                ]
+            }"#,
+        );
+
+        assert_eq!(reasons(&r), vec![]);
+    }
+
+    #[test]
+    fn loss_reason() {
+        assert_eq!(RejectionReason::BelowBidFloor(1.5).loss_reason(), LossReason::BelowAuctionFloor);
+        assert_eq!(RejectionReason::BelowDealFloor(1.5).loss_reason(), LossReason::BelowDealFloor);
+        assert_eq!(RejectionReason::SizeNotAllowed(320, 50).loss_reason(), LossReason::CreativeSizeNotAllowed);
+        assert_eq!(u32::from(RejectionReason::SeatNotAllowed(None).loss_reason()), 104);
+    }
+
+    #[test]
+    fn reject_response() {
+        let r = response(r#"{"id":"other","cur":"GBP","seatbid":[{"bid":[{"id":"a","impid":"1","price":1.5}]}]}"#);
+
+        assert_eq!(
+            reasons(&r),
+            vec![
+                ("$.id".to_string(), RejectionReason::ResponseIdMismatch("other".to_string())),
+                ("$.cur".to_string(), RejectionReason::CurrencyNotAllowed("GBP".to_string())),
+                ("$.seatbid[0].bid[0]".to_string(), RejectionReason::FloorCurrencyMismatch("USD".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn reject_bids() {
+        let r = response(
+            r#"{
+                "id": "req",
+                "seatbid": [
+                    {"seat": "seat-z", "bid": [
+                        {"id": "a", "impid": "3", "price": 1},
+                        {"id": "b", "impid": "1", "price": 1, "w": 320, "h": 50, "attr": [3], "adomain": ["ads.Blocked.com"], "cat": ["IAB25-2", "IAB7-39"]}
+                    ]},
+                    {"seat": "seat-b", "bid": [
+                        {"id": "c", "impid": "2", "price": 4, "dealid": "deal-1", "attr": [8], "adomain": ["other.com"]},
+                        {"id": "d", "impid": "2", "price": 10, "dealid": "deal-2"},
+                        {"id": "e", "impid": "2", "price": 10}
+                    ]}
+                ]
+            }"#,
+        );
+
+        let blocked_subcategory: Category = serde_json::from_str(r#""IAB25-2""#).unwrap();
+        let blocked_category: Category = serde_json::from_str(r#""IAB7-39""#).unwrap();
+        assert_eq!(
+            reasons(&r),
+            vec![
+                ("$.seatbid[0].bid[0]".to_string(), RejectionReason::UnknownImp("3".to_string())),
+                ("$.seatbid[0].bid[1]".to_string(), RejectionReason::SeatNotAllowed(Some("seat-z".to_string()))),
+                ("$.seatbid[0].bid[1]".to_string(), RejectionReason::BlockedAdvertiser("ads.Blocked.com".to_string())),
+                ("$.seatbid[0].bid[1]".to_string(), RejectionReason::BlockedCategory(blocked_subcategory)),
+                ("$.seatbid[0].bid[1]".to_string(), RejectionReason::BlockedCategory(blocked_category)),
+                ("$.seatbid[0].bid[1]".to_string(), RejectionReason::BelowBidFloor(1.5)),
+                ("$.seatbid[0].bid[1]".to_string(), RejectionReason::BlockedAttribute(3)),
+                ("$.seatbid[0].bid[1]".to_string(), RejectionReason::SizeNotAllowed(320, 50)),
+                ("$.seatbid[1].bid[0]".to_string(), RejectionReason::SeatNotAllowed(Some("seat-b".to_string()))),
+                ("$.seatbid[1].bid[0]".to_string(), RejectionReason::AdvertiserNotAllowed(Some("other.com".to_string()))),
+                ("$.seatbid[1].bid[0]".to_string(), RejectionReason::BelowDealFloor(5.0)),
+                ("$.seatbid[1].bid[0]".to_string(), RejectionReason::BlockedAttribute(8)),
+                ("$.seatbid[1].bid[1]".to_string(), RejectionReason::UnknownDeal("deal-2".to_string())),
+                ("$.seatbid[1].bid[2]".to_string(), RejectionReason::DealRequired),
+            ]
+        );
+    }
+
+    #[test]
+    fn reject_floor_in_other_currency() {
+        let r = response(r#"{"id":"req","cur":"EUR","seatbid":[{"bid":[{"id":"a","impid":"1","price":1}]}]}"#);
+        assert_eq!(
+            reasons(&r),
+            vec![("$.seatbid[0].bid[0]".to_string(), RejectionReason::FloorCurrencyMismatch("USD".to_string()))]
+        );
+
+        let mut request = request();
+        request.seat_whitelist = vec!["seat-a".to_string()];
+        let bid = Bid::new("a".to_string(), "1".to_string(), 2.0);
+        assert_eq!(request.bid_rejections(Some("seat-a"), "USD", &bid), vec![]);
+        assert_eq!(request.bid_rejections(None, "USD", &bid), vec![RejectionReason::SeatNotAllowed(None)]);
+    }
+
+    #[test]
+    fn reject_advertiser_not_on_deal_whitelist() {
+        let request = request();
+        let mut bid = Bid::new("a".to_string(), "2".to_string(), 5.0);
+        bid.deal_id = Some("deal-1".to_string());
+
+        assert_eq!(
+            request.bid_rejections(Some("seat-a"), "USD", &bid),
+            vec![RejectionReason::AdvertiserNotAllowed(None)]
+        );
+
+        bid.adomain = vec!["brand.com".to_string(), "other.com".to_string()];
+        assert_eq!(
+            request.bid_rejections(Some("seat-a"), "USD", &bid),
+            vec![RejectionReason::AdvertiserNotAllowed(Some("other.com".to_string()))]
+        );
+    }
+}
@@ -0,0 +1,463 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use super::bid_request::BidRequest;
+use super::device::Device;
+use super::eid;
+use super::geo::Geo;
+use super::user::User;
+
+// Number of decimals Geo coordinates are rounded to, about 1 km.
+const GEO_DECIMALS: i32 = 2;
+
+// TCF purpose covering the storage of and access to information on a device,
+// without which no user or device identifier may be used.
+const TCF_PURPOSE_STORAGE: u16 = 1;
+
+// Why a request has to be scrubbed of personal data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrubReason {
+    // The request is subject to COPPA.
+    Coppa,
+    // GDPR applies and the consent string is missing, invalid or does not
+    // consent to storage and access of information on the device.
+    GdprWithoutConsent,
+    // The device has Limit Ad Tracking enabled.
+    LimitAdTracking,
+    // The browser sent a Do Not Track header.
+    DoNotTrack,
+    // The US Privacy or GPP string opts the user out of sale or targeted
+    // advertising.
+    UsPrivacyOptOut,
+}
+
+// A field that was removed or generalized by a scrub.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrubbedField {
+    // The last octet of Device.ip was zeroed, or the address was removed
+    // if it could not be parsed.
+    DeviceIp,
+    // Device.ipv6 was truncated to its first 64 bits, or removed if it
+    // could not be parsed.
+    DeviceIpv6,
+    // Device.geo lat and lon were rounded.
+    DeviceGeo,
+    DeviceIfa,
+    // The hashed device and platform IDs and MAC addresses.
+    DeviceHashedIds,
+    UserId,
+    UserBuyerUid,
+    // The bidder data set in the exchange's cookie.
+    UserCustomdata,
+    UserYob,
+    UserGender,
+    UserKeywords,
+    UserData,
+    // User.geo lat and lon were rounded.
+    UserGeo,
+    UserEids,
+}
+
+impl ScrubReason {
+    // Whether the IP addresses have to be truncated. They identify the user
+    // to anyone they may not be shared with.
+    fn truncates_ip(self) -> bool {
+        matches!(self, ScrubReason::Coppa | ScrubReason::GdprWithoutConsent | ScrubReason::UsPrivacyOptOut)
+    }
+
+    // Whether the location and profile of the user (geo, year of birth,
+    // gender, keywords and data segments) have to go too, as they do for
+    // children and for GDPR personal data without a legal basis. The other
+    // reasons only restrict tracking, for which removing the identifiers is
+    // enough.
+    fn removes_profile(self) -> bool {
+        matches!(self, ScrubReason::Coppa | ScrubReason::GdprWithoutConsent)
+    }
+}
+
+// What a scrub did to a request. Both lists are empty when the request did
+// not need scrubbing.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ScrubReport {
+    pub reasons: Vec<ScrubReason>,
+    pub fields: Vec<ScrubbedField>,
+}
+
+impl ScrubReport {
+    pub fn is_scrubbed(&self) -> bool {
+        !self.fields.is_empty()
+    }
+}
+
+impl BidRequest {
+    /// Returns the reasons the regulations and device signals of the request
+    /// give for scrubbing it of personal data.
+    pub fn scrub_reasons(&self) -> Vec<ScrubReason> {
+        let mut reasons = vec![];
+
+        if let Some(ref regulations) = self.regulations {
+            if regulations.coppa {
+                reasons.push(ScrubReason::Coppa);
+            }
+            if regulations.gdpr_applies() == Some(true) && !self.has_storage_consent() {
+                reasons.push(ScrubReason::GdprWithoutConsent);
+            }
+        }
+
+        if let Some(ref device) = self.device {
+            if device.lmt == Some(true) {
+                reasons.push(ScrubReason::LimitAdTracking);
+            }
+            if device.dnt == Some(true) {
+                reasons.push(ScrubReason::DoNotTrack);
+            }
+        }
+
+        if let Some(ref regulations) = self.regulations {
+            if regulations.opted_out_of_sale_or_targeted_advertising() {
+                reasons.push(ScrubReason::UsPrivacyOptOut);
+            }
+        }
+
+        reasons
+    }
+
+    /// Removes or generalizes the personal data in the request as its scrub
+    /// reasons demand. Any reason removes the device and user identifiers.
+    /// COPPA, GDPR without consent and a US Privacy opt-out also truncate
+    /// the IP addresses, and COPPA and GDPR without consent round the geo
+    /// coordinates and remove the user's year of birth, gender, keywords
+    /// and data segments.
+    pub fn scrub_personal_data(&mut self) -> ScrubReport {
+        let reasons = self.scrub_reasons();
+        if reasons.is_empty() {
+            return ScrubReport::default();
+        }
+
+        let scrub = Scrub {
+            truncate_ip: reasons.iter().any(|reason| reason.truncates_ip()),
+            remove_profile: reasons.iter().any(|reason| reason.removes_profile()),
+        };
+        let mut fields = vec![];
+        if let Some(ref mut device) = self.device {
+            scrub_device(device, scrub, &mut fields);
+        }
+        if let Some(ref mut user) = self.user {
+            scrub_user(user, scrub, &mut fields);
+        }
+
+        ScrubReport { reasons, fields }
+    }
+
+    fn has_storage_consent(&self) -> bool {
+        match self.user.as_ref().and_then(User::tcf_consent) {
+            Some(Ok(tc_string)) => tc_string.purpose_consent(TCF_PURPOSE_STORAGE),
+            _ => false,
+        }
+    }
+}
+
+// What a scrub does beyond removing identifiers, for all its reasons together.
+#[derive(Debug, Clone, Copy)]
+struct Scrub {
+    truncate_ip: bool,
+    remove_profile: bool,
+}
+
+fn scrub_device(device: &mut Device, scrub: Scrub, fields: &mut Vec<ScrubbedField>) {
+    if scrub.truncate_ip {
+        truncate_ips(device, fields);
+    }
+
+    if let Some(ref mut geo) = device.geo {
+        if scrub.remove_profile && round_coordinates(geo) {
+            fields.push(ScrubbedField::DeviceGeo);
+        }
+    }
+
+    if device.ifa.take().is_some() {
+        fields.push(ScrubbedField::DeviceIfa);
+    }
+
+    let hashed_ids = [
+        device.didsha1.take(),
+        device.didmd5.take(),
+        device.dpidsha1.take(),
+        device.dpidmd5.take(),
+        device.macsha1.take(),
+        device.macmd5.take(),
+    ];
+    if hashed_ids.iter().any(Option::is_some) {
+        fields.push(ScrubbedField::DeviceHashedIds);
+    }
+}
+
+fn truncate_ips(device: &mut Device, fields: &mut Vec<ScrubbedField>) {
+    if let Some(ip) = device.ip.take() {
+        device.ip = ip.parse::<Ipv4Addr>().ok().map(|ip| {
+            let [a, b, c, _] = ip.octets();
+            Ipv4Addr::new(a, b, c, 0).to_string()
+        });
+        fields.push(ScrubbedField::DeviceIp);
+    }
+
+    if let Some(ipv6) = device.ipv6.take() {
+        device.ipv6 = ipv6
+            .parse::<Ipv6Addr>()
+            .ok()
+            .map(|ipv6| Ipv6Addr::from(u128::from(ipv6) & u128::MAX << 64).to_string());
+        fields.push(ScrubbedField::DeviceIpv6);
+    }
+}
+
+fn round_coordinates(geo: &mut Geo) -> bool {
+    let factor = 10f64.powi(GEO_DECIMALS);
+    let mut rounded = false;
+    for coordinate in geo.lat.iter_mut().chain(geo.lon.iter_mut()) {
+        *coordinate = (*coordinate * factor).round() / factor;
+        rounded = true;
+    }
+
+    rounded
+}
+
+fn scrub_user(user: &mut User, scrub: Scrub, fields: &mut Vec<ScrubbedField>) {
+    if user.id.take().is_some() {
+        fields.push(ScrubbedField::UserId);
+    }
+
+    if user.buyer_uid.take().is_some() {
+        fields.push(ScrubbedField::UserBuyerUid);
+    }
+
+    if user.customdata.take().is_some() {
+        fields.push(ScrubbedField::UserCustomdata);
+    }
+
+    if scrub.remove_profile {
+        if user.yob.take().is_some() {
+            fields.push(ScrubbedField::UserYob);
+        }
+
+        if user.gender.take().is_some() {
+            fields.push(ScrubbedField::UserGender);
+        }
+
+        if user.keywords.take().is_some() {
+            fields.push(ScrubbedField::UserKeywords);
+        }
+
+        if user.data.take().is_some() {
+            fields.push(ScrubbedField::UserData);
+        }
+
+        if let Some(ref mut geo) = user.geo {
+            if round_coordinates(geo) {
+                fields.push(ScrubbedField::UserGeo);
+            }
+        }
+    }
+
+    if eid::remove_ext_eids(&mut user.ext) {
+        fields.push(ScrubbedField::UserEids);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    fn request(json: &str) -> BidRequest {
+        serde_json::from_str(json).unwrap()
+    }
+
+    const DEVICE_AND_USER: &str = r#"
+        "device": {
+            "ip": "192.168.1.42",
+            "ipv6": "2001:db8:85a3:8d3:1319:8a2e:370:7348",
+            "geo": {"lat": 48.85837, "lon": 2.294481, "country": "FRA"},
+            "ifa": "AA000000-0000-0000-0000-000000000001",
+            "dpidsha1": "0f8aa3c3b5f9cb3b8e0a5c4b1c5f9cbb3c3b5f9c"
+        },
+        "user": {
+            "id": "user-1",
+            "buyeruid": "buyer-1",
+            "customdata": "cookie-1",
+            "yob": 1984,
+            "gender": "F",
+            "keywords": "cars,travel",
+            "geo": {"lat": 48.8566, "lon": 2.3522},
+            "data": [{"id": "1", "segment": [{"id": "2"}]}],
+            "ext": {"eids": [{"source": "id5-sync.com", "uids": [{"id": "ID5*xxx"}]}]}
+        }"#;
+
+    // The fields every scrub reason removes.
+    const IDENTIFIERS: [ScrubbedField; 6] = [
+        ScrubbedField::DeviceIfa,
+        ScrubbedField::DeviceHashedIds,
+        ScrubbedField::UserId,
+        ScrubbedField::UserBuyerUid,
+        ScrubbedField::UserCustomdata,
+        ScrubbedField::UserEids,
+    ];
+
+    // Checks that only the identifiers of DEVICE_AND_USER were removed.
+    fn assert_profile_kept(r: &BidRequest) {
+        let device = r.device.as_ref().unwrap();
+        assert_eq!(device.geo.as_ref().unwrap().lat, Some(48.85837));
+        let user = r.user.as_ref().unwrap();
+        assert_eq!(user.yob, Some(1984));
+        assert_eq!(user.keywords, Some("cars,travel".to_string()));
+        assert!(user.data.is_some());
+        assert_eq!(user.geo.as_ref().unwrap().lat, Some(48.8566));
+        assert_eq!(user.id, None);
+        assert_eq!(user.ext, None);
+    }
+
+    #[test]
+    fn scrub_coppa() {
+        let mut r = request(&format!(r#"{{"id":"1","imp":[],"regs":{{"coppa":1}},{}}}"#, DEVICE_AND_USER));
+        let report = r.scrub_personal_data();
+
+        assert_eq!(report.reasons, vec![ScrubReason::Coppa]);
+        assert_eq!(
+            report.fields,
+            vec![
+                ScrubbedField::DeviceIp,
+                ScrubbedField::DeviceIpv6,
+                ScrubbedField::DeviceGeo,
+                ScrubbedField::DeviceIfa,
+                ScrubbedField::DeviceHashedIds,
+                ScrubbedField::UserId,
+                ScrubbedField::UserBuyerUid,
+                ScrubbedField::UserCustomdata,
+                ScrubbedField::UserYob,
+                ScrubbedField::UserGender,
+                ScrubbedField::UserKeywords,
+                ScrubbedField::UserData,
+                ScrubbedField::UserGeo,
+                ScrubbedField::UserEids,
+            ]
+        );
+
+        let device = r.device.unwrap();
+        assert_eq!(device.ip, Some("192.168.1.0".to_string()));
+        assert_eq!(device.ipv6, Some("2001:db8:85a3:8d3::".to_string()));
+        let geo = device.geo.unwrap();
+        assert_eq!(geo.lat, Some(48.86));
+        assert_eq!(geo.lon, Some(2.29));
+        assert_eq!(geo.country, Some("FRA".to_string()));
+        assert_eq!(device.ifa, None);
+        assert_eq!(device.dpidsha1, None);
+        let user = r.user.unwrap();
+        let geo = user.geo.as_ref().unwrap();
+        assert_eq!(geo.lat, Some(48.86));
+        assert_eq!(geo.lon, Some(2.35));
+        assert_eq!(user.id, None);
+        assert_eq!(user.customdata, None);
+        assert_eq!(user.yob, None);
+        assert_eq!(user.gender, None);
+        assert_eq!(user.keywords, None);
+        assert_eq!(user.ext, None);
+    }
+
+    #[test]
+    fn scrub_gdpr_without_consent() {
+        let mut r = request(&format!(r#"{{"id":"1","imp":[],"regs":{{"ext":{{"gdpr":1}}}},{}}}"#, DEVICE_AND_USER));
+        assert_eq!(r.scrub_reasons(), vec![ScrubReason::GdprWithoutConsent]);
+
+        // Purpose 1 is consented to.
+        r.user.as_mut().unwrap().ext.as_mut().unwrap().insert(
+            "consent".to_string(),
+            "COvFyGBOvFyGBAbAAAENAPCAAOAAAAAAAAAAAEEUACCKAAA".into(),
+        );
+        assert!(r.scrub_reasons().is_empty());
+        assert!(!r.scrub_personal_data().is_scrubbed());
+        assert_eq!(r.user.unwrap().id, Some("user-1".to_string()));
+    }
+
+    #[test]
+    fn scrub_gdpr_without_consent_removes_profile() {
+        let mut r = request(&format!(r#"{{"id":"1","imp":[],"regs":{{"ext":{{"gdpr":1}}}},{}}}"#, DEVICE_AND_USER));
+        let report = r.scrub_personal_data();
+
+        assert_eq!(report.reasons, vec![ScrubReason::GdprWithoutConsent]);
+        assert!(report.fields.contains(&ScrubbedField::DeviceIp));
+        assert!(report.fields.contains(&ScrubbedField::DeviceGeo));
+        assert!(report.fields.contains(&ScrubbedField::UserData));
+        let user = r.user.unwrap();
+        assert_eq!(user.yob, None);
+        assert_eq!(user.gender, None);
+        assert_eq!(user.keywords, None);
+        assert_eq!(user.geo.unwrap().lat, Some(48.86));
+    }
+
+    #[test]
+    fn scrub_limit_ad_tracking() {
+        let mut r = request(&format!(r#"{{"id":"1","imp":[],{}}}"#, DEVICE_AND_USER));
+        r.device.as_mut().unwrap().lmt = Some(true);
+        let report = r.scrub_personal_data();
+
+        assert_eq!(report.reasons, vec![ScrubReason::LimitAdTracking]);
+        assert_eq!(report.fields, IDENTIFIERS.to_vec());
+        assert_eq!(r.device.as_ref().unwrap().ip, Some("192.168.1.42".to_string()));
+        assert_profile_kept(&r);
+    }
+
+    #[test]
+    fn scrub_do_not_track() {
+        let mut r = request(&format!(r#"{{"id":"1","imp":[],{}}}"#, DEVICE_AND_USER));
+        r.device.as_mut().unwrap().dnt = Some(true);
+        let report = r.scrub_personal_data();
+
+        assert_eq!(report.reasons, vec![ScrubReason::DoNotTrack]);
+        assert_eq!(report.fields, IDENTIFIERS.to_vec());
+        assert_eq!(r.device.as_ref().unwrap().ipv6, Some("2001:db8:85a3:8d3:1319:8a2e:370:7348".to_string()));
+        assert_profile_kept(&r);
+    }
+
+    #[test]
+    fn scrub_device_signals() {
+        let r = request(r#"{"id":"1","imp":[],"device":{"lmt":true,"dnt":1}}"#);
+        assert_eq!(r.scrub_reasons(), vec![ScrubReason::LimitAdTracking, ScrubReason::DoNotTrack]);
+
+        let r = request(r#"{"id":"1","imp":[],"device":{"lmt":false,"dnt":0}}"#);
+        assert!(r.scrub_reasons().is_empty());
+    }
+
+    #[test]
+    fn scrub_us_privacy_opt_out() {
+        let mut r = request(r#"{"id":"1","imp":[],"regs":{"ext":{"us_privacy":"1YYN"}},"device":{"ip":"not an ip"},"user":{"ext":{"eids":[],"consent":"x"}}}"#);
+        let report = r.scrub_personal_data();
+
+        assert_eq!(report.reasons, vec![ScrubReason::UsPrivacyOptOut]);
+        assert_eq!(report.fields, vec![ScrubbedField::DeviceIp, ScrubbedField::UserEids]);
+        assert_eq!(r.device.unwrap().ip, None);
+        assert_eq!(r.user.unwrap().consent_string(), Some("x"));
+
+        let mut r = request(&format!(r#"{{"id":"1","imp":[],"regs":{{"us_privacy":"1YYN"}},{}}}"#, DEVICE_AND_USER));
+        let mut expected = vec![ScrubbedField::DeviceIp, ScrubbedField::DeviceIpv6];
+        expected.extend(IDENTIFIERS);
+
+        assert_eq!(r.scrub_personal_data().fields, expected);
+        assert_eq!(r.device.as_ref().unwrap().ip, Some("192.168.1.0".to_string()));
+        assert_profile_kept(&r);
+    }
+
+    #[test]
+    fn scrub_nothing() {
+        let mut r = request(&format!(r#"{{"id":"1","imp":[],"regs":{{"us_privacy":"1YNN"}},{}}}"#, DEVICE_AND_USER));
+        let expected = r.clone();
+
+        assert_eq!(r.scrub_personal_data(), ScrubReport::default());
+        assert_eq!(r, expected);
+    }
+}
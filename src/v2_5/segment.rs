@@ -11,4 +11,10 @@ pub struct Segment {
     // The ID that is associated with this data segment and is specific to the data provider.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
+    // Name of the data segment specific to the data provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    // String representation of the data segment value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
 }
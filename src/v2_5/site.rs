@@ -140,18 +140,20 @@ mod tests {
 
     #[test]
     fn serialize_with_publisher_and_content() {
-        let mut site = Site::default();
-        site.id = Some("site123".to_string());
-        site.publisher = Some(Publisher {
-            id: "pub123".to_string(),
-            name: Some("Example Publisher".to_string()),
-            ..Default::default()
-        });
-        site.content = Some(Content {
-            id: Some("content123".to_string()),
-            title: Some("Example Content".to_string()),
+        let site = Site {
+            id: Some("site123".to_string()),
+            publisher: Some(Publisher {
+                id: "pub123".to_string(),
+                name: Some("Example Publisher".to_string()),
+                ..Default::default()
+            }),
+            content: Some(Content {
+                id: Some("content123".to_string()),
+                title: Some("Example Content".to_string()),
+                ..Default::default()
+            }),
             ..Default::default()
-        });
+        };
 
         let serialized = serde_json::to_string(&site).unwrap();
         let deserialized: Site = serde_json::from_str(&serialized).unwrap();
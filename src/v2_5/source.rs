@@ -6,7 +6,9 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use crate::serde_utils;
+use crate::serde_utils::{self, EXT_SCHAIN};
+
+use super::supply_chain::SupplyChain;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
 pub struct Source {
@@ -19,21 +21,82 @@ pub struct Source {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pchain: Option<String>,
 
+    // The supply chain as sent by OpenRTB 2.6 requests. OpenRTB 2.5
+    // requests carry it in ext.schain instead; use supply_chain() to
+    // read it from either location.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schain: Option<SupplyChain>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ext: Option<serde_utils::Ext>,
 }
 
+impl Source {
+    /// Returns the supply chain from `schain`, or failing that from `ext.schain`.
+    /// A malformed `ext.schain` is treated as absent.
+    pub fn supply_chain(&self) -> Option<SupplyChain> {
+        if self.schain.is_some() {
+            return self.schain.clone();
+        }
+
+        self.ext
+            .as_ref()
+            .and_then(|ext| ext.get(EXT_SCHAIN))
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+
+    /// Stores the supply chain in `schain` (OpenRTB 2.6 style), removing any `ext.schain`.
+    pub fn set_supply_chain(&mut self, schain: SupplyChain) {
+        serde_utils::remove_ext(&mut self.ext, EXT_SCHAIN);
+        self.schain = Some(schain);
+    }
+
+    /// Stores the supply chain in `ext.schain` (OpenRTB 2.5 style), removing any `schain`.
+    pub fn set_ext_supply_chain(&mut self, schain: SupplyChain) {
+        self.schain = None;
+        serde_utils::put_ext(&mut self.ext, EXT_SCHAIN, schain);
+    }
+
+    /// Removes the supply chain from both locations, returning it.
+    pub fn take_supply_chain(&mut self) -> Option<SupplyChain> {
+        let schain = self.supply_chain();
+        self.schain = None;
+        serde_utils::remove_ext(&mut self.ext, EXT_SCHAIN);
+        schain
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde_json;
 
+    use super::super::supply_chain::SupplyChainNode;
+
+    fn supply_chain() -> SupplyChain {
+        SupplyChain {
+            complete: true,
+            nodes: vec![SupplyChainNode {
+                asi: "exchange1.com".to_string(),
+                sid: "1234".to_string(),
+                rid: None,
+                name: None,
+                domain: None,
+                hp: true,
+                ext: None,
+            }],
+            ver: "1.0".to_string(),
+            ext: None,
+        }
+    }
+
     #[test]
     fn serialization_skip_fields() {
         let s = Source {
             fd: None,
             tid: None,
             pchain: None,
+            schain: None,
             ext: None,
         };
 
@@ -42,4 +105,44 @@ mod tests {
 
         assert_eq!(expected, serialized)
     }
+
+    #[test]
+    fn read_schain() {
+        let s: Source = serde_json::from_str(
+            r#"{"schain":{"complete":1,"nodes":[{"asi":"exchange1.com","sid":"1234","hp":1}],"ver":"1.0"}}"#,
+        )
+        .unwrap();
+        assert_eq!(s.supply_chain(), Some(supply_chain()));
+
+        let s: Source = serde_json::from_str(
+            r#"{"ext":{"schain":{"complete":1,"nodes":[{"asi":"exchange1.com","sid":"1234","hp":1}],"ver":"1.0"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(s.schain, None);
+        assert_eq!(s.supply_chain(), Some(supply_chain()));
+
+        let s: Source = serde_json::from_str(r#"{"ext":{"schain":"invalid"}}"#).unwrap();
+        assert_eq!(s.supply_chain(), None);
+    }
+
+    #[test]
+    fn write_schain() {
+        let mut s = Source::default();
+
+        s.set_ext_supply_chain(supply_chain());
+        assert_eq!(s.schain, None);
+        assert_eq!(
+            serde_json::to_value(&s).unwrap(),
+            serde_json::json!({"ext":{"schain":{"complete":1,"nodes":[{"asi":"exchange1.com","sid":"1234","hp":1}],"ver":"1.0"}}})
+        );
+
+        s.set_supply_chain(supply_chain());
+        assert_eq!(
+            serde_json::to_string(&s).unwrap(),
+            r#"{"schain":{"complete":1,"nodes":[{"asi":"exchange1.com","sid":"1234","hp":1}],"ver":"1.0"}}"#
+        );
+
+        assert_eq!(s.take_supply_chain(), Some(supply_chain()));
+        assert_eq!(s, Source::default());
+    }
 }
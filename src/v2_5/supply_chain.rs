@@ -6,8 +6,18 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use std::error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
 use crate::serde_utils;
 
+// Separators of the compact string form of a supply chain (e.g. the value of
+// a `schain` query string parameter or VAST macro):
+// `ver,complete!asi,sid,hp,rid,name,domain,ext!...`.
+const NODE_SEPARATOR: char = '!';
+const FIELD_SEPARATOR: char = ',';
+
 // This object represents both the links in the supply chain as well
 // as an indicator whether or not the supply chain is complete.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -105,24 +115,314 @@ pub struct SupplyChainNode {
     pub ext: Option<serde_utils::Ext>,
 }
 
-#[test]
-fn serialization_skip_fields() {
-    let s = SupplyChain {
-        complete: true,
-        nodes: vec![SupplyChainNode {
-            asi: "directseller.com".to_string(),
-            sid: "00001".to_string(),
-            domain: None,
+impl SupplyChain {
+    pub fn new(ver: String) -> SupplyChain {
+        SupplyChain {
+            complete: false,
+            nodes: vec![],
+            ver,
+            ext: None,
+        }
+    }
+
+    /// Appends a node, as an intermediary does for itself before
+    /// forwarding the request.
+    pub fn append_node(&mut self, node: SupplyChainNode) {
+        self.nodes.push(node);
+    }
+
+    /// Whether the chain claims to be complete and actually is: it has at
+    /// least one node and every node identifies its system and seller.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+            && !self.nodes.is_empty()
+            && self.nodes.iter().all(|node| !node.asi.is_empty() && !node.sid.is_empty())
+    }
+
+    /// The owner of the inventory, i.e. the first node of a complete chain.
+    /// An incomplete chain does not know its originating seller.
+    pub fn originating_seller(&self) -> Option<&SupplyChainNode> {
+        if self.is_complete() {
+            self.nodes.first()
+        } else {
+            None
+        }
+    }
+}
+
+impl SupplyChainNode {
+    pub fn new(asi: String, sid: String) -> SupplyChainNode {
+        SupplyChainNode {
+            asi,
+            sid,
+            rid: None,
             name: None,
-            rid: Some("BidRequest1".to_string()),
+            domain: None,
             hp: true,
             ext: None,
-        }],
-        ver: "1.0".to_string(),
-        ext: None,
-    };
-    let expected = r#"{"complete":1,"nodes":[{"asi":"directseller.com","sid":"00001","rid":"BidRequest1","hp":1}],"ver":"1.0"}"#;
-    let serialized = serde_json::to_string(&s).unwrap();
-
-    assert_eq!(expected, serialized)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseSupplyChainError {
+    // The version and complete flag are missing.
+    MissingHeader,
+    // A node does not contain at least asi, sid and hp.
+    MissingNodeFields(String),
+    // A 0/1 flag has another value.
+    InvalidFlag(String),
+    // A value contains an invalid percent-encoded sequence.
+    InvalidEncoding(String),
+    // A node ext is not a JSON object.
+    InvalidExt(String),
+}
+
+impl Display for ParseSupplyChainError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseSupplyChainError::MissingHeader => formatter.write_str("supply chain has no version and complete flag"),
+            ParseSupplyChainError::MissingNodeFields(node) => write!(formatter, "supply chain node is missing fields: {}", node),
+            ParseSupplyChainError::InvalidFlag(flag) => write!(formatter, "supply chain flag must be 0 or 1: {}", flag),
+            ParseSupplyChainError::InvalidEncoding(value) => write!(formatter, "invalid percent-encoding: {}", value),
+            ParseSupplyChainError::InvalidExt(ext) => write!(formatter, "supply chain node ext is not a JSON object: {}", ext),
+        }
+    }
+}
+
+impl error::Error for ParseSupplyChainError {}
+
+// Parses the compact string form, e.g. `1.0,1!exchange1.com,1234,1,,,`.
+impl FromStr for SupplyChain {
+    type Err = ParseSupplyChainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(NODE_SEPARATOR);
+
+        let header: Vec<&str> = parts.next().unwrap_or_default().split(FIELD_SEPARATOR).collect();
+        if header.len() != 2 || header[0].is_empty() {
+            return Err(ParseSupplyChainError::MissingHeader);
+        }
+
+        let mut schain = SupplyChain::new(percent_decode(header[0])?);
+        schain.complete = parse_flag(header[1])?;
+
+        for node in parts {
+            let fields: Vec<&str> = node.split(FIELD_SEPARATOR).collect();
+            if fields.len() < 3 {
+                return Err(ParseSupplyChainError::MissingNodeFields(node.to_string()));
+            }
+
+            let field = |i: usize| -> Result<Option<String>, ParseSupplyChainError> {
+                match fields.get(i) {
+                    Some(value) if !value.is_empty() => Ok(Some(percent_decode(value)?)),
+                    _ => Ok(None),
+                }
+            };
+
+            let ext = match field(6)? {
+                Some(ext) => match serde_json::from_str(&ext) {
+                    Ok(ext) => Some(ext),
+                    Err(_) => return Err(ParseSupplyChainError::InvalidExt(ext)),
+                },
+                None => None,
+            };
+
+            schain.append_node(SupplyChainNode {
+                asi: field(0)?.unwrap_or_default(),
+                sid: field(1)?.unwrap_or_default(),
+                hp: parse_flag(fields[2])?,
+                rid: field(3)?,
+                name: field(4)?,
+                domain: field(5)?,
+                ext,
+            });
+        }
+
+        Ok(schain)
+    }
+}
+
+// Writes the compact string form, percent-encoding every value.
+impl Display for SupplyChain {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{},{}", percent_encode(&self.ver), self.complete as u8)?;
+
+        for node in &self.nodes {
+            write!(
+                formatter,
+                "!{},{},{},{},{},{}",
+                percent_encode(&node.asi),
+                percent_encode(&node.sid),
+                node.hp as u8,
+                percent_encode(node.rid.as_deref().unwrap_or_default()),
+                percent_encode(node.name.as_deref().unwrap_or_default()),
+                percent_encode(node.domain.as_deref().unwrap_or_default()),
+            )?;
+
+            if let Some(ref ext) = node.ext {
+                let ext = serde_json::to_string(ext).map_err(|_| fmt::Error)?;
+                write!(formatter, ",{}", percent_encode(&ext))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_flag(flag: &str) -> Result<bool, ParseSupplyChainError> {
+    match flag {
+        "0" => Ok(false),
+        "1" => Ok(true),
+        _ => Err(ParseSupplyChainError::InvalidFlag(flag.to_string())),
+    }
+}
+
+// Escapes everything but the RFC 3986 unreserved characters, so that the
+// separators can never appear in a value.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn percent_decode(value: &str) -> Result<String, ParseSupplyChainError> {
+    let invalid = || ParseSupplyChainError::InvalidEncoding(value.to_string());
+
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut iter = value.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [iter.next().ok_or_else(invalid)?, iter.next().ok_or_else(invalid)?];
+            // from_str_radix would also accept a sign, as in “%+F”.
+            if !hex.iter().all(u8::is_ascii_hexdigit) {
+                return Err(invalid());
+            }
+            let hex = std::str::from_utf8(&hex).map_err(|_| invalid())?;
+            bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn serialization_skip_fields() {
+        let s = SupplyChain {
+            complete: true,
+            nodes: vec![SupplyChainNode {
+                asi: "directseller.com".to_string(),
+                sid: "00001".to_string(),
+                domain: None,
+                name: None,
+                rid: Some("BidRequest1".to_string()),
+                hp: true,
+                ext: None,
+            }],
+            ver: "1.0".to_string(),
+            ext: None,
+        };
+        let expected = r#"{"complete":1,"nodes":[{"asi":"directseller.com","sid":"00001","rid":"BidRequest1","hp":1}],"ver":"1.0"}"#;
+        let serialized = serde_json::to_string(&s).unwrap();
+
+        assert_eq!(expected, serialized)
+    }
+
+    #[test]
+    fn append_node() {
+        let mut s: SupplyChain = "1.0,1!exchange1.com,1234,1,,,".parse().unwrap();
+        s.append_node(SupplyChainNode::new("exchange2.com".to_string(), "abcd".to_string()));
+
+        assert_eq!(s.nodes.len(), 2);
+        assert!(s.is_complete());
+        assert_eq!(s.originating_seller().unwrap().asi, "exchange1.com");
+        assert_eq!(s.to_string(), "1.0,1!exchange1.com,1234,1,,,!exchange2.com,abcd,1,,,");
+    }
+
+    #[test]
+    fn incomplete_chain() {
+        let s: SupplyChain = "1.0,0!exchange1.com,1234,1,,,".parse().unwrap();
+        assert!(!s.is_complete());
+        assert_eq!(s.originating_seller(), None);
+
+        let s: SupplyChain = "1.0,1".parse().unwrap();
+        assert!(!s.is_complete());
+
+        let s: SupplyChain = "1.0,1!,1234,1,,,".parse().unwrap();
+        assert!(!s.is_complete());
+    }
+
+    #[test]
+    fn parse_string() {
+        let s: SupplyChain =
+            "1.0,1!exchange1.com,1234%21abcd,1,bid-request-1,publisher%2C%20Inc.,publisher.com!exchange2.com,abcd,0,,,,%7B%22k%22%3A1%7D"
+                .parse()
+                .unwrap();
+
+        assert_eq!(s.ver, "1.0");
+        assert!(s.complete);
+        assert_eq!(s.nodes[0].sid, "1234!abcd");
+        assert!(s.nodes[0].hp);
+        assert_eq!(s.nodes[0].rid, Some("bid-request-1".to_string()));
+        assert_eq!(s.nodes[0].name, Some("publisher, Inc.".to_string()));
+        assert_eq!(s.nodes[0].domain, Some("publisher.com".to_string()));
+        assert!(!s.nodes[1].hp);
+        assert_eq!(s.nodes[1].name, None);
+        assert_eq!(s.nodes[1].ext.as_ref().unwrap()["k"], 1);
+    }
+
+    #[test]
+    fn write_string() {
+        let mut s = SupplyChain::new("1.0".to_string());
+        s.complete = true;
+        let mut node = SupplyChainNode::new("exchange1.com".to_string(), "1234!abcd".to_string());
+        node.name = Some("publisher, Inc.".to_string());
+        node.ext = Some(serde_json::from_str(r#"{"k":1}"#).unwrap());
+        s.append_node(node);
+
+        let written = s.to_string();
+        assert_eq!(
+            written,
+            "1.0,1!exchange1.com,1234%21abcd,1,,publisher%2C%20Inc.,,%7B%22k%22%3A1%7D"
+        );
+        assert_eq!(written.parse::<SupplyChain>().unwrap(), s);
+    }
+
+    #[test]
+    fn parse_invalid_string() {
+        assert_eq!("".parse::<SupplyChain>(), Err(ParseSupplyChainError::MissingHeader));
+        assert_eq!("1.0".parse::<SupplyChain>(), Err(ParseSupplyChainError::MissingHeader));
+        assert_eq!(
+            "1.0,2".parse::<SupplyChain>(),
+            Err(ParseSupplyChainError::InvalidFlag("2".to_string()))
+        );
+        assert_eq!(
+            "1.0,1!exchange1.com,1234".parse::<SupplyChain>(),
+            Err(ParseSupplyChainError::MissingNodeFields("exchange1.com,1234".to_string()))
+        );
+        assert_eq!(
+            "1.0,1!exchange1.com,12%G4,1".parse::<SupplyChain>(),
+            Err(ParseSupplyChainError::InvalidEncoding("12%G4".to_string()))
+        );
+        assert_eq!(
+            "1.0,1!exchange1.com,12%+F,1".parse::<SupplyChain>(),
+            Err(ParseSupplyChainError::InvalidEncoding("12%+F".to_string()))
+        );
+        assert_eq!(
+            "1.0,1!exchange1.com,1234,1,,,,%5B%5D".parse::<SupplyChain>(),
+            Err(ParseSupplyChainError::InvalidExt("[]".to_string()))
+        );
+    }
 }
@@ -7,20 +7,137 @@
 // except according to those terms.
 
 use super::data::Data;
-use crate::serde_utils;
+use super::eid::{self, Eid, Uid};
+use super::gender::Gender;
+use super::geo::Geo;
+use crate::privacy::tcf_v2::{TcString, TcfError};
+use crate::serde_utils::{self, EXT_CONSENT};
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct User {
     // A DSP's UID for this user (see user matching for format).
     #[serde(rename = "buyeruid", skip_serializing_if = "Option::is_none")]
     pub buyer_uid: Option<String>,
+    // Optional feature to pass bidder data that was set in the exchange's cookie.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customdata: Option<String>,
     // This object used by publishers to pass additional attributes about the user or content.
     #[serde(skip_serializing_if = "serde_utils::is_none_or_empty")]
     pub data: Option<Vec<Data>>,
     // The User Ext Object, which is used to indicate requests that contain certain user identifiers and are subject to GDPR regulations.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ext: Option<serde_utils::Ext>,
+    // Gender, where "M" = male, "F" = female, "O" = known to be other (i.e., omitted is unknown).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gender: Option<Gender>,
+    // Location of the user's home base defined by a Geo object (Section 3.2.19). This is not necessarily their current location.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geo: Option<Geo>,
     // The Index static identifier for this user (contains only alphanumeric or the following characters: @ - . _ ).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
+    // Comma separated list of keywords, interests, or intent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+    // Year of birth as a 4-digit integer.
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "crate::serde_utils::int_or_string_to_u32")]
+    pub yob: Option<u32>,
+}
+
+impl User {
+    /// The TCF consent string from `ext.consent`.
+    pub fn consent_string(&self) -> Option<&str> {
+        self.ext.as_ref()?.get(EXT_CONSENT)?.as_str()
+    }
+
+    /// Decodes the TCF v2 consent string, if there is one.
+    pub fn tcf_consent(&self) -> Option<Result<TcString, TcfError>> {
+        self.consent_string().map(TcString::parse)
+    }
+
+    /// The extended identifiers from `ext.eids`, skipping any that are malformed.
+    pub fn eids(&self) -> Vec<Eid> {
+        eid::ext_eids(&self.ext)
+    }
+
+    /// Stores the extended identifiers in `ext.eids`, removing it when there are none.
+    pub fn set_eids(&mut self, eids: Vec<Eid>) {
+        eid::set_ext_eids(&mut self.ext, eids);
+    }
+
+    /// The extended identifiers from the given source domain (e.g. “uid2.com”).
+    pub fn eid(&self, source: &str) -> Option<Eid> {
+        self.eids().into_iter().find(|eid| eid.is_from(source))
+    }
+
+    /// The user IDs of the given agent type, across all sources.
+    pub fn uids_with_atype(&self, atype: u32) -> Vec<Uid> {
+        eid::uids_with_atype(self.eids(), atype)
+    }
+
+    /// Removes the extended identifiers from the given source domains, e.g.
+    /// for a partner not allowed to see them, and returns how many were removed.
+    pub fn strip_eids(&mut self, sources: &[&str]) -> usize {
+        eid::strip_ext_eids(&mut self.ext, sources)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn tcf_consent() {
+        let u: User = serde_json::from_str(
+            r#"{"id":"1","ext":{"consent":"COvFyGBOvFyGBAbAAAENAPCAAOAAAAAAAAAAAEEUACCKAAA"}}"#,
+        )
+        .unwrap();
+
+        let tc = u.tcf_consent().unwrap().unwrap();
+        assert!(tc.vendor_consent(8));
+        assert!(!tc.vendor_consent(9));
+
+        let u: User = serde_json::from_str(r#"{"ext":{"consent":"!"}}"#).unwrap();
+        assert_eq!(u.tcf_consent(), Some(Err(TcfError::InvalidCharacter('!'))));
+
+        assert_eq!(User::default().tcf_consent(), None);
+    }
+
+    #[test]
+    fn eids() {
+        let mut u: User = serde_json::from_str(
+            r#"{"ext":{"eids":[{"source":"uid2.com","uids":[{"id":"A4AAAAs","atype":3}]},{"source":"pubcid.org","uids":[{"id":"c29cb2ae","atype":1}]},{"source":"liveramp.com","uids":[{"id":"XY1000bIVBVah9ium","atype":3}]}]}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(u.eids().len(), 3);
+        assert_eq!(u.eid("pubcid.org").unwrap().uids[0].id, "c29cb2ae");
+        assert_eq!(u.eid("id5-sync.com"), None);
+        let ids: Vec<String> = u.uids_with_atype(3).into_iter().map(|uid| uid.id).collect();
+        assert_eq!(ids, vec!["A4AAAAs", "XY1000bIVBVah9ium"]);
+
+        assert_eq!(u.strip_eids(&["uid2.com", "liveramp.com"]), 2);
+        assert_eq!(u.eids().len(), 1);
+        assert_eq!(u.uids_with_atype(3), vec![]);
+
+        u.set_eids(vec![]);
+        assert_eq!(u, User::default());
+    }
+
+    #[test]
+    fn deserialize_all_fields() {
+        let serialized = r#"{"buyeruid":"buyer-1","customdata":"cookie-1","data":[{"id":"7","name":"data.com","segment":[{"id":"1","name":"auto intenders","value":"high"}]}],"gender":"F","geo":{"country":"FRA"},"id":"user-1","keywords":"cars,travel","yob":1984}"#;
+        let u: User = serde_json::from_str(serialized).unwrap();
+
+        assert_eq!(u.gender, Some(Gender::Female));
+        assert_eq!(u.yob, Some(1984));
+        let data = &u.data.as_ref().unwrap()[0];
+        assert_eq!(data.id, Some("7".to_string()));
+        assert_eq!(data.segment[0].value, Some("high".to_string()));
+        assert_eq!(serialized, serde_json::to_string(&u).unwrap());
+
+        let u: User = serde_json::from_str(r#"{"yob":"1984"}"#).unwrap();
+        assert_eq!(u.yob, Some(1984));
+    }
 }
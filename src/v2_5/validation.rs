@@ -0,0 +1,329 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::collections::HashSet;
+use std::fmt::{self, Display};
+
+use phf::phf_set;
+
+use super::bid_request::BidRequest;
+use super::format::Format;
+use super::imp::Imp;
+
+// Active ISO-4217 alpha codes, without the testing (XTS) and no currency
+// (XXX) codes.
+static CURRENCIES: phf::Set<&'static str> = phf_set! {
+    "AED", "AFN", "ALL", "AMD", "ANG", "AOA", "ARS", "AUD", "AWG", "AZN",
+    "BAM", "BBD", "BDT", "BGN", "BHD", "BIF", "BMD", "BND", "BOB", "BOV",
+    "BRL", "BSD", "BTN", "BWP", "BYN", "BZD", "CAD", "CDF", "CHE", "CHF",
+    "CHW", "CLF", "CLP", "CNY", "COP", "COU", "CRC", "CUC", "CUP", "CVE",
+    "CZK", "DJF", "DKK", "DOP", "DZD", "EGP", "ERN", "ETB", "EUR", "FJD",
+    "FKP", "GBP", "GEL", "GHS", "GIP", "GMD", "GNF", "GTQ", "GYD", "HKD",
+    "HNL", "HTG", "HUF", "IDR", "ILS", "INR", "IQD", "IRR", "ISK", "JMD",
+    "JOD", "JPY", "KES", "KGS", "KHR", "KMF", "KPW", "KRW", "KWD", "KYD",
+    "KZT", "LAK", "LBP", "LKR", "LRD", "LSL", "LYD", "MAD", "MDL", "MGA",
+    "MKD", "MMK", "MNT", "MOP", "MRU", "MUR", "MVR", "MWK", "MXN", "MXV",
+    "MYR", "MZN", "NAD", "NGN", "NIO", "NOK", "NPR", "NZD", "OMR", "PAB",
+    "PEN", "PGK", "PHP", "PKR", "PLN", "PYG", "QAR", "RON", "RSD", "RUB",
+    "RWF", "SAR", "SBD", "SCR", "SDG", "SEK", "SGD", "SHP", "SLE", "SLL",
+    "SOS", "SRD", "SSP", "STN", "SVC", "SYP", "SZL", "THB", "TJS", "TMT",
+    "TND", "TOP", "TRY", "TTD", "TWD", "TZS", "UAH", "UGX", "USD", "USN",
+    "UYI", "UYU", "UYW", "UZS", "VED", "VES", "VND", "VUV", "WST", "XAF",
+    "XAG", "XAU", "XBA", "XBB", "XBC", "XBD", "XCD", "XCG", "XDR", "XOF",
+    "XPD", "XPF", "XPT", "XSU", "XUA", "YER", "ZAR", "ZMW", "ZWG", "ZWL",
+};
+
+/// Whether the code is an active ISO-4217 alpha currency code, in upper case.
+pub fn is_iso_4217_currency(code: &str) -> bool {
+    CURRENCIES.contains(code)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    // The request goes against a recommendation of the specification.
+    Warning,
+    // The request breaks a requirement of the specification.
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    // A request must have at least one Imp.
+    ImpRequired,
+    // A request must not contain both a Site and an App.
+    SiteAppExclusive,
+    // Imp IDs must be unique within a request.
+    ImpIdUnique,
+    // An Imp must offer at least one of Banner, Video, Audio or Native.
+    ImpMediaRequired,
+    // At most one of wseat and bseat should be used in a request.
+    SeatWhitelistBlocklistExclusive,
+    // Currencies must be ISO-4217 alpha codes.
+    CurrencyIso4217,
+    // Bid floors must not be negative.
+    BidFloorNonNegative,
+    // A Format must have complete w/h and wratio/hratio pairs which, when
+    // both present, describe the same ratio.
+    FormatSizeRatio,
+}
+
+impl Rule {
+    /// A stable identifier of the rule, for reporting.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Rule::ImpRequired => "imp-required",
+            Rule::SiteAppExclusive => "site-app-exclusive",
+            Rule::ImpIdUnique => "imp-id-unique",
+            Rule::ImpMediaRequired => "imp-media-required",
+            Rule::SeatWhitelistBlocklistExclusive => "wseat-bseat-exclusive",
+            Rule::CurrencyIso4217 => "currency-iso-4217",
+            Rule::BidFloorNonNegative => "bidfloor-non-negative",
+            Rule::FormatSizeRatio => "format-size-ratio",
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            Rule::SeatWhitelistBlocklistExclusive => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.id())
+    }
+}
+
+/// A rule a request breaks, and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// JSON path of the offending value, e.g. `$.imp[1].bidfloor`.
+    pub path: String,
+    pub rule: Rule,
+    pub severity: Severity,
+}
+
+impl Violation {
+    fn new(path: String, rule: Rule) -> Violation {
+        Violation {
+            path,
+            rule,
+            severity: rule.severity(),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{:?} {} at {}", self.severity, self.rule, self.path)
+    }
+}
+
+impl BidRequest {
+    /// Checks the request against the rules of the specification, returning
+    /// every violation found in document order.
+    pub fn violations(&self) -> Vec<Violation> {
+        let mut violations = vec![];
+
+        if self.imp.is_empty() {
+            violations.push(Violation::new("$.imp".to_string(), Rule::ImpRequired));
+        }
+
+        let mut imp_ids = HashSet::new();
+        for (i, imp) in self.imp.iter().enumerate() {
+            let path = format!("$.imp[{}]", i);
+            if !imp_ids.insert(&imp.id) {
+                violations.push(Violation::new(format!("{}.id", path), Rule::ImpIdUnique));
+            }
+            imp_violations(imp, &path, &mut violations);
+        }
+
+        if self.site.is_some() && self.app.is_some() {
+            violations.push(Violation::new("$.app".to_string(), Rule::SiteAppExclusive));
+        }
+
+        if !self.seat_whitelist.is_empty() && !self.seat_blocklist.is_empty() {
+            violations.push(Violation::new("$.bseat".to_string(), Rule::SeatWhitelistBlocklistExclusive));
+        }
+
+        for (i, currency) in self.currency.iter().enumerate() {
+            check_currency(currency, format!("$.cur[{}]", i), &mut violations);
+        }
+
+        violations
+    }
+}
+
+fn imp_violations(imp: &Imp, path: &str, violations: &mut Vec<Violation>) {
+    if imp.banner.is_none() && imp.video.is_none() && imp.audio.is_none() && imp.native.is_none() {
+        violations.push(Violation::new(path.to_string(), Rule::ImpMediaRequired));
+    }
+
+    if let Some(ref banner) = imp.banner {
+        for (i, format) in banner.format.iter().enumerate() {
+            if !is_consistent(format) {
+                violations.push(Violation::new(format!("{}.banner.format[{}]", path, i), Rule::FormatSizeRatio));
+            }
+        }
+    }
+
+    check_bid_floor(imp.bid_floor, format!("{}.bidfloor", path), violations);
+    if let Some(ref currency) = imp.bid_floor_cur {
+        check_currency(currency, format!("{}.bidfloorcur", path), violations);
+    }
+
+    if let Some(ref pmp) = imp.pmp {
+        for (i, deal) in pmp.deals.iter().enumerate() {
+            let path = format!("{}.pmp.deals[{}]", path, i);
+            check_bid_floor(deal.bid_floor, format!("{}.bidfloor", path), violations);
+            if let Some(ref currency) = deal.bid_floor_cur {
+                check_currency(currency, format!("{}.bidfloorcur", path), violations);
+            }
+        }
+    }
+}
+
+fn check_bid_floor(bid_floor: Option<f32>, path: String, violations: &mut Vec<Violation>) {
+    // NaN is not a valid floor either.
+    if bid_floor.is_some_and(|bid_floor| bid_floor < 0.0 || bid_floor.is_nan()) {
+        violations.push(Violation::new(path, Rule::BidFloorNonNegative));
+    }
+}
+
+fn check_currency(currency: &str, path: String, violations: &mut Vec<Violation>) {
+    if !is_iso_4217_currency(currency) {
+        violations.push(Violation::new(path, Rule::CurrencyIso4217));
+    }
+}
+
+fn is_consistent(format: &Format) -> bool {
+    match (format.w, format.h, format.w_ratio, format.h_ratio) {
+        (Some(_), None, _, _) | (None, Some(_), _, _) => false,
+        (_, _, Some(_), None) | (_, _, None, Some(_)) => false,
+        (_, _, Some(0), _) | (_, _, _, Some(0)) => false,
+        (Some(w), Some(h), Some(w_ratio), Some(h_ratio)) => w as u64 * h_ratio as u64 == h as u64 * w_ratio as u64,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    fn violations(json: &str) -> Vec<(String, Rule)> {
+        let request: BidRequest = serde_json::from_str(json).unwrap();
+        request
+            .violations()
+            .into_iter()
+            .map(|violation| (violation.path, violation.rule))
+            .collect()
+    }
+
+    #[test]
+    fn valid_request() {
+        let json = r#"{
+            "id": "1",
+            "imp": [
+                {"id": "1", "banner": {"format": [{"w": 300, "h": 250}, {"wratio": 6, "hratio": 5, "w_min": 300}]}},
+                {"id": "2", "video": {"mimes": ["video/mp4"]}, "bidfloor": 0, "bidfloorcur": "EUR"}
+            ],
+            "site": {"id": "1"},
+            "cur": ["USD", "EUR"]
+        }"#;
+
+        assert!(violations(json).is_empty());
+    }
+
+    #[test]
+    fn imp_required() {
+        let request = BidRequest::new("1".to_string());
+
+        assert_eq!(
+            request.violations(),
+            vec![Violation {
+                path: "$.imp".to_string(),
+                rule: Rule::ImpRequired,
+                severity: Severity::Error,
+            }]
+        );
+        assert!(!request.validate());
+    }
+
+    #[test]
+    fn request_violations() {
+        let json = r#"{
+            "id": "1",
+            "imp": [
+                {"id": "1", "banner": {"format": [{"w": 300}, {"w": 300, "h": 250, "wratio": 4, "hratio": 3}]}},
+                {"id": "1", "bidfloor": -0.5, "bidfloorcur": "usd"},
+                {"id": "2", "native": {"request": ""}, "pmp": {"deals": [{"id": "d", "bidfloor": -1, "bidfloorcur": "XXX"}]}}
+            ],
+            "site": {"id": "1"},
+            "app": {"id": "1"},
+            "wseat": ["a"],
+            "bseat": ["b"],
+            "cur": ["USD", "EURO"]
+        }"#;
+
+        assert_eq!(
+            violations(json),
+            vec![
+                ("$.imp[0].banner.format[0]".to_string(), Rule::FormatSizeRatio),
+                ("$.imp[0].banner.format[1]".to_string(), Rule::FormatSizeRatio),
+                ("$.imp[1].id".to_string(), Rule::ImpIdUnique),
+                ("$.imp[1]".to_string(), Rule::ImpMediaRequired),
+                ("$.imp[1].bidfloor".to_string(), Rule::BidFloorNonNegative),
+                ("$.imp[1].bidfloorcur".to_string(), Rule::CurrencyIso4217),
+                ("$.imp[2].pmp.deals[0].bidfloor".to_string(), Rule::BidFloorNonNegative),
+                ("$.imp[2].pmp.deals[0].bidfloorcur".to_string(), Rule::CurrencyIso4217),
+                ("$.app".to_string(), Rule::SiteAppExclusive),
+                ("$.bseat".to_string(), Rule::SeatWhitelistBlocklistExclusive),
+                ("$.cur[1]".to_string(), Rule::CurrencyIso4217),
+            ]
+        );
+    }
+
+    #[test]
+    fn warnings_do_not_invalidate() {
+        let request: BidRequest =
+            serde_json::from_str(r#"{"id":"1","imp":[{"id":"1","audio":{}}],"wseat":["a"],"bseat":["b"]}"#).unwrap();
+        let violations = request.violations();
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].severity, Severity::Warning);
+        assert_eq!(violations[0].to_string(), "Warning wseat-bseat-exclusive at $.bseat");
+        assert!(request.validate());
+    }
+
+    #[test]
+    fn format_consistency() {
+        let format = |w, h, w_ratio, h_ratio| Format {
+            w,
+            h,
+            w_ratio,
+            h_ratio,
+            ..Default::default()
+        };
+
+        assert!(is_consistent(&format(None, None, None, None)));
+        assert!(is_consistent(&format(Some(320), Some(50), None, None)));
+        assert!(is_consistent(&format(None, None, Some(32), Some(5))));
+        assert!(is_consistent(&format(Some(320), Some(50), Some(32), Some(5))));
+        assert!(!is_consistent(&format(Some(320), Some(50), Some(1), Some(1))));
+        assert!(!is_consistent(&format(None, Some(50), None, None)));
+        assert!(!is_consistent(&format(None, None, Some(0), Some(1))));
+        assert!(!is_consistent(&format(None, None, None, Some(1))));
+    }
+}
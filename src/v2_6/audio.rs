@@ -0,0 +1,146 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::serde_utils;
+
+use crate::v2_5::Banner;
+
+/// 3.2.8 Object: Audio
+///
+/// This object represents an audio type impression. Many of the fields are non-essential for minimally
+/// viable transactions, but are included to offer fine control when needed. Audio in OpenRTB generally
+/// assumes compliance with the DAAST standard. As such, the notion of companion ads is supported by
+/// optionally including an array of Banner objects that define these companion ads.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Audio {
+    /// Content MIME types supported (e.g., “audio/mp4”).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mimes: Vec<String>,
+    /// Minimum audio ad duration in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minduration: Option<u32>,
+    /// Maximum audio ad duration in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxduration: Option<u32>,
+    /// Array of supported audio protocols.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protocols: Vec<u32>,
+    /// Indicates the start delay in seconds for pre-roll, mid-roll, or post-roll ad placements,
+    /// where 0 = pre-roll, -1 = generic mid-roll and -2 = generic post-roll.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub startdelay: Option<i32>,
+    /// If multiple ad impressions are offered in the same bid request, the sequence number will
+    /// allow for the coordinated delivery of multiple creatives.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<u32>,
+    /// Blocked creative attributes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub battr: Vec<u64>,
+    /// Maximum extended ad duration if extension is allowed. If blank or 0, extension is not
+    /// allowed. If -1, extension is allowed, and there is no time limit imposed. If greater
+    /// than 0, then the value represents the number of seconds of extended play supported
+    /// beyond the maxduration value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxextended: Option<i32>,
+    /// Minimum bit rate in Kbps.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minbitrate: Option<u32>,
+    /// Maximum bit rate in Kbps.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxbitrate: Option<u32>,
+    /// Supported delivery methods (e.g., streaming, progressive).
+    /// If none specified, assume all are supported.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub delivery: Vec<u32>,
+    /// Array of Banner objects if companion ads are available.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub companionad: Vec<Banner>,
+    /// List of supported API frameworks for this impression.
+    /// If an API is not explicitly listed, it is assumed not to be supported.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api: Vec<u32>,
+    /// Supported DAAST companion ad types. Recommended if companion Banner objects
+    /// are included via the companionad array.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub companiontype: Vec<u32>,
+    /// The maximum number of ads that can be played in an ad pod.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxseq: Option<u32>,
+    /// Type of audio feed, where 1 = music service, 2 = FM/AM broadcast
+    /// and 3 = podcast.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feed: Option<u32>,
+    /// Indicates if the ad is stitched with audio content or delivered
+    /// independently, where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub stitched: Option<bool>,
+    /// Volume normalization mode, where 0 = none, 1 = average volume,
+    /// 2 = peak volume, 3 = loudness and 4 = custom volume.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nvol: Option<u32>,
+    /// Indicates the total amount of time in seconds that advertisers may
+    /// fill for a “dynamic” audio ad pod, or the length of a “structured”
+    /// one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub poddur: Option<u32>,
+    /// Precise acceptable durations for audio creatives in seconds.
+    /// Mutually exclusive with minduration and maxduration.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rqddurs: Vec<u32>,
+    /// Unique identifier indicating that an impression opportunity belongs
+    /// to an audio ad pod.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub podid: Option<String>,
+    /// The sequence (position) of the audio ad pod within a content stream,
+    /// where 0 = any, 1 = first pod and -1 = last pod.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub podseq: Option<i32>,
+    /// For audio ad pods, the seller's guidance on where the impression may
+    /// be positioned within the pod, where 0 = any, 1 = first, -1 = last
+    /// and 2 = first or last.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slotinpod: Option<i32>,
+    /// Minimum CPM per second, the price floor for the “dynamic” portion of
+    /// an audio ad pod relative to the duration of bids.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mincpmpersec: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn deserialize_ad_pod() {
+        let json = r#"{
+            "mimes": ["audio/mpeg"],
+            "poddur": 60,
+            "rqddurs": [30],
+            "podid": "1",
+            "podseq": 1,
+            "slotinpod": -1,
+            "mincpmpersec": 0.1
+        }"#;
+        let audio: Audio = serde_json::from_str(json).unwrap();
+
+        assert_eq!(audio.poddur, Some(60));
+        assert_eq!(audio.rqddurs, vec![30]);
+        assert_eq!(audio.podid, Some("1".to_string()));
+        assert_eq!(audio.podseq, Some(1));
+        assert_eq!(audio.slotinpod, Some(-1));
+        assert_eq!(audio.mincpmpersec, Some(0.1));
+    }
+}
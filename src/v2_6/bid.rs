@@ -0,0 +1,181 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::imp::Imp;
+use crate::native::v1_2::{MarkupError, Response as NativeResponse};
+use crate::serde_utils;
+use crate::v2_5::Category;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Bid {
+    #[serde(deserialize_with = "serde_utils::anything_to_string")]
+    pub id: String,
+    #[serde(rename = "impid", deserialize_with = "serde_utils::anything_to_string")]
+    pub imp_id: String,
+    pub price: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nurl: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub burl: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lurl: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adid: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub adomain: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iurl: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cid: Option<String>,
+    #[serde(rename = "crid", skip_serializing_if = "Option::is_none")]
+    pub cr_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tactic: Option<String>,
+    // The taxonomy in use for cat, where 1 = IAB Content Category
+    // Taxonomy 1.0, the default for OpenRTB 2.5 categories.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cattax: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cat: Vec<Category>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attr: Vec<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api: Option<u32>,
+    // List of supported APIs for the markup, which supersedes api.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub apis: Vec<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qagmediarating: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(rename = "dealid", skip_serializing_if = "Option::is_none")]
+    pub deal_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub w: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub h: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wratio: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hratio: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<u64>,
+    // Duration of the video or audio creative in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dur: Option<u32>,
+    // Type of the creative markup, where 1 = banner, 2 = video, 3 = audio
+    // and 4 = native, so that the markup can be parsed without guessing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtype: Option<u32>,
+    // For ad pods, the position the bid wants in the pod, where 0 = any,
+    // 1 = first, -1 = last and 2 = first or last.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slotinpod: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+impl Bid {
+    pub fn new(id: String, imp_id: String, price: f64) -> Bid {
+        Bid {
+            id,
+            imp_id,
+            price,
+            nurl: None,
+            burl: None,
+            lurl: None,
+            adm: None,
+            adid: None,
+            adomain: vec![],
+            bundle: None,
+            iurl: None,
+            cid: None,
+            cr_id: None,
+            tactic: None,
+            cattax: None,
+            cat: vec![],
+            attr: vec![],
+            api: None,
+            apis: vec![],
+            protocol: None,
+            qagmediarating: None,
+            language: None,
+            deal_id: None,
+            w: None,
+            h: None,
+            wratio: None,
+            hratio: None,
+            exp: None,
+            dur: None,
+            mtype: None,
+            slotinpod: None,
+            ext: None,
+        }
+    }
+
+    /// Parses the adm as a native response, whether or not it is wrapped
+    /// in a `{"native": ...}` object.
+    pub fn parse_native_adm(&self) -> Result<NativeResponse, MarkupError> {
+        match self.adm {
+            Some(ref adm) => NativeResponse::from_markup(adm),
+            None => Err(MarkupError::MissingMarkup),
+        }
+    }
+
+    /// Parses the adm as a native response and checks its assets against
+    /// the native request of the impression the bid is for.
+    pub fn parse_native_adm_for(&self, imp: &Imp) -> Result<NativeResponse, MarkupError> {
+        let request = match imp.native {
+            Some(ref native) => native.parse_request()?,
+            None => return Err(MarkupError::MissingNativeRequest),
+        };
+
+        let response = self.parse_native_adm()?;
+        response.check_assets(&request)?;
+
+        Ok(response)
+    }
+
+    /// Sets the adm to the given native response in the 1.2 form.
+    pub fn set_native_adm(&mut self, response: &NativeResponse) -> Result<(), MarkupError> {
+        self.adm = Some(response.to_markup()?);
+        Ok(())
+    }
+
+    /// Sets the adm to the given native response wrapped in a
+    /// `{"native": ...}` object.
+    pub fn set_wrapped_native_adm(&mut self, response: &NativeResponse) -> Result<(), MarkupError> {
+        self.adm = Some(response.to_wrapped_markup()?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_2_6_fields() {
+        let bid: Bid = serde_json::from_str(
+            r#"{"id":"1","impid":"1","price":2,"apis":[7],"mtype":2,"dur":30,"slotinpod":1,"cattax":2,"cat":["IAB1"]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(bid.apis, vec![7]);
+        assert_eq!(bid.mtype, Some(2));
+        assert_eq!(bid.dur, Some(30));
+        assert_eq!(bid.slotinpod, Some(1));
+        assert_eq!(bid.cattax, Some(2));
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::seat_bid::SeatBid;
+use crate::serde_utils;
+use crate::v2_5::NoBidReason;
+
+// 4.2.1 Object: BidResponse
+//
+// This object is the top-level bid response object (i.e., the unnamed outer
+// JSON object). The id attribute is a reflection of the bid request ID for
+// logging purposes. Similarly, bidid is an optional response tracking ID for
+// bidders. If specified, it can be included in the subsequent win notice call
+// if the bidder wins. At least one seatbid object is required, which contains
+// at least one bid for an impression. Other attributes are optional.
+// To express a “no-bid”, the options are to return an empty response with
+// HTTP 204. Alternately if the bidder wishes to convey to the exchange a
+// reason for not bidding, just a BidResponse object is returned with a reason
+// code in the nbr attribute.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BidResponse {
+    // Required. ID of the bid request to which this is a response.
+    pub id: String,
+
+    #[serde(rename = "seatbid", default, skip_serializing_if = "Vec::is_empty")]
+    pub seat_bid: Vec<SeatBid>,
+
+    #[serde(rename = "bidid", skip_serializing_if = "Option::is_none")]
+    pub bid_id: Option<String>,
+
+    #[serde(rename = "cur", skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+
+    #[serde(rename = "customdata", skip_serializing_if = "Option::is_none")]
+    pub custom_data: Option<String>,
+
+    // Reason for not bidding. Refer to List 5.24.
+    #[serde(rename = "nbr", skip_serializing_if = "Option::is_none")]
+    pub no_bidding_reason: Option<NoBidReason>,
+
+    // Placeholder for exchange-specific extensions to OpenRTB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+impl BidResponse {
+    pub fn new(id: String) -> BidResponse {
+        BidResponse {
+            id,
+            seat_bid: vec![],
+            bid_id: None,
+            currency: None,
+            custom_data: None,
+            no_bidding_reason: None,
+            ext: None,
+        }
+    }
+
+    /// Builds a response to the request with the given ID that bids on
+    /// nothing, for the given reason.
+    pub fn no_bid(id: String, reason: NoBidReason) -> BidResponse {
+        BidResponse {
+            no_bidding_reason: Some(reason),
+            ..BidResponse::new(id)
+        }
+    }
+}
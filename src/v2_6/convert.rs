@@ -0,0 +1,921 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Conversions between the OpenRTB 2.5 and 2.6 models.
+//
+// OpenRTB 2.5 requests carry the supply chain, extended IDs, GDPR, US
+// Privacy, GPP and consent signals in ext, where 2.6 promotes them to fields
+// of their own. Converting to 2.6 moves them out of ext, leaving values that
+// do not parse where they are, so nothing is lost. Converting to 2.5 moves
+// them back into ext, and reports the values 2.5 has no place for by their
+// JSON path, e.g. `$.imp[0].rwdd`.
+
+use std::convert::TryFrom;
+use std::error;
+use std::fmt::{self, Display};
+
+use serde::Serialize;
+
+use crate::serde_utils::{
+    remove_ext, take_ext, Ext, EXT_CONSENT, EXT_EIDS, EXT_GDPR, EXT_GPP, EXT_GPP_SID, EXT_SCHAIN, EXT_US_PRIVACY,
+};
+use crate::v2_5;
+
+use super::{
+    App, Audio, Bid, BidRequest, BidResponse, Content, Device, Eid, Imp, Regulations, SeatBid, Site, Source,
+    SupplyChain, User, Video,
+};
+
+// Category taxonomy of OpenRTB 2.5, IAB Content Category Taxonomy 1.0.
+const CATTAX_IAB_1_0: u32 = 1;
+
+/// The values of an OpenRTB 2.6 request or response that OpenRTB 2.5 cannot
+/// represent, by JSON path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversionError {
+    pub paths: Vec<String>,
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "OpenRTB 2.5 cannot represent {}", self.paths.join(", "))
+    }
+}
+
+impl error::Error for ConversionError {}
+
+impl From<v2_5::BidRequest> for BidRequest {
+    fn from(request: v2_5::BidRequest) -> BidRequest {
+        BidRequest {
+            id: request.id,
+            imp: request.imp.into_iter().map(Imp::from).collect(),
+            site: request.site.map(Site::from),
+            app: request.app.map(App::from),
+            device: request.device.map(Device::from),
+            user: request.user.map(User::from),
+            test: request.test,
+            auction_type: request.auction_type,
+            tmax: request.tmax,
+            seat_whitelist: request.seat_whitelist,
+            seat_blocklist: request.seat_blocklist,
+            all_imps: request.all_imps,
+            currency: request.currency,
+            language_whitelist: request.language_whitelist,
+            category_blocklist: request.category_blocklist,
+            advertiser_blocklist: request.advertiser_blocklist,
+            app_blocklist: request.app_blocklist,
+            source: request.source.map(upgrade_source),
+            regulations: request.regulations.map(upgrade_regulations),
+            ext: request.ext,
+        }
+    }
+}
+
+impl TryFrom<BidRequest> for v2_5::BidRequest {
+    type Error = ConversionError;
+
+    fn try_from(request: BidRequest) -> Result<v2_5::BidRequest, ConversionError> {
+        match request.into_v2_5_lossy() {
+            (request, paths) if paths.is_empty() => Ok(request),
+            (_, paths) => Err(ConversionError { paths }),
+        }
+    }
+}
+
+impl BidRequest {
+    /// Converts the request to OpenRTB 2.5, dropping the values it cannot
+    /// represent and returning their JSON paths.
+    pub fn into_v2_5_lossy(self) -> (v2_5::BidRequest, Vec<String>) {
+        let mut dropped = Dropped(vec![]);
+
+        let request = v2_5::BidRequest {
+            id: self.id,
+            imp: self
+                .imp
+                .into_iter()
+                .enumerate()
+                .map(|(i, imp)| downgrade_imp(imp, &format!("$.imp[{}]", i), &mut dropped))
+                .collect(),
+            site: self.site.map(|site| downgrade_site(site, "$.site", &mut dropped)),
+            app: self.app.map(|app| downgrade_app(app, "$.app", &mut dropped)),
+            device: self.device.map(|device| downgrade_device(device, "$.device", &mut dropped)),
+            user: self.user.map(|user| downgrade_user(user, "$.user", &mut dropped)),
+            test: self.test,
+            auction_type: self.auction_type,
+            tmax: self.tmax,
+            seat_whitelist: self.seat_whitelist,
+            seat_blocklist: self.seat_blocklist,
+            all_imps: self.all_imps,
+            currency: self.currency,
+            language_whitelist: self.language_whitelist,
+            category_blocklist: self.category_blocklist,
+            advertiser_blocklist: self.advertiser_blocklist,
+            app_blocklist: self.app_blocklist,
+            source: self.source.map(|source| downgrade_source(source, "$.source", &mut dropped)),
+            regulations: self.regulations.map(|regs| downgrade_regulations(regs, "$.regs", &mut dropped)),
+            ext: self.ext,
+        };
+
+        (request, dropped.0)
+    }
+}
+
+impl From<v2_5::BidResponse> for BidResponse {
+    fn from(response: v2_5::BidResponse) -> BidResponse {
+        BidResponse {
+            id: response.id,
+            seat_bid: response.seat_bid.into_iter().map(SeatBid::from).collect(),
+            bid_id: response.bid_id,
+            currency: response.currency,
+            custom_data: response.custom_data,
+            no_bidding_reason: response.no_bidding_reason,
+            ext: response.ext,
+        }
+    }
+}
+
+impl TryFrom<BidResponse> for v2_5::BidResponse {
+    type Error = ConversionError;
+
+    fn try_from(response: BidResponse) -> Result<v2_5::BidResponse, ConversionError> {
+        match response.into_v2_5_lossy() {
+            (response, paths) if paths.is_empty() => Ok(response),
+            (_, paths) => Err(ConversionError { paths }),
+        }
+    }
+}
+
+impl BidResponse {
+    /// Converts the response to OpenRTB 2.5, dropping the values it cannot
+    /// represent and returning their JSON paths.
+    pub fn into_v2_5_lossy(self) -> (v2_5::BidResponse, Vec<String>) {
+        let mut dropped = Dropped(vec![]);
+
+        let response = v2_5::BidResponse {
+            id: self.id,
+            seat_bid: self
+                .seat_bid
+                .into_iter()
+                .enumerate()
+                .map(|(i, seat_bid)| v2_5::SeatBid {
+                    bid: seat_bid
+                        .bid
+                        .into_iter()
+                        .enumerate()
+                        .map(|(j, bid)| downgrade_bid(bid, &format!("$.seatbid[{}].bid[{}]", i, j), &mut dropped))
+                        .collect(),
+                    seat: seat_bid.seat,
+                    group: seat_bid.group,
+                    ext: seat_bid.ext,
+                })
+                .collect(),
+            bid_id: self.bid_id,
+            currency: self.currency,
+            custom_data: self.custom_data,
+            no_bidding_reason: self.no_bidding_reason,
+            ext: self.ext,
+        };
+
+        (response, dropped.0)
+    }
+}
+
+impl From<v2_5::Imp> for Imp {
+    fn from(imp: v2_5::Imp) -> Imp {
+        Imp {
+            id: imp.id,
+            metric: imp.metric,
+            banner: imp.banner,
+            video: imp.video.map(Video::from),
+            audio: imp.audio.map(Audio::from),
+            native: imp.native,
+            pmp: imp.pmp,
+            display_manager: imp.display_manager,
+            display_manager_ver: imp.display_manager_ver,
+            interstitial: imp.interstitial,
+            tag_id: imp.tag_id,
+            bid_floor: imp.bid_floor,
+            bid_floor_cur: imp.bid_floor_cur,
+            secure: imp.secure,
+            exp: imp.exp,
+            rwdd: None,
+            ssai: None,
+            qty: None,
+            dt: None,
+            refresh: None,
+            ext: imp.ext,
+        }
+    }
+}
+
+impl From<v2_5::Video> for Video {
+    fn from(video: v2_5::Video) -> Video {
+        Video {
+            mimes: video.mimes,
+            minduration: video.minduration,
+            maxduration: video.maxduration,
+            protocols: video.protocols,
+            w: video.w,
+            h: video.h,
+            placement: video.placement,
+            plcmt: None,
+            linearity: video.linearity,
+            battr: video.battr,
+            minbitrate: video.minbitrate,
+            maxbitrate: video.maxbitrate,
+            boxingallowed: video.boxingallowed,
+            playbackmethod: video.playbackmethod,
+            maxseq: None,
+            poddur: None,
+            rqddurs: vec![],
+            podid: None,
+            podseq: None,
+            slotinpod: None,
+            mincpmpersec: None,
+            ext: video.ext,
+        }
+    }
+}
+
+impl From<v2_5::Audio> for Audio {
+    fn from(audio: v2_5::Audio) -> Audio {
+        Audio {
+            mimes: audio.mimes,
+            minduration: audio.minduration,
+            maxduration: audio.maxduration,
+            protocols: audio.protocols,
+            startdelay: audio.startdelay,
+            sequence: audio.sequence,
+            battr: audio.battr,
+            maxextended: audio.maxextended,
+            minbitrate: audio.minbitrate,
+            maxbitrate: audio.maxbitrate,
+            delivery: audio.delivery,
+            companionad: audio.companionad,
+            api: audio.api,
+            companiontype: audio.companiontype,
+            maxseq: audio.maxseq,
+            feed: audio.feed,
+            stitched: audio.stitched,
+            nvol: audio.nvol,
+            poddur: None,
+            rqddurs: vec![],
+            podid: None,
+            podseq: None,
+            slotinpod: None,
+            mincpmpersec: None,
+            ext: audio.ext,
+        }
+    }
+}
+
+impl From<v2_5::Site> for Site {
+    fn from(site: v2_5::Site) -> Site {
+        Site {
+            id: site.id,
+            name: site.name,
+            domain: site.domain,
+            cat: site.cat,
+            sectioncat: site.sectioncat,
+            pagecat: site.pagecat,
+            page: site.page,
+            referrer: site.referrer,
+            search: site.search,
+            mobile: site.mobile,
+            privacypolicy: site.privacypolicy,
+            publisher: site.publisher,
+            content: site.content.map(Content::from),
+            keywords: site.keywords,
+            ext: site.ext,
+        }
+    }
+}
+
+impl From<v2_5::App> for App {
+    fn from(app: v2_5::App) -> App {
+        App {
+            id: app.id,
+            name: app.name,
+            bundle: app.bundle,
+            domain: app.domain,
+            store_url: app.store_url,
+            cat: app.cat,
+            section_cat: app.section_cat,
+            page_cat: app.page_cat,
+            ver: app.ver,
+            publisher: app.publisher,
+            content: app.content.map(Content::from),
+            keywords: app.keywords,
+            ext: app.ext,
+        }
+    }
+}
+
+impl From<v2_5::Content> for Content {
+    fn from(content: v2_5::Content) -> Content {
+        Content {
+            id: content.id,
+            episode: content.episode,
+            title: content.title,
+            series: content.series,
+            season: content.season,
+            artist: content.artist,
+            genre: content.genre,
+            album: content.album,
+            isrc: content.isrc,
+            url: content.url,
+            cat: content.cat,
+            prodq: content.prodq,
+            context: content.context,
+            content_rating: content.content_rating,
+            user_rating: content.user_rating,
+            qag_media_rating: content.qag_media_rating,
+            keywords: content.keywords,
+            live_stream: content.live_stream,
+            source_relationship: content.source_relationship,
+            len: content.len,
+            language: content.language,
+            embeddable: content.embeddable,
+            network: None,
+            channel: None,
+            ext: content.ext,
+        }
+    }
+}
+
+impl From<v2_5::Device> for Device {
+    fn from(device: v2_5::Device) -> Device {
+        Device {
+            ua: device.ua,
+            sua: None,
+            geo: device.geo,
+            dnt: device.dnt,
+            lmt: device.lmt,
+            ip: device.ip,
+            ipv6: device.ipv6,
+            device_type: device.device_type,
+            make: device.make,
+            model: device.model,
+            os: device.os,
+            osv: device.osv,
+            hwv: device.hwv,
+            h: device.h,
+            w: device.w,
+            ppi: device.ppi,
+            pxratio: device.pxratio,
+            js: device.js,
+            geofetch: device.geofetch,
+            flashver: device.flashver,
+            language: device.language,
+            carrier: device.carrier,
+            mccmnc: device.mccmnc,
+            connection_type: device.connection_type,
+            ifa: device.ifa,
+            didsha1: device.didsha1,
+            didmd5: device.didmd5,
+            dpidsha1: device.dpidsha1,
+            dpidmd5: device.dpidmd5,
+            macsha1: device.macsha1,
+            macmd5: device.macmd5,
+            ext: device.ext,
+        }
+    }
+}
+
+impl From<v2_5::User> for User {
+    fn from(user: v2_5::User) -> User {
+        let mut ext = user.ext;
+        let consent = take_ext(&mut ext, EXT_CONSENT);
+        let eids = take_ext::<Vec<Eid>>(&mut ext, EXT_EIDS).unwrap_or_default();
+
+        User {
+            buyer_uid: user.buyer_uid,
+            customdata: user.customdata,
+            data: user.data,
+            consent,
+            eids,
+            ext,
+            gender: user.gender,
+            geo: user.geo,
+            id: user.id,
+            keywords: user.keywords,
+            yob: user.yob,
+        }
+    }
+}
+
+impl From<v2_5::SeatBid> for SeatBid {
+    fn from(seat_bid: v2_5::SeatBid) -> SeatBid {
+        SeatBid {
+            bid: seat_bid.bid.into_iter().map(Bid::from).collect(),
+            seat: seat_bid.seat,
+            group: seat_bid.group,
+            ext: seat_bid.ext,
+        }
+    }
+}
+
+impl From<v2_5::Bid> for Bid {
+    fn from(bid: v2_5::Bid) -> Bid {
+        Bid {
+            id: bid.id,
+            imp_id: bid.imp_id,
+            price: bid.price,
+            nurl: bid.nurl,
+            burl: bid.burl,
+            lurl: bid.lurl,
+            adm: bid.adm,
+            adid: bid.adid,
+            adomain: bid.adomain,
+            bundle: bid.bundle,
+            iurl: bid.iurl,
+            cid: bid.cid,
+            cr_id: bid.cr_id,
+            tactic: bid.tactic,
+            cattax: None,
+            cat: bid.cat,
+            attr: bid.attr,
+            api: bid.api,
+            apis: vec![],
+            protocol: bid.protocol,
+            qagmediarating: bid.qagmediarating,
+            language: bid.language,
+            deal_id: bid.deal_id,
+            w: bid.w,
+            h: bid.h,
+            wratio: bid.wratio,
+            hratio: bid.hratio,
+            exp: bid.exp,
+            dur: None,
+            mtype: None,
+            slotinpod: None,
+            ext: bid.ext,
+        }
+    }
+}
+
+// Source and Regs are shared by both models, with the fields OpenRTB 2.6
+// promotes out of ext, so converting only moves values.
+
+fn upgrade_source(mut source: Source) -> Source {
+    if source.schain.is_none() {
+        source.schain = take_ext::<SupplyChain>(&mut source.ext, EXT_SCHAIN);
+    }
+    source
+}
+
+fn upgrade_regulations(mut regs: Regulations) -> Regulations {
+    if regs.gdpr.is_none() {
+        regs.gdpr = regs.gdpr_applies();
+        if regs.gdpr.is_some() {
+            remove_ext(&mut regs.ext, EXT_GDPR);
+        }
+    }
+    if regs.us_privacy.is_none() {
+        regs.us_privacy = take_ext(&mut regs.ext, EXT_US_PRIVACY);
+    }
+    if regs.gpp.is_none() {
+        regs.gpp = take_ext(&mut regs.ext, EXT_GPP);
+    }
+    if regs.gpp_sid.is_empty() {
+        regs.gpp_sid = take_ext(&mut regs.ext, EXT_GPP_SID).unwrap_or_default();
+    }
+    regs
+}
+
+// The JSON paths of the values dropped by a conversion to OpenRTB 2.5.
+struct Dropped(Vec<String>);
+
+impl Dropped {
+    fn field(&mut self, present: bool, path: &str, name: &str) {
+        if present {
+            self.0.push(format!("{}.{}", path, name));
+        }
+    }
+
+    // Moves a value into ext, unless ext already has a different value
+    // under the same key.
+    fn move_to_ext<T: Serialize>(&mut self, ext: &mut Option<Ext>, key: &str, value: T, path: &str) {
+        let value = serde_json::to_value(value).expect("OpenRTB values are always representable as JSON");
+        let ext = ext.get_or_insert_with(Ext::new);
+
+        match ext.get(key) {
+            Some(existing) if *existing != value => self.field(true, path, key),
+            _ => {
+                ext.insert(key.to_string(), value);
+            }
+        }
+    }
+}
+
+fn downgrade_imp(imp: Imp, path: &str, dropped: &mut Dropped) -> v2_5::Imp {
+    dropped.field(imp.rwdd.is_some(), path, "rwdd");
+    dropped.field(imp.ssai.is_some(), path, "ssai");
+    dropped.field(imp.qty.is_some(), path, "qty");
+    dropped.field(imp.dt.is_some(), path, "dt");
+    dropped.field(imp.refresh.is_some(), path, "refresh");
+
+    v2_5::Imp {
+        id: imp.id,
+        metric: imp.metric,
+        banner: imp.banner,
+        video: imp.video.map(|video| downgrade_video(video, &format!("{}.video", path), dropped)),
+        audio: imp.audio.map(|audio| downgrade_audio(audio, &format!("{}.audio", path), dropped)),
+        native: imp.native,
+        pmp: imp.pmp,
+        display_manager: imp.display_manager,
+        display_manager_ver: imp.display_manager_ver,
+        interstitial: imp.interstitial,
+        tag_id: imp.tag_id,
+        bid_floor: imp.bid_floor,
+        bid_floor_cur: imp.bid_floor_cur,
+        secure: imp.secure,
+        exp: imp.exp,
+        ext: imp.ext,
+    }
+}
+
+fn downgrade_video(video: Video, path: &str, dropped: &mut Dropped) -> v2_5::Video {
+    dropped.field(video.plcmt.is_some(), path, "plcmt");
+    dropped.field(video.maxseq.is_some(), path, "maxseq");
+    dropped.field(video.poddur.is_some(), path, "poddur");
+    dropped.field(!video.rqddurs.is_empty(), path, "rqddurs");
+    dropped.field(video.podid.is_some(), path, "podid");
+    dropped.field(video.podseq.is_some(), path, "podseq");
+    dropped.field(video.slotinpod.is_some(), path, "slotinpod");
+    dropped.field(video.mincpmpersec.is_some(), path, "mincpmpersec");
+
+    v2_5::Video {
+        mimes: video.mimes,
+        minduration: video.minduration,
+        maxduration: video.maxduration,
+        protocols: video.protocols,
+        w: video.w,
+        h: video.h,
+        placement: video.placement,
+        linearity: video.linearity,
+        battr: video.battr,
+        minbitrate: video.minbitrate,
+        maxbitrate: video.maxbitrate,
+        boxingallowed: video.boxingallowed,
+        playbackmethod: video.playbackmethod,
+        ext: video.ext,
+    }
+}
+
+fn downgrade_audio(audio: Audio, path: &str, dropped: &mut Dropped) -> v2_5::Audio {
+    dropped.field(audio.poddur.is_some(), path, "poddur");
+    dropped.field(!audio.rqddurs.is_empty(), path, "rqddurs");
+    dropped.field(audio.podid.is_some(), path, "podid");
+    dropped.field(audio.podseq.is_some(), path, "podseq");
+    dropped.field(audio.slotinpod.is_some(), path, "slotinpod");
+    dropped.field(audio.mincpmpersec.is_some(), path, "mincpmpersec");
+
+    v2_5::Audio {
+        mimes: audio.mimes,
+        minduration: audio.minduration,
+        maxduration: audio.maxduration,
+        protocols: audio.protocols,
+        startdelay: audio.startdelay,
+        sequence: audio.sequence,
+        battr: audio.battr,
+        maxextended: audio.maxextended,
+        minbitrate: audio.minbitrate,
+        maxbitrate: audio.maxbitrate,
+        delivery: audio.delivery,
+        companionad: audio.companionad,
+        api: audio.api,
+        companiontype: audio.companiontype,
+        maxseq: audio.maxseq,
+        feed: audio.feed,
+        stitched: audio.stitched,
+        nvol: audio.nvol,
+        ext: audio.ext,
+    }
+}
+
+fn downgrade_site(site: Site, path: &str, dropped: &mut Dropped) -> v2_5::Site {
+    v2_5::Site {
+        id: site.id,
+        name: site.name,
+        domain: site.domain,
+        cat: site.cat,
+        sectioncat: site.sectioncat,
+        pagecat: site.pagecat,
+        page: site.page,
+        referrer: site.referrer,
+        search: site.search,
+        mobile: site.mobile,
+        privacypolicy: site.privacypolicy,
+        publisher: site.publisher,
+        content: site.content.map(|content| downgrade_content(content, &format!("{}.content", path), dropped)),
+        keywords: site.keywords,
+        ext: site.ext,
+    }
+}
+
+fn downgrade_app(app: App, path: &str, dropped: &mut Dropped) -> v2_5::App {
+    v2_5::App {
+        id: app.id,
+        name: app.name,
+        bundle: app.bundle,
+        domain: app.domain,
+        store_url: app.store_url,
+        cat: app.cat,
+        section_cat: app.section_cat,
+        page_cat: app.page_cat,
+        ver: app.ver,
+        publisher: app.publisher,
+        content: app.content.map(|content| downgrade_content(content, &format!("{}.content", path), dropped)),
+        keywords: app.keywords,
+        ext: app.ext,
+    }
+}
+
+fn downgrade_content(content: Content, path: &str, dropped: &mut Dropped) -> v2_5::Content {
+    dropped.field(content.network.is_some(), path, "network");
+    dropped.field(content.channel.is_some(), path, "channel");
+
+    v2_5::Content {
+        id: content.id,
+        episode: content.episode,
+        title: content.title,
+        series: content.series,
+        season: content.season,
+        artist: content.artist,
+        genre: content.genre,
+        album: content.album,
+        isrc: content.isrc,
+        url: content.url,
+        cat: content.cat,
+        prodq: content.prodq,
+        context: content.context,
+        content_rating: content.content_rating,
+        user_rating: content.user_rating,
+        qag_media_rating: content.qag_media_rating,
+        keywords: content.keywords,
+        live_stream: content.live_stream,
+        source_relationship: content.source_relationship,
+        len: content.len,
+        language: content.language,
+        embeddable: content.embeddable,
+        ext: content.ext,
+    }
+}
+
+fn downgrade_device(device: Device, path: &str, dropped: &mut Dropped) -> v2_5::Device {
+    dropped.field(device.sua.is_some(), path, "sua");
+
+    v2_5::Device {
+        ua: device.ua,
+        geo: device.geo,
+        dnt: device.dnt,
+        lmt: device.lmt,
+        ip: device.ip,
+        ipv6: device.ipv6,
+        device_type: device.device_type,
+        make: device.make,
+        model: device.model,
+        os: device.os,
+        osv: device.osv,
+        hwv: device.hwv,
+        h: device.h,
+        w: device.w,
+        ppi: device.ppi,
+        pxratio: device.pxratio,
+        js: device.js,
+        geofetch: device.geofetch,
+        flashver: device.flashver,
+        language: device.language,
+        carrier: device.carrier,
+        mccmnc: device.mccmnc,
+        connection_type: device.connection_type,
+        ifa: device.ifa,
+        didsha1: device.didsha1,
+        didmd5: device.didmd5,
+        dpidsha1: device.dpidsha1,
+        dpidmd5: device.dpidmd5,
+        macsha1: device.macsha1,
+        macmd5: device.macmd5,
+        ext: device.ext,
+    }
+}
+
+fn downgrade_user(user: User, path: &str, dropped: &mut Dropped) -> v2_5::User {
+    let mut ext = user.ext;
+    if let Some(consent) = user.consent {
+        dropped.move_to_ext(&mut ext, EXT_CONSENT, consent, path);
+    }
+    if !user.eids.is_empty() {
+        dropped.move_to_ext(&mut ext, EXT_EIDS, user.eids, path);
+    }
+
+    v2_5::User {
+        buyer_uid: user.buyer_uid,
+        customdata: user.customdata,
+        data: user.data,
+        ext,
+        gender: user.gender,
+        geo: user.geo,
+        id: user.id,
+        keywords: user.keywords,
+        yob: user.yob,
+    }
+}
+
+fn downgrade_source(mut source: Source, path: &str, dropped: &mut Dropped) -> Source {
+    if let Some(schain) = source.schain.take() {
+        dropped.move_to_ext(&mut source.ext, EXT_SCHAIN, schain, path);
+    }
+    source
+}
+
+fn downgrade_regulations(mut regs: Regulations, path: &str, dropped: &mut Dropped) -> Regulations {
+    if let Some(gdpr) = regs.gdpr.take() {
+        dropped.move_to_ext(&mut regs.ext, EXT_GDPR, gdpr as u8, path);
+    }
+    if let Some(us_privacy) = regs.us_privacy.take() {
+        dropped.move_to_ext(&mut regs.ext, EXT_US_PRIVACY, us_privacy, path);
+    }
+    if let Some(gpp) = regs.gpp.take() {
+        dropped.move_to_ext(&mut regs.ext, EXT_GPP, gpp, path);
+    }
+    if !regs.gpp_sid.is_empty() {
+        let gpp_sid = std::mem::take(&mut regs.gpp_sid);
+        dropped.move_to_ext(&mut regs.ext, EXT_GPP_SID, gpp_sid, path);
+    }
+    regs
+}
+
+fn downgrade_bid(bid: Bid, path: &str, dropped: &mut Dropped) -> v2_5::Bid {
+    // A single API fits in api, which apis supersedes.
+    let api = match (bid.api, bid.apis.as_slice()) {
+        (api, []) => api,
+        (None, &[api]) => Some(api),
+        (Some(api), &[single]) if api == single => Some(api),
+        (api, _) => {
+            dropped.field(true, path, "apis");
+            api
+        }
+    };
+    dropped.field(bid.cattax.is_some_and(|cattax| cattax != CATTAX_IAB_1_0), path, "cattax");
+    dropped.field(bid.dur.is_some(), path, "dur");
+    dropped.field(bid.mtype.is_some(), path, "mtype");
+    dropped.field(bid.slotinpod.is_some(), path, "slotinpod");
+
+    v2_5::Bid {
+        id: bid.id,
+        imp_id: bid.imp_id,
+        price: bid.price,
+        nurl: bid.nurl,
+        burl: bid.burl,
+        lurl: bid.lurl,
+        adm: bid.adm,
+        adid: bid.adid,
+        adomain: bid.adomain,
+        bundle: bid.bundle,
+        iurl: bid.iurl,
+        cid: bid.cid,
+        cr_id: bid.cr_id,
+        tactic: bid.tactic,
+        cat: bid.cat,
+        attr: bid.attr,
+        api,
+        protocol: bid.protocol,
+        qagmediarating: bid.qagmediarating,
+        language: bid.language,
+        deal_id: bid.deal_id,
+        w: bid.w,
+        h: bid.h,
+        wratio: bid.wratio,
+        hratio: bid.hratio,
+        exp: bid.exp,
+        ext: bid.ext,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+    use serde_json::json;
+
+    #[test]
+    fn upgrade_request() {
+        let request: v2_5::BidRequest = serde_json::from_value(json!({
+            "id": "1",
+            "imp": [{"id": "1", "video": {"mimes": ["video/mp4"]}}],
+            "user": {"ext": {"consent": "CONSENT", "eids": [{"source": "id5-sync.com", "uids": [{"id": "ID5", "atype": 1}]}], "other": 1}},
+            "source": {"ext": {"schain": {"complete": 1, "nodes": [{"asi": "exchange1.com", "sid": "1", "hp": 1}], "ver": "1.0"}}},
+            "regs": {"ext": {"gdpr": 1, "us_privacy": "1YNN", "gpp": "DBABTA~1YNN", "gpp_sid": [6]}}
+        }))
+        .unwrap();
+
+        let upgraded = BidRequest::from(request.clone());
+        assert_eq!(
+            serde_json::to_value(&upgraded).unwrap(),
+            json!({
+                "id": "1",
+                "imp": [{"id": "1", "video": {"mimes": ["video/mp4"]}}],
+                "user": {"consent": "CONSENT", "eids": [{"source": "id5-sync.com", "uids": [{"id": "ID5", "atype": 1}]}], "ext": {"other": 1}},
+                "source": {"schain": {"complete": 1, "nodes": [{"asi": "exchange1.com", "sid": "1", "hp": 1}], "ver": "1.0"}},
+                "regs": {"gdpr": 1, "us_privacy": "1YNN", "gpp": "DBABTA~1YNN", "gpp_sid": [6]}
+            })
+        );
+
+        assert_eq!(v2_5::BidRequest::try_from(upgraded), Ok(request));
+    }
+
+    #[test]
+    fn upgrade_keeps_malformed_ext() {
+        let request: v2_5::BidRequest = serde_json::from_value(json!({
+            "id": "1",
+            "imp": [],
+            "user": {"ext": {"eids": "invalid"}},
+            "source": {"ext": {"schain": {"nodes": "invalid"}}},
+            "regs": {"ext": {"gdpr": "yes"}}
+        }))
+        .unwrap();
+
+        let upgraded = BidRequest::from(request.clone());
+        assert_eq!(upgraded.user.as_ref().unwrap().eids, vec![]);
+        assert_eq!(upgraded.source.as_ref().unwrap().schain, None);
+        assert_eq!(upgraded.regulations.as_ref().unwrap().gdpr, None);
+        assert_eq!(v2_5::BidRequest::try_from(upgraded), Ok(request));
+    }
+
+    #[test]
+    fn downgrade_request_reports_unrepresentable() {
+        let request: BidRequest = serde_json::from_value(json!({
+            "id": "1",
+            "imp": [{"id": "1", "rwdd": 1, "video": {"mimes": ["video/mp4"], "plcmt": 1, "podid": "1"}}],
+            "app": {"id": "1", "content": {"network": {"name": "ABC"}}},
+            "device": {"sua": {"mobile": 1}},
+            "user": {"consent": "CONSENT", "ext": {"consent": "OTHER"}},
+            "regs": {"gdpr": 0}
+        }))
+        .unwrap();
+
+        let (downgraded, paths) = request.clone().into_v2_5_lossy();
+        assert_eq!(
+            paths,
+            vec![
+                "$.imp[0].rwdd",
+                "$.imp[0].video.plcmt",
+                "$.imp[0].video.podid",
+                "$.app.content.network",
+                "$.device.sua",
+                "$.user.consent",
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&downgraded).unwrap(),
+            json!({
+                "id": "1",
+                "imp": [{"id": "1", "video": {"mimes": ["video/mp4"]}}],
+                "app": {"id": "1", "content": {}},
+                "device": {},
+                "user": {"ext": {"consent": "OTHER"}},
+                "regs": {"ext": {"gdpr": 0}}
+            })
+        );
+
+        let error = v2_5::BidRequest::try_from(request).unwrap_err();
+        assert_eq!(error.paths.len(), 6);
+        assert!(error.to_string().starts_with("OpenRTB 2.5 cannot represent $.imp[0].rwdd, "));
+    }
+
+    #[test]
+    fn convert_response() {
+        let response: v2_5::BidResponse = serde_json::from_value(json!({
+            "id": "1",
+            "seatbid": [{"seat": "a", "bid": [{"id": "1", "impid": "1", "price": 1.5, "api": 3}]}]
+        }))
+        .unwrap();
+
+        let upgraded = BidResponse::from(response.clone());
+        assert_eq!(upgraded.seat_bid[0].bid[0].api, Some(3));
+        assert_eq!(v2_5::BidResponse::try_from(upgraded), Ok(response));
+
+        let response: BidResponse = serde_json::from_value(json!({
+            "id": "1",
+            "seatbid": [{"bid": [
+                {"id": "1", "impid": "1", "price": 1, "apis": [7], "cattax": 1},
+                {"id": "2", "impid": "1", "price": 1, "apis": [5, 7], "mtype": 2, "cattax": 2}
+            ]}]
+        }))
+        .unwrap();
+
+        let (downgraded, paths) = response.into_v2_5_lossy();
+        assert_eq!(downgraded.seat_bid[0].bid[0].api, Some(7));
+        assert_eq!(
+            paths,
+            vec!["$.seatbid[0].bid[1].apis", "$.seatbid[0].bid[1].cattax", "$.seatbid[0].bid[1].mtype"]
+        );
+    }
+}
@@ -0,0 +1,181 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::serde_utils;
+
+use super::audio::Audio;
+use super::qty::Qty;
+use super::refresh::Refresh;
+use super::video::Video;
+use crate::v2_5::{Banner, Metric, Native, PMP};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Imp {
+    pub id: String,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub metric: Vec<Metric>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub banner: Option<Banner>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video: Option<Video>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<Audio>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub native: Option<Native>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pmp: Option<PMP>,
+
+    #[serde(rename = "displaymanager", skip_serializing_if = "Option::is_none")]
+    pub display_manager: Option<String>,
+
+    #[serde(rename = "displaymanagerver", skip_serializing_if = "Option::is_none")]
+    pub display_manager_ver: Option<String>,
+
+    #[serde(
+        default,
+        rename = "instl",
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub interstitial: Option<bool>,
+
+    #[serde(rename = "tagid", skip_serializing_if = "Option::is_none")]
+    pub tag_id: Option<String>,
+
+    #[serde(rename = "bidfloor", skip_serializing_if = "Option::is_none")]
+    pub bid_floor: Option<f32>,
+
+    #[serde(rename = "bidfloorcur", skip_serializing_if = "Option::is_none")]
+    pub bid_floor_cur: Option<String>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub secure: Option<bool>,
+
+    /// Advisory as to the number of seconds that may elapse
+    /// between the auction and the actual impression.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<u64>,
+
+    /// Indicates whether the user receives a reward for viewing the
+    /// creative, where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub rwdd: Option<bool>,
+
+    /// Indicates if server-side ad insertion (e.g., stitching an ad into an
+    /// audio or video stream) is in use, where 0 = status unknown,
+    /// 1 = all client-side, 2 = assets stitched server-side but tracking
+    /// pixels fired client-side and 3 = all server-side.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssai: Option<u32>,
+
+    /// A Qty object (Section 3.2.31) indicating the impression multiplier,
+    /// e.g. for digital out-of-home screens seen by several people.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qty: Option<Qty>,
+
+    /// Timestamp when the item is estimated to be fulfilled (e.g. when a
+    /// DOOH impression will be displayed) in Unix format (i.e.,
+    /// milliseconds since the epoch).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dt: Option<f64>,
+
+    /// Details about ad slots being refreshed automatically (Section 3.2.33).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh: Option<Refresh>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+impl Default for Imp {
+    fn default() -> Self {
+        Imp {
+            id: "".to_string(),
+            metric: vec![],
+            banner: None,
+            video: None,
+            audio: None,
+            native: None,
+            pmp: None,
+            display_manager: None,
+            display_manager_ver: None,
+            interstitial: None,
+            tag_id: None,
+            bid_floor: None,
+            bid_floor_cur: None,
+            secure: None,
+            exp: None,
+            rwdd: None,
+            ssai: None,
+            qty: None,
+            dt: None,
+            refresh: None,
+            ext: None,
+        }
+    }
+}
+
+impl Imp {
+    /// Returns the first metric of the given type (e.g., “viewability”), if any.
+    pub fn find_metric(&self, type_: &str) -> Option<&Metric> {
+        self.metric.iter().find(|metric| metric.type_ == type_)
+    }
+
+    /// Returns the value of the first metric of the given type, if any.
+    pub fn metric_value(&self, type_: &str) -> Option<f32> {
+        self.find_metric(type_).map(|metric| metric.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn deserialize_2_6_fields() {
+        let i: Imp = serde_json::from_str(
+            r#"{
+                "id": "1",
+                "video": {"mimes": ["video/mp4"], "plcmt": 1},
+                "rwdd": 1,
+                "ssai": 3,
+                "qty": {"multiplier": 12.5, "sourcetype": 1, "vendor": "measurer.com"},
+                "dt": 1700000000000,
+                "refresh": {"refsettings": [{"reftype": 1, "minint": 30}], "count": 2}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(i.rwdd, Some(true));
+        assert_eq!(i.ssai, Some(3));
+        assert_eq!(i.qty.as_ref().unwrap().multiplier, 12.5);
+        assert_eq!(i.qty.as_ref().unwrap().vendor, Some("measurer.com".to_string()));
+        assert_eq!(i.dt, Some(1_700_000_000_000.0));
+        assert_eq!(i.refresh.as_ref().unwrap().count, Some(2));
+        assert_eq!(i.refresh.as_ref().unwrap().refsettings[0].minint, Some(30));
+        assert_eq!(i.video.as_ref().unwrap().plcmt, Some(1));
+    }
+}
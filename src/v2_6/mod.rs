@@ -0,0 +1,59 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// OpenRTB 2.6
+//
+// Objects that OpenRTB 2.6 leaves unchanged are those of OpenRTB 2.5, and
+// are re-exported from there. Objects that gain fields are defined here, as
+// are those that hold one of them (e.g. App and Site for Content). The
+// Source and Regs objects of the v2_5 module already carry the fields 2.6
+// promotes out of ext, and the EID objects 2.5 requests carry in User.ext
+// are those of 2.6. Requests and responses convert to and from their
+// OpenRTB 2.5 counterparts.
+
+mod app;
+mod audio;
+mod bid;
+mod bid_request;
+mod bid_response;
+mod content;
+mod convert;
+mod device;
+mod imp;
+mod network;
+mod qty;
+mod refresh;
+mod seat_bid;
+mod site;
+mod user;
+mod user_agent;
+mod video;
+
+pub use self::app::*;
+pub use self::audio::*;
+pub use self::bid::*;
+pub use self::bid_request::*;
+pub use self::bid_response::*;
+pub use self::content::*;
+pub use self::convert::*;
+pub use self::device::*;
+pub use self::imp::*;
+pub use self::network::*;
+pub use self::qty::*;
+pub use self::refresh::*;
+pub use self::seat_bid::*;
+pub use self::site::*;
+pub use self::user::*;
+pub use self::user_agent::*;
+pub use self::video::*;
+pub use crate::serde_utils::Ext;
+pub use crate::v2_5::category::*;
+pub use crate::v2_5::{
+    AdPosition, AuctionType, Banner, BannerAdType, Data, Deal, Eid, Format, Gender, Geo, LossReason, Metric, Native, NoBidReason,
+    ParseSupplyChainError, Publisher, Regulations, Segment, Source, SupplyChain, SupplyChainNode, Uid, PMP,
+};
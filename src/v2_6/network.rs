@@ -0,0 +1,57 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::serde_utils;
+
+// 3.2.23 Object: Network
+//
+// Details about the network the content is on, e.g. a TV network like
+// “ABC”, the parent entity of channels.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Network {
+    // A unique identifier assigned by the publisher. This may not be a
+    // unique identifier across all supply sources.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    // Network the content is on (e.g., a TV network like “ABC”).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    // The primary domain of the network (e.g. “abc.com”).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+
+    // Placeholder for exchange-specific extensions to OpenRTB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// 3.2.24 Object: Channel
+//
+// Details about the channel the content is on, e.g. a local channel like
+// “WABC-TV”, within a network.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Channel {
+    // A unique identifier assigned by the publisher. This may not be a
+    // unique identifier across all supply sources.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    // Channel the content is on (e.g., a local channel like “WABC-TV”).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    // The primary domain of the channel (e.g. “abc7ny.com”).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+
+    // Placeholder for exchange-specific extensions to OpenRTB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
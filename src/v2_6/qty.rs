@@ -0,0 +1,36 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::serde_utils;
+
+// 3.2.31 Object: Qty
+//
+// A programmatic impression is often referred to as a ‘spot’ in digital
+// out-of-home and CTV, with an impression being a unique member of the
+// audience viewing it. This object carries the multiplier of the spot, i.e.
+// the number of impressions it represents.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Qty {
+    // Required. The quantity of billable events which will be deemed to have
+    // occurred if this item is purchased.
+    pub multiplier: f64,
+
+    // The source of the quantity measurement, where 1 = measurement vendor
+    // provided, 2 = publisher provided and 3 = exchange provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sourcetype: Option<u32>,
+
+    // The top level business domain name of the measurement vendor
+    // providing the quantity measurement. Required if sourcetype is 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vendor: Option<String>,
+
+    // Placeholder for exchange-specific extensions to OpenRTB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
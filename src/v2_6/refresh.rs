@@ -0,0 +1,50 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::serde_utils;
+
+// 3.2.33 Object: Refresh
+//
+// Information on how often and what triggers an ad slot being refreshed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Refresh {
+    // A RefSettings object (Section 3.2.34) describing the mechanics of how
+    // an ad placement automatically refreshes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub refsettings: Vec<RefSettings>,
+
+    // The number of times this ad slot had been refreshed since last page
+    // load.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+
+    // Placeholder for exchange-specific extensions to OpenRTB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// 3.2.34 Object: RefSettings
+//
+// Information on how often and what triggers an ad slot being refreshed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct RefSettings {
+    // The type of the declared auto refresh, where 0 = unknown, 1 = user
+    // action, 2 = event and 3 = time. Refer to List: Auto Refresh Triggers
+    // in AdCOM 1.0.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reftype: Option<u32>,
+
+    // The minimum refresh interval in seconds. This applies to all refresh
+    // types.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minint: Option<u32>,
+
+    // Placeholder for exchange-specific extensions to OpenRTB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
@@ -0,0 +1,39 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::bid::Bid;
+use crate::serde_utils;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SeatBid {
+    // todo: require 1+ bid somehow
+    pub bid: Vec<Bid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seat: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+impl SeatBid {
+    pub fn new() -> SeatBid {
+        SeatBid {
+            bid: vec![],
+            seat: None,
+            group: None,
+            ext: None,
+        }
+    }
+}
+
+impl Default for SeatBid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::privacy::tcf_v2::{TcString, TcfError};
+use crate::serde_utils::{self, EXT_CONSENT};
+use crate::v2_5::eid::{self, Uid};
+use crate::v2_5::{Data, Eid, Gender, Geo};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct User {
+    // A DSP's UID for this user (see user matching for format).
+    #[serde(rename = "buyeruid", skip_serializing_if = "Option::is_none")]
+    pub buyer_uid: Option<String>,
+    // When GDPR regulations are in effect, the consent string required by
+    // the IAB Transparency & Consent Framework.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consent: Option<String>,
+    // Optional feature to pass bidder data that was set in the exchange's cookie.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customdata: Option<String>,
+    // This object used by publishers to pass additional attributes about the user or content.
+    #[serde(skip_serializing_if = "serde_utils::is_none_or_empty")]
+    pub data: Option<Vec<Data>>,
+    // Details for support of a standard protocol for multiple third party
+    // identity providers (Section 3.2.27).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub eids: Vec<Eid>,
+    // Placeholder for exchange-specific extensions to OpenRTB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+    // Gender, where "M" = male, "F" = female, "O" = known to be other (i.e., omitted is unknown).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gender: Option<Gender>,
+    // Location of the user's home base defined by a Geo object (Section 3.2.19). This is not necessarily their current location.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geo: Option<Geo>,
+    // The Index static identifier for this user (contains only alphanumeric or the following characters: @ - . _ ).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    // Comma separated list of keywords, interests, or intent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+    // Year of birth as a 4-digit integer.
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "crate::serde_utils::int_or_string_to_u32")]
+    pub yob: Option<u32>,
+}
+
+impl User {
+    /// The TCF consent string from `consent`, or failing that from
+    /// `ext.consent`.
+    pub fn consent_string(&self) -> Option<&str> {
+        match self.consent {
+            Some(ref consent) => Some(consent),
+            None => self.ext.as_ref()?.get(EXT_CONSENT)?.as_str(),
+        }
+    }
+
+    /// Decodes the TCF v2 consent string, if there is one.
+    pub fn tcf_consent(&self) -> Option<Result<TcString, TcfError>> {
+        self.consent_string().map(TcString::parse)
+    }
+
+    /// The extended identifiers from `eids`, or failing that from
+    /// `ext.eids`.
+    pub fn eids(&self) -> Vec<Eid> {
+        if self.eids.is_empty() {
+            eid::ext_eids(&self.ext)
+        } else {
+            self.eids.clone()
+        }
+    }
+
+    /// Stores the extended identifiers in `eids`, removing any left in
+    /// `ext.eids`.
+    pub fn set_eids(&mut self, eids: Vec<Eid>) {
+        self.eids = eids;
+        eid::remove_ext_eids(&mut self.ext);
+    }
+
+    /// The extended identifiers from the given source domain (e.g. “uid2.com”).
+    pub fn eid(&self, source: &str) -> Option<Eid> {
+        self.eids().into_iter().find(|eid| eid.is_from(source))
+    }
+
+    /// The user IDs of the given agent type, across all sources.
+    pub fn uids_with_atype(&self, atype: u32) -> Vec<Uid> {
+        eid::uids_with_atype(self.eids(), atype)
+    }
+
+    /// Removes the extended identifiers from the given source domains from
+    /// both `eids` and `ext.eids`, and returns how many were removed.
+    pub fn strip_eids(&mut self, sources: &[&str]) -> usize {
+        eid::strip_eids(&mut self.eids, sources) + eid::strip_ext_eids(&mut self.ext, sources)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn tcf_consent() {
+        let u: User = serde_json::from_str(r#"{"id":"1","consent":"COvFyGBOvFyGBAbAAAENAPCAAOAAAAAAAAAAAEEUACCKAAA"}"#)
+            .unwrap();
+
+        let tc = u.tcf_consent().unwrap().unwrap();
+        assert!(tc.vendor_consent(8));
+        assert!(!tc.vendor_consent(9));
+
+        let u: User = serde_json::from_str(r#"{"ext":{"consent":"!"}}"#).unwrap();
+        assert_eq!(u.tcf_consent(), Some(Err(TcfError::InvalidCharacter('!'))));
+
+        assert_eq!(User::default().tcf_consent(), None);
+    }
+
+    #[test]
+    fn deserialize_eids() {
+        let u: User = serde_json::from_str(
+            r#"{"eids":[{"source":"liveramp.com","uids":[{"id":"XY1000bIVBVah9ium-sZ3ykhPiXQbEcUpn4GjCtxrrw2BRDGM","atype":3}]}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(u.eids[0].source, "liveramp.com");
+        assert_eq!(u.eids[0].uids[0].atype, Some(3));
+    }
+
+    #[test]
+    fn eids_from_either_location() {
+        let mut u: User = serde_json::from_str(
+            r#"{"eids":[{"source":"id5-sync.com","uids":[{"id":"ID5*abc","atype":1}]}],"ext":{"eids":[{"source":"uid2.com","uids":[{"id":"A4AAAAs","atype":3}]}]}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(u.eids().len(), 1);
+        assert!(u.eid("ID5-Sync.com").is_some());
+        assert_eq!(u.eid("uid2.com"), None);
+
+        assert_eq!(u.strip_eids(&["id5-sync.com", "uid2.com"]), 2);
+        assert_eq!(u, User::default());
+
+        let mut u: User =
+            serde_json::from_str(r#"{"ext":{"eids":[{"source":"uid2.com","uids":[{"id":"A4AAAAs","atype":3}]}]}}"#).unwrap();
+        assert_eq!(u.uids_with_atype(3)[0].id, "A4AAAAs");
+
+        let eids = u.eids();
+        u.set_eids(eids);
+        assert_eq!(
+            r#"{"eids":[{"source":"uid2.com","uids":[{"id":"A4AAAAs","atype":3}]}]}"#,
+            serde_json::to_string(&u).unwrap()
+        );
+    }
+}
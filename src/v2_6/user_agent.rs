@@ -0,0 +1,100 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::serde_utils;
+
+// 3.2.29 Object: UserAgent
+//
+// Structured user agent information, which can be used when a client
+// supports User-Agent Client Hints. If both device.ua and device.sua are
+// present, bidders should use device.sua.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct UserAgent {
+    // Each BrandVersion object (Section 3.2.30) identifies a browser or
+    // similar software component, from Sec-CH-UA-Full-Version-List.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub browsers: Vec<BrandVersion>,
+
+    // A BrandVersion object (Section 3.2.30) that identifies the user agent’s
+    // execution platform / OS, from Sec-CH-UA-Platform and
+    // Sec-CH-UA-Platform-Version.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<BrandVersion>,
+
+    // 1 if the agent prefers a “mobile” version of the content, 0 otherwise,
+    // from Sec-CH-UA-Mobile.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub mobile: Option<bool>,
+
+    // Device’s major binary architecture, e.g. “x86” or “arm”, from
+    // Sec-CH-UA-Arch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub architecture: Option<String>,
+
+    // Device’s bitness, e.g. “64” for 64-bit architecture, from
+    // Sec-CH-UA-Bitness.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitness: Option<String>,
+
+    // Device model, from Sec-CH-UA-Model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    // The source of data used to create this object, where 0 = not
+    // specified, 1 = low-entropy client hints, 2 = high-entropy client
+    // hints and 3 = parsed from the User-Agent header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<u32>,
+
+    // Placeholder for exchange-specific extensions to OpenRTB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+// 3.2.30 Object: BrandVersion
+//
+// Further identification based on User-Agent Client Hints, used for the
+// browsers and the platform of a UserAgent.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct BrandVersion {
+    // Required. A brand identifier, e.g. “Chrome” or “Windows”.
+    pub brand: String,
+
+    // A sequence of version components, in descending hierarchical order
+    // (major, minor, micro, …).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub version: Vec<String>,
+
+    // Placeholder for exchange-specific extensions to OpenRTB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn round_trip() {
+        let json = r#"{"browsers":[{"brand":"Chromium","version":["118","0","5993"]},{"brand":"Not=A?Brand","version":["99"]}],"platform":{"brand":"macOS","version":["14","0","0"]},"mobile":0,"architecture":"arm","bitness":"64","source":2}"#;
+        let sua: UserAgent = serde_json::from_str(json).unwrap();
+
+        assert_eq!(sua.browsers[0].brand, "Chromium");
+        assert_eq!(sua.browsers[0].version, vec!["118", "0", "5993"]);
+        assert_eq!(sua.platform.as_ref().unwrap().brand, "macOS");
+        assert_eq!(sua.mobile, Some(false));
+        assert_eq!(sua.source, Some(2));
+        assert_eq!(serde_json::to_string(&sua).unwrap(), json);
+    }
+}
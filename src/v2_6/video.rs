@@ -0,0 +1,155 @@
+// Copyright (c) 2018 The openrtb-rust authors
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::serde_utils;
+
+/// This object represents an in-stream video impression.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Video {
+    /// Content MIME types supported (e.g., “video/x-ms-wmv”,“video/mp4”).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mimes: Vec<String>,
+    /// Minimum video ad duration in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minduration: Option<u32>,
+    /// Maximum video ad duration in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxduration: Option<u32>,
+    /// Array of supported video protocols.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protocols: Vec<u32>,
+    /// Width of the video player in device independent pixels (DIPS).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub w: Option<u32>,
+    /// Height of the video player in device independent pixels (DIPS).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub h: Option<u32>,
+    /// #### Placement type for the impression:
+    ///
+    /// - 1: In-Stream
+    ///
+    ///   Played before, during or after the streaming video content that the consumer has requested
+    ///   (e.g., Pre-roll, Mid-roll, Post-roll).
+    /// ***
+    /// - 2: In-Banner
+    ///
+    ///   Exists within a web banner that leverages the banner space to deliver a video experience as
+    ///   opposed to another static or rich media format. The format relies on the existence of display
+    ///   ad inventory on the page for its delivery.
+    /// ***
+    /// - 3: In-Article
+    ///
+    ///   Loads and plays dynamically between paragraphs of editorial content; existing as a standalone
+    ///   branded message.
+    /// ***
+    /// - 4: In-Feed
+    ///
+    ///   Found in content, social, or product feeds.
+    /// ***
+    /// - 5: Interstitial/Slider/Floating
+    ///
+    ///   Covers the entire or a portion of screen area, but is always on screen while displayed (i.e.
+    ///   cannot be scrolled out of view). Note that a full-screen interstitial (e.g., in mobile) can be
+    ///   distinguished from a floating/slider unit by the `imp.instl` field
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placement: Option<u32>,
+    /// Video placement type for the impression, where 1 = instream,
+    /// 2 = accompanying content, 3 = interstitial and 4 = no content
+    /// (standalone). Supersedes placement, which OpenRTB 2.6 deprecates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plcmt: Option<u32>,
+    /// Indicates if the impression must be linear, nonlinear, etc.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub linearity: Option<u32>,
+    /// Blocked creative attributes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub battr: Vec<u64>,
+    /// Minimum bit rate in Kbps.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minbitrate: Option<u32>,
+    /// Maximum bit rate in Kbps.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxbitrate: Option<u32>,
+    /// Indicates if letter-boxing of 4:3 content into
+    /// a 16:9 window is allowed, where 0 = no, 1 = yes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serde_utils::mbool_to_u8",
+        deserialize_with = "serde_utils::u8_to_mbool"
+    )]
+    pub boxingallowed: Option<bool>,
+    /// Playback methods that may be in use.
+    /// If none are specified, any method may be used.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub playbackmethod: Vec<u32>,
+    /// The maximum number of ads that can be played in an ad pod.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxseq: Option<u32>,
+    /// Indicates the total amount of time in seconds that advertisers may
+    /// fill for a “dynamic” video ad pod, or the length of a “structured”
+    /// one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub poddur: Option<u32>,
+    /// Precise acceptable durations for video creatives in seconds, for
+    /// e.g. live TV where ads must fill their slot exactly. Mutually
+    /// exclusive with minduration and maxduration.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rqddurs: Vec<u32>,
+    /// Unique identifier indicating that an impression opportunity belongs
+    /// to a video ad pod. Impressions with the same podid are in the same
+    /// pod.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub podid: Option<String>,
+    /// The sequence (position) of the video ad pod within a content stream,
+    /// where 0 = any, 1 = first pod and -1 = last pod.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub podseq: Option<i32>,
+    /// For video ad pods, the seller's guidance on where the impression may
+    /// be positioned within the pod, where 0 = any, 1 = first, -1 = last
+    /// and 2 = first or last.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slotinpod: Option<i32>,
+    /// Minimum CPM per second, the price floor for the “dynamic” portion of
+    /// a video ad pod relative to the duration of bids.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mincpmpersec: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_utils::Ext>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn deserialize_dynamic_pod() {
+        let json = r#"{
+            "mimes": ["video/mp4"],
+            "plcmt": 1,
+            "maxseq": 4,
+            "poddur": 120,
+            "rqddurs": [15, 30],
+            "podid": "pod-1",
+            "podseq": -1,
+            "slotinpod": 2,
+            "mincpmpersec": 0.25
+        }"#;
+        let video: Video = serde_json::from_str(json).unwrap();
+
+        assert_eq!(video.plcmt, Some(1));
+        assert_eq!(video.maxseq, Some(4));
+        assert_eq!(video.poddur, Some(120));
+        assert_eq!(video.rqddurs, vec![15, 30]);
+        assert_eq!(video.podid, Some("pod-1".to_string()));
+        assert_eq!(video.podseq, Some(-1));
+        assert_eq!(video.slotinpod, Some(2));
+        assert_eq!(video.mincpmpersec, Some(0.25));
+    }
+}
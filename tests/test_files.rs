@@ -1,8 +1,15 @@
 extern crate openrtb;
 extern crate serde_json;
 
+use std::convert::TryFrom;
+
 use serde_json::json;
-use openrtb::v2_5::{AuctionType, BidRequest, BidResponse};
+use openrtb::native::v1_2::{
+    DataAssetType, ImageAssetType, Request as NativeRequest, Response as NativeResponse,
+};
+use openrtb::v2_5::{AuctionType, BidRequest, BidResponse, Rejection, RejectionReason};
+use openrtb::openrtb3;
+use openrtb::v2_6;
 
 #[test]
 fn brandscreen_request_mobile() {
@@ -12,8 +19,8 @@ fn brandscreen_request_mobile() {
     // Test some assorted values.
     assert_eq!(request.id, "IxexyLDIIk");
     assert_eq!(request.auction_type, Some(AuctionType::SecondPricePlus));
-    assert_eq!(request.user.is_some(), true);
-    assert_eq!(request.all_imps, false);
+    assert!(request.user.is_some());
+    assert!(!request.all_imps);
     assert_eq!(request.imp.len(), 1);
     assert_eq!(request.imp[0].id, "1");
 }
@@ -26,7 +33,7 @@ fn brandscreen_request_pc_multi() {
     // Test some assorted values.
     assert_eq!(request.id, "8652a8680db33faabbf3fa76150f35df50a67060");
     assert_eq!(request.auction_type, Some(AuctionType::FirstPrice));
-    assert_eq!(request.user.is_some(), true);
+    assert!(request.user.is_some());
     assert_eq!(request.imp.len(), 2);
     assert_eq!(request.imp[0].id, "121-dt1");
     assert_eq!(request.imp[1].id, "121-dt2");
@@ -40,7 +47,7 @@ fn brandscreen_request_pc_single() {
     // Test some assorted values.
     assert_eq!(request.id, "80ce30c53c16e6ede735f123ef6e32361bfc7b22");
     assert_eq!(request.auction_type, Some(AuctionType::FirstPrice));
-    assert_eq!(request.user.is_some(), true);
+    assert!(request.user.is_some());
     assert_eq!(
         request.user.unwrap().id,
         Some("55816b39711f9b5acf3b90e313ed29e51665623f".to_string())
@@ -61,6 +68,24 @@ fn brandscreen_response_mobile() {
     assert_eq!(response.currency, Some("USD".to_string()));
 }
 
+#[test]
+fn brandscreen_response_mobile_rejections() {
+    let request: BidRequest = serde_json::from_str(include_str!("files/brandscreen/request-mobile.json")).unwrap();
+    let mut response: BidResponse =
+        serde_json::from_str(include_str!("files/brandscreen/response-mobile.json")).unwrap();
+
+    assert_eq!(response.rejections(&request), vec![]);
+
+    response.seat_bid[0].bid[0].imp_id = "2".to_string();
+    assert_eq!(
+        response.rejections(&request),
+        vec![Rejection {
+            path: "$.seatbid[0].bid[0]".to_string(),
+            reason: RejectionReason::UnknownImp("2".to_string()),
+        }]
+    );
+}
+
 #[test]
 fn brandscreen_response_pc_multi() {
     let test_data = include_str!("files/brandscreen/response-pc-multi.json");
@@ -102,7 +127,301 @@ fn aps() {
 
     // Test some assorted values.
     assert_eq!(request.id, response.seat_bid[0].bid[0].id);
-    assert_eq!(request.regulations.unwrap().coppa, false);
+    let regulations = request.regulations.unwrap();
+    assert!(!regulations.coppa);
+    assert_eq!(regulations.us_privacy_string(), Some("1---"));
+    assert_eq!(regulations.gdpr_applies(), None);
+}
+
+#[test]
+fn iab_request_pmp_direct_deal() {
+    let test_data = include_str!("files/iab/request-pmp-direct-deal.json");
+    let request = test_v2_5_request_serialization_round_trip(test_data);
+
+    // Test some assorted values.
+    let pmp = request.imp[0].pmp.as_ref().unwrap();
+    assert!(pmp.private_auction);
+    assert_eq!(pmp.deals.len(), 2);
+    assert_eq!(pmp.deals[0].id, "AB-Agency1-0001");
+    assert_eq!(pmp.deals[0].auction_type, Some(AuctionType::FirstPrice));
+    assert_eq!(pmp.deals[0].bid_floor, Some(2.5));
+    assert_eq!(pmp.deals[0].seat_whitelist, vec!["Agency1".to_string()]);
+    assert_eq!(pmp.deals[1].id, "XY-Agency2-0003");
+    assert_eq!(pmp.deals[1].auction_type, Some(AuctionType::SecondPricePlus));
+}
+
+#[test]
+fn iab_request_pmp_open_auction() {
+    let test_data = include_str!("files/iab/request-pmp-open-auction.json");
+    let request = test_v2_5_request_serialization_round_trip(test_data);
+
+    // Test some assorted values.
+    let pmp = request.imp[0].pmp.as_ref().unwrap();
+    assert!(!pmp.private_auction);
+    assert_eq!(pmp.deals.len(), 1);
+    assert_eq!(pmp.deals[0].bid_floor_cur, Some("EUR".to_string()));
+    assert_eq!(
+        pmp.deals[0].auction_type,
+        Some(AuctionType::ExchangeSpecific(3))
+    );
+    assert_eq!(pmp.deals[0].advertiser_domain_whitelist.len(), 2);
+    assert!(pmp.deals[0].ext.is_some());
+}
+
+#[test]
+fn iab_request_audio_podcast() {
+    let test_data = include_str!("files/iab/request-audio-podcast.json");
+    let request = test_v2_5_request_serialization_round_trip(test_data);
+
+    // Test some assorted values.
+    let audio = request.imp[0].audio.as_ref().unwrap();
+    assert_eq!(audio.mimes, vec!["audio/mpeg".to_string(), "audio/mp4".to_string()]);
+    assert_eq!(audio.minduration, Some(15));
+    assert_eq!(audio.maxduration, Some(60));
+    assert_eq!(audio.startdelay, Some(0));
+    assert_eq!(audio.delivery, vec![1, 2]);
+    assert_eq!(audio.companionad.len(), 2);
+    assert_eq!(audio.companionad[1].w, Some(640));
+    assert_eq!(audio.feed, Some(3));
+    assert_eq!(audio.stitched, Some(true));
+    assert_eq!(audio.nvol, Some(3));
+    assert!(audio.ext.is_some());
+}
+
+#[test]
+fn iab_request_audio_streaming() {
+    let test_data = include_str!("files/iab/request-audio-streaming.json");
+    let request = test_v2_5_request_serialization_round_trip(test_data);
+
+    // Test some assorted values.
+    assert_eq!(request.imp.len(), 2);
+    let audio = request.imp[0].audio.as_ref().unwrap();
+    assert_eq!(audio.startdelay, Some(-1));
+    assert_eq!(audio.stitched, Some(false));
+    let audio = request.imp[1].audio.as_ref().unwrap();
+    assert_eq!(audio.sequence, Some(2));
+    assert_eq!(audio.maxextended, Some(-1));
+}
+
+#[test]
+fn iab_request_native() {
+    let test_data = include_str!("files/iab/request-native.json");
+    let request = test_v2_5_request_serialization_round_trip(test_data);
+
+    // The 1.2 form carries the native request unwrapped.
+    let native = request.imp[0].native.as_ref().unwrap();
+    let native_request = native.parse_request().unwrap();
+    assert_eq!(native_request.ver, Some("1.2".to_string()));
+    assert_eq!(native_request.assets.len(), 4);
+    assert_eq!(
+        native_request.data_asset(&DataAssetType::Description).unwrap().id,
+        127
+    );
+
+    // Older exchanges still wrap it in a "native" object.
+    let native = request.imp[1].native.as_ref().unwrap();
+    let native_request = native.parse_request().unwrap();
+    assert_eq!(native_request.ver, Some("1.1".to_string()));
+    assert_eq!(
+        native_request.assets[1].img.as_ref().unwrap().type_,
+        Some(ImageAssetType::Main)
+    );
+}
+
+#[test]
+fn iab_request_schain() {
+    let ext_style = test_v2_5_request_serialization_round_trip(include_str!(
+        "files/iab/request-schain-ext.json"
+    ));
+    let top_level_style = test_v2_5_request_serialization_round_trip(include_str!(
+        "files/iab/request-schain.json"
+    ));
+
+    // Both locations yield the same supply chain.
+    let schain = ext_style.supply_chain().unwrap();
+    assert_eq!(top_level_style.supply_chain(), Some(schain.clone()));
+    assert!(ext_style.source.unwrap().schain.is_none());
+    assert!(schain.complete);
+    assert_eq!(schain.nodes.len(), 2);
+    assert_eq!(schain.nodes[0].rid, Some("bid-request-1".to_string()));
+    assert_eq!(schain.nodes[1].name, Some("Publisher Inc.".to_string()));
+}
+
+#[test]
+fn iab_request_schain_conversion() {
+    let ext_style: BidRequest = serde_json::from_str(include_str!("files/iab/request-schain-ext.json")).unwrap();
+    let top_level_style: BidRequest = serde_json::from_str(include_str!("files/iab/request-schain.json")).unwrap();
+
+    // Upgrading moves ext.schain to the top-level location, and downgrading
+    // moves it back.
+    let upgraded = v2_6::BidRequest::from(ext_style.clone());
+    assert_eq!(upgraded.source, v2_6::BidRequest::from(top_level_style).source);
+    assert_eq!(BidRequest::try_from(upgraded), Ok(ext_style));
+}
+
+#[test]
+fn iab_request_2_6_ctv_conversion() {
+    let request: v2_6::BidRequest = serde_json::from_str(include_str!("files/iab/request-2.6-ctv.json")).unwrap();
+
+    let (downgraded, dropped) = request.into_v2_5_lossy();
+    assert_eq!(dropped.len(), 18);
+    assert!(dropped.contains(&"$.imp[0].qty".to_string()));
+    assert!(dropped.contains(&"$.device.sua".to_string()));
+
+    let user = downgraded.user.unwrap();
+    assert!(user.tcf_consent().unwrap().is_ok());
+    assert_eq!(user.ext.unwrap()["eids"][0]["source"], "liveramp.com");
+    assert_eq!(downgraded.regulations.unwrap().gpp_section_ids(), vec![6]);
+}
+
+#[test]
+fn iab_request_native_to_openrtb3() {
+    let request: BidRequest = serde_json::from_str(include_str!("files/iab/request-native.json")).unwrap();
+
+    let body = openrtb3::Body::request(openrtb3::Request::try_from(request).unwrap());
+    let value = serde_json::to_value(&body).unwrap();
+    assert_eq!(value["openrtb"]["ver"], "3.0");
+    assert_eq!(value["openrtb"]["domainspec"], "adcom");
+
+    let request = body.openrtb.request.unwrap();
+    let display = request.item[0].placement().unwrap().display.as_ref().unwrap();
+    let asset = &display.nativefmt.as_ref().unwrap().asset;
+    assert_eq!(asset.len(), 4);
+    assert!(asset.iter().any(|asset| asset.data.as_ref().is_some_and(|data| data.type_ == DataAssetType::Description)));
+}
+
+#[test]
+fn openrtb3_response_to_v2_5() {
+    let body: openrtb3::Body = serde_json::from_str(include_str!("files/openrtb3/response.json")).unwrap();
+
+    let response = BidResponse::from(body.openrtb.response.unwrap());
+    assert_eq!(response.id, "0123456789ABCDEF");
+    assert_eq!(response.currency, Some("USD".to_string()));
+
+    let bid = &response.seat_bid[0].bid[0];
+    assert_eq!(bid.imp_id, "30b31c1e1ca2");
+    assert_eq!(bid.adid, Some("abc123".to_string()));
+    assert_eq!(bid.cr_id, Some("555555".to_string()));
+    assert_eq!((bid.w, bid.h), (Some(320), Some(50)));
+    assert_eq!(bid.lurl, Some("https://buyer.com/loss?reason=${AUCTION_LOSS}&t=1127987134".to_string()));
+    assert!(bid.adm.as_ref().unwrap().contains("t=A7D800F2716DB"));
+}
+
+#[test]
+fn iab_request_2_6_ctv() {
+    let test_data = include_str!("files/iab/request-2.6-ctv.json");
+    let request = test_v2_6_request_serialization_round_trip(test_data);
+
+    // Test some assorted values.
+    let video = request.imp[0].video.as_ref().unwrap();
+    assert_eq!(video.plcmt, Some(1));
+    assert_eq!(video.podid, Some("preroll".to_string()));
+    assert_eq!(request.imp[0].qty.as_ref().unwrap().multiplier, 1.8);
+    assert_eq!(request.imp[1].rwdd, Some(true));
+    assert_eq!(request.imp[1].video.as_ref().unwrap().rqddurs, vec![15, 30]);
+
+    let content = request.app.as_ref().unwrap().content.as_ref().unwrap();
+    assert_eq!(content.network.as_ref().unwrap().name, Some("ABC".to_string()));
+    assert_eq!(content.channel.as_ref().unwrap().name, Some("WABC-TV".to_string()));
+
+    let sua = request.device.as_ref().unwrap().sua.as_ref().unwrap();
+    assert_eq!(sua.platform.as_ref().unwrap().brand, "Tizen");
+
+    let user = request.user.as_ref().unwrap();
+    assert!(user.tcf_consent().unwrap().is_ok());
+    assert_eq!(user.eids[0].uids[0].atype, Some(3));
+
+    assert_eq!(request.supply_chain().unwrap().nodes[0].asi, "exchange1.com");
+    assert!(request.regulations.unwrap().gpp_opt_out_sale());
+}
+
+#[test]
+fn native_request_assets() {
+    let test_data = include_str!("files/native/request-assets.json");
+    let request = test_native_request_serialization_round_trip(test_data);
+
+    // Test some assorted values.
+    assert_eq!(request.assets.len(), 7);
+    assert_eq!(
+        request.assets[1].img.as_ref().unwrap().type_,
+        Some(ImageAssetType::Main)
+    );
+    assert_eq!(
+        request.assets[2].img.as_ref().unwrap().type_,
+        Some(ImageAssetType::Icon)
+    );
+    assert_eq!(
+        request.assets[5].data.as_ref().unwrap().type_,
+        DataAssetType::Unknown(501)
+    );
+    let video = request.assets[6].video.as_ref().unwrap();
+    assert_eq!(video.mimes, vec!["video/mp4".to_string()]);
+    assert_eq!(video.maxduration, Some(30));
+    assert_eq!(video.protocols, vec![2, 3]);
+
+    let sponsored = request.data_asset(&DataAssetType::Sponsored).unwrap();
+    assert_eq!(sponsored.id, 126);
+    assert_eq!(sponsored.data.as_ref().unwrap().len, Some(25));
+    assert!(request.data_asset(&DataAssetType::Rating).is_none());
+    let required: Vec<u64> = request.required_assets().map(|asset| asset.id).collect();
+    assert_eq!(required, vec![123, 126, 127]);
+}
+
+#[test]
+fn native_response_embedded_assets() {
+    let test_data = include_str!("files/native/response-embedded-assets.json");
+    let response = test_native_response_serialization_round_trip(test_data);
+
+    // Test some assorted values.
+    assert_eq!(response.link.url, "http://i.am.a/URL");
+    assert_eq!(response.assets.len(), 7);
+    assert_eq!(
+        response.assets[0].title.as_ref().unwrap().text,
+        "Learn about this awesome thing"
+    );
+    assert_eq!(response.assets[3].data.as_ref().unwrap().value, "My Brand");
+    assert_eq!(
+        response.assets[5].video.as_ref().unwrap().vasttag,
+        "<VAST version=\"2.0\"></VAST>"
+    );
+    let link = response.assets[6].link.as_ref().unwrap();
+    assert_eq!(link.clicktrackers, vec!["http://tracker.com".to_string()]);
+    assert_eq!(link.fallback, Some("http://fallback.com".to_string()));
+    assert_eq!(response.imp_trackers.len(), 2);
+}
+
+#[test]
+fn native_response_assetsurl() {
+    let test_data = include_str!("files/native/response-assetsurl.json");
+    let response = test_native_response_serialization_round_trip(test_data);
+
+    // Test some assorted values.
+    assert!(response.assets.is_empty());
+    assert_eq!(
+        response.assets_url,
+        Some("http://www.myads.com/assets/1234".to_string())
+    );
+    assert_eq!(response.link.clicktrackers.len(), 2);
+    assert_eq!(response.event_trackers.len(), 2);
+    assert!(response.event_trackers[1].custom_data.is_some());
+}
+
+#[test]
+fn native_response_dcourl() {
+    let test_data = include_str!("files/native/response-dcourl.json");
+    let response = test_native_response_serialization_round_trip(test_data);
+
+    // Test some assorted values.
+    assert_eq!(response.dcourl, Some("http://www.myads.com/dco/abcd".to_string()));
+    assert_eq!(response.assets[0].title.as_ref().unwrap().len, Some(11));
+    assert_eq!(
+        response.assets[1].img.as_ref().unwrap().type_,
+        Some(ImageAssetType::Main)
+    );
+    let data = response.assets[2].data.as_ref().unwrap();
+    assert_eq!(data.type_, Some(DataAssetType::Description));
+    assert_eq!(data.len, Some(26));
+    assert!(response.js_tracker.is_some());
 }
 
 #[test]
@@ -155,9 +474,7 @@ fn test_v2_5_request_serialization_round_trip(data: &str) -> BidRequest {
     let request = match serde_json::from_str(data) {
         Ok(request) => request,
         Err(e) => {
-            println!("Failed: {}", e);
-            assert!(false);
-            panic!();
+            panic!("Failed: {}", e);
         }
     };
 
@@ -172,6 +489,22 @@ fn test_v2_5_request_serialization_round_trip(data: &str) -> BidRequest {
     };
 
     assert_eq!(deserialized, request);
+    assert_eq!(request.violations(), vec![]);
+
+    request
+}
+
+fn test_v2_6_request_serialization_round_trip(data: &str) -> v2_6::BidRequest {
+    let request: v2_6::BidRequest = match serde_json::from_str(data) {
+        Ok(request) => request,
+        Err(e) => {
+            panic!("Failed: {}", e);
+        }
+    };
+
+    // Serializing must reproduce the original document exactly, as every field is modelled.
+    let original: serde_json::Value = serde_json::from_str(data).unwrap();
+    assert_eq!(json!(request), original);
 
     request
 }
@@ -180,9 +513,7 @@ fn test_v2_5_response_serialization_round_trip(data: &str) -> BidResponse {
     let request = match serde_json::from_str(data) {
         Ok(request) => request,
         Err(e) => {
-            println!("Failed: {}", e);
-            assert!(false);
-            panic!();
+            panic!("Failed: {}", e);
         }
     };
 
@@ -200,3 +531,33 @@ fn test_v2_5_response_serialization_round_trip(data: &str) -> BidResponse {
 
     request
 }
+
+fn test_native_response_serialization_round_trip(data: &str) -> NativeResponse {
+    let response: NativeResponse = match serde_json::from_str(data) {
+        Ok(response) => response,
+        Err(e) => {
+            panic!("Failed: {}", e);
+        }
+    };
+
+    // Serializing must reproduce the original document exactly, as every field is modelled.
+    let original: serde_json::Value = serde_json::from_str(data).unwrap();
+    assert_eq!(json!(response), original);
+
+    response
+}
+
+fn test_native_request_serialization_round_trip(data: &str) -> NativeRequest {
+    let request: NativeRequest = match serde_json::from_str(data) {
+        Ok(request) => request,
+        Err(e) => {
+            panic!("Failed: {}", e);
+        }
+    };
+
+    // Serializing must reproduce the original document exactly, as every field is modelled.
+    let original: serde_json::Value = serde_json::from_str(data).unwrap();
+    assert_eq!(json!(request), original);
+
+    request
+}